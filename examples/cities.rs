@@ -9,10 +9,11 @@ use std::fs::File;
 
 use csv::Reader;
 use kiddo::float::{distance::SquaredEuclidean, kdtree::KdTree};
+use kiddo::geo::{great_circle_to_squared_chord, lat_lon_to_ecef, squared_chord_to_great_circle};
 use serde::Deserialize;
 
 #[allow(dead_code)]
-pub const EARTH_RADIUS_IN_KM: f32 = 6371.0;
+pub const EARTH_RADIUS_IN_KM: f32 = kiddo::geo::EARTH_RADIUS_IN_KM as f32;
 
 /// Each `CityCsvRecord` corresponds to 1 row in our city source data CSV.
 ///
@@ -51,12 +52,7 @@ impl CityCsvRecord {
 /// We use this when populating our tree, to convert from the `f32` lat/lng data into `f32` (x,y,z) co-ordinates to store in our tree, as well as
 /// allowing us to query the created tree using lat/lng query points.
 pub fn degrees_lat_lng_to_unit_sphere(lat: f32, lng: f32) -> [f32; 3] {
-    // convert from degrees to radians
-    let lat = lat.to_radians();
-    let lng = lng.to_radians();
-
-    // convert from ra/dec to xyz coords on unit sphere
-    [lat.cos() * lng.cos(), lat.cos() * lng.sin(), lat.sin()]
+    lat_lon_to_ecef([lat, lng], 1.0)
 }
 
 #[allow(dead_code)]
@@ -205,14 +201,14 @@ fn main() -> Result<(), Box<dyn Error>> {
 /// our kd-tree) into kilometres for user convenience.
 #[allow(dead_code)]
 pub fn unit_sphere_squared_euclidean_to_kilometres(sq_euc_dist: f32) -> f32 {
-    sq_euc_dist.sqrt() * EARTH_RADIUS_IN_KM
+    squared_chord_to_great_circle(sq_euc_dist, 1.0) * EARTH_RADIUS_IN_KM
 }
 
 /// Converts a value in km to squared euclidean distance on a unit sphere representing Earth.
 ///
 /// This allows us to query using kilometres as distances in our kd-tree.
 pub fn kilometres_to_unit_sphere_squared_euclidean(km_dist: f32) -> f32 {
-    (km_dist / EARTH_RADIUS_IN_KM).powi(2)
+    great_circle_to_squared_chord(km_dist / EARTH_RADIUS_IN_KM, 1.0)
 }
 
 /// Parses CSV data from `file` into a `Vec` of `R`
@@ -0,0 +1,181 @@
+//! Flattened results returned by the `nearest_n_batch` and `within_batch` queries
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+/// The results of a batch of queries, stored in a single flat `Vec` rather than a
+/// separate `Vec` per query.
+///
+/// The results for the `i`th query are `results[offsets[i]..offsets[i + 1]]`, so
+/// `offsets` always has one more element than there were queries.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::KdTree;
+/// use kiddo::SquaredEuclidean;
+///
+/// let mut tree: KdTree<f64, 3> = KdTree::new();
+/// tree.add(&[1.0, 2.0, 5.0], 100);
+/// tree.add(&[2.0, 3.0, 6.0], 101);
+///
+/// let queries = [[1.0, 2.0, 5.1], [2.0, 3.0, 6.1]];
+/// let batch = tree.nearest_n_batch::<SquaredEuclidean>(&queries, 1);
+///
+/// assert_eq!(batch.len(), 2);
+/// assert_eq!(batch[0][0].item, 100);
+/// assert_eq!(batch[1][0].item, 101);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchResults<A, T> {
+    /// the results of every query, one after the other
+    pub results: Vec<NearestNeighbour<A, T>>,
+    /// the index into `results` at which each query's results start,
+    /// followed by the total number of results
+    pub offsets: Vec<usize>,
+}
+
+impl<A: Copy + Send, T: Content> BatchResults<A, T> {
+    /// Runs `query_into` for each of `queries` in parallel, writing the results of
+    /// every query straight into one flat `Vec`.
+    ///
+    /// Each Rayon job reuses a single `Vec` for the results of its queries before
+    /// appending them to its share of the flat results, so there is no per-query allocation.
+    pub(crate) fn from_par_queries<Q, F>(queries: &[Q], query_into: F) -> Self
+    where
+        Q: Sync,
+        F: Fn(&Q, &mut Vec<NearestNeighbour<A, T>>) + Sync,
+    {
+        let (results, lengths) = queries
+            .par_iter()
+            .fold(
+                || (Vec::new(), Vec::new(), Vec::new()),
+                |(mut results, mut lengths, mut query_results), query| {
+                    query_into(query, &mut query_results);
+                    results.extend_from_slice(&query_results);
+                    lengths.push(query_results.len());
+                    (results, lengths, query_results)
+                },
+            )
+            .map(|(results, lengths, _)| (results, lengths))
+            .reduce(
+                || (Vec::new(), Vec::new()),
+                |(mut results, mut lengths), (more_results, more_lengths)| {
+                    results.extend_from_slice(&more_results);
+                    lengths.extend_from_slice(&more_lengths);
+                    (results, lengths)
+                },
+            );
+
+        Self::from_lengths(results, &lengths)
+    }
+
+    /// Builds the offsets from the number of results that each query returned
+    pub(crate) fn from_lengths(results: Vec<NearestNeighbour<A, T>>, lengths: &[usize]) -> Self {
+        let mut offsets = Vec::with_capacity(lengths.len() + 1);
+        offsets.push(0);
+        let mut total = 0;
+        for len in lengths {
+            total += len;
+            offsets.push(total);
+        }
+        debug_assert_eq!(total, results.len());
+
+        BatchResults { results, offsets }
+    }
+}
+
+impl<A, T> BatchResults<A, T> {
+    /// returns the number of queries in the batch
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// returns `true` if the batch contained no queries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// returns the results of the `idx`th query, or `None` if out of range
+    pub fn get(&self, idx: usize) -> Option<&[NearestNeighbour<A, T>]> {
+        if idx >= self.len() {
+            return None;
+        }
+
+        Some(&self.results[self.offsets[idx]..self.offsets[idx + 1]])
+    }
+
+    /// returns an iterator over the results of each query, in the same order as the queries
+    pub fn iter(&self) -> impl Iterator<Item = &[NearestNeighbour<A, T>]> {
+        self.offsets
+            .windows(2)
+            .map(|bounds| &self.results[bounds[0]..bounds[1]])
+    }
+}
+
+impl<A, T> std::ops::Index<usize> for BatchResults<A, T> {
+    type Output = [NearestNeighbour<A, T>];
+
+    fn index(&self, idx: usize) -> &Self::Output {
+        self.get(idx).expect("query index out of range")
+    }
+}
+
+impl<A: PartialEq, T: Content> PartialEq for BatchResults<A, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offsets == other.offsets && self.results == other.results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::batch_results::BatchResults;
+    use crate::nearest_neighbour::NearestNeighbour;
+
+    fn nn(distance: f32, item: u32) -> NearestNeighbour<f32, u32> {
+        NearestNeighbour { distance, item }
+    }
+
+    #[test]
+    fn can_split_flat_results_by_query() {
+        let batch =
+            BatchResults::from_lengths(vec![nn(0.1, 1), nn(0.2, 2), nn(0.3, 3)], &[2, 0, 1]);
+
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.offsets, vec![0, 2, 2, 3]);
+        assert_eq!(batch[0], [nn(0.1, 1), nn(0.2, 2)]);
+        assert!(batch[1].is_empty());
+        assert_eq!(batch.get(2), Some(&[nn(0.3, 3)][..]));
+        assert_eq!(batch.get(3), None);
+        assert_eq!(
+            batch.iter().map(|r| r.len()).collect::<Vec<_>>(),
+            vec![2, 0, 1]
+        );
+    }
+
+    #[test]
+    fn empty_batch_has_no_queries() {
+        let batch: BatchResults<f32, u32> = BatchResults::from_lengths(vec![], &[]);
+
+        assert!(batch.is_empty());
+        assert_eq!(batch.offsets, vec![0]);
+        assert_eq!(batch.iter().count(), 0);
+    }
+
+    #[test]
+    fn can_collect_par_queries_in_query_order() {
+        let queries: Vec<u32> = (0..1000).collect();
+
+        // query i returns i % 4 results, so some queries return nothing
+        let batch = BatchResults::from_par_queries(&queries, |&query, results| {
+            results.clear();
+            results.extend((0..query % 4).map(|i| nn(i as f32, query)));
+        });
+
+        assert_eq!(batch.len(), queries.len());
+        for (query, query_results) in queries.iter().zip(batch.iter()) {
+            assert_eq!(query_results.len(), (query % 4) as usize);
+            assert!(query_results.iter().all(|entry| entry.item == *query));
+        }
+    }
+}
@@ -43,6 +43,35 @@ impl<A, T: Content> From<BestNeighbour<A, T>> for (A, T) {
     }
 }
 
+/// A query result paired with a score, ordered by that score alone. Used as
+/// the heap entry of queries that rank results by something other than
+/// distance, such as `best_n_within_by`.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ScoredNeighbour<S, N> {
+    pub(crate) score: S,
+    pub(crate) neighbour: N,
+}
+
+impl<S: Ord, N> Ord for ScoredNeighbour<S, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.cmp(&other.score)
+    }
+}
+
+impl<S: Ord, N> PartialOrd for ScoredNeighbour<S, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Ord, N> Eq for ScoredNeighbour<S, N> {}
+
+impl<S: Ord, N> PartialEq for ScoredNeighbour<S, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::best_neighbour::BestNeighbour;
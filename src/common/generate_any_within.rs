@@ -0,0 +1,113 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_any_within {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn any_within<D>(&self, query: &[A; K], dist: D::Output) -> bool
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                unsafe {
+                    self.any_within_recurse::<D>(query, dist, self.root_index, 0, &mut off, D::Output::zero(), &mut min, &mut max)
+                }
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn any_within_recurse<D>(
+                &self,
+                query: &[A; K],
+                radius: D::Output,
+                curr_node_idx: IDX,
+                split_dim: usize,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) -> bool
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let mut rd = rd;
+                    let old_off = off[split_dim];
+                    let new_off = query[split_dim].saturating_dist(node.split_val);
+
+                    let [closer_node_idx, further_node_idx] =
+                        if *query.get_unchecked(split_dim) < node.split_val {
+                            [node.left, node.right]
+                        } else {
+                            [node.right, node.left]
+                        };
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    if self.any_within_recurse::<D>(
+                        query,
+                        radius,
+                        closer_node_idx,
+                        next_split_dim,
+                        off,
+                        rd,
+                        min,
+                        max,
+                    ) {
+                        return true;
+                    }
+
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
+
+                    if rd <= radius {
+                        off[split_dim] = new_off;
+                        let found = self.any_within_recurse::<D>(
+                            query,
+                            radius,
+                            further_node_idx,
+                            next_split_dim,
+                            off,
+                            rd,
+                            min,
+                            max,
+                        );
+                        off[split_dim] = old_off;
+                        min[split_dim] = old_min;
+                        max[split_dim] = old_max;
+
+                        return found;
+                    }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+
+                    false
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .take(leaf_node.size.az::<usize>())
+                        .any(|entry| D::dist(query, entry) < radius)
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,142 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_approx_nearest_n {
+    ($comments:tt) => {
+    doc_comment! {
+    concat!$comments,
+    #[inline]
+    pub fn approx_nearest_n<D>(&self, query: &[A; K], qty: usize, epsilon: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
+    where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        if qty == 0 {
+            return Vec::new();
+        }
+
+        let mut off = [A::zero(); K];
+        let mut min = [A::min_value(); K];
+        let mut max = [A::max_value(); K];
+        let mut result: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(qty);
+
+        unsafe {
+            self.approx_nearest_n_recurse::<D>(
+                query,
+                qty,
+                D::Output::one() + epsilon,
+                self.root_index,
+                0,
+                &mut result,
+                &mut off,
+                D::Output::zero(),
+                &mut min,
+                &mut max,
+            )
+        }
+
+        result.into_sorted_vec()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn approx_nearest_n_recurse<D>(
+        &self,
+        query: &[A; K],
+        qty: usize,
+        rd_scale: D::Output,
+        curr_node_idx: IDX,
+        split_dim: usize,
+        results: &mut BinaryHeap<NearestNeighbour<D::Output, T>>,
+        off: &mut [A; K],
+        rd: D::Output,
+        min: &mut [A; K],
+        max: &mut [A; K],
+    ) where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        if is_stem_index(curr_node_idx) {
+            let node = &self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+            let mut rd = rd;
+            let old_off = off[split_dim];
+            let new_off = query[split_dim].saturating_dist(node.split_val);
+
+            let [closer_node_idx, further_node_idx] =
+                if *query.get_unchecked(split_dim) < node.split_val {
+                    [node.left, node.right]
+                } else {
+                    [node.right, node.left]
+                };
+            let next_split_dim = (split_dim + 1).rem(K);
+
+            self.approx_nearest_n_recurse::<D>(
+                query,
+                qty,
+                rd_scale,
+                closer_node_idx,
+                next_split_dim,
+                results,
+                off,
+                rd,
+                min,
+                max,
+            );
+
+            let old_min = min[split_dim];
+            let old_max = max[split_dim];
+            if query[split_dim] < node.split_val {
+                min[split_dim] = node.split_val;
+            } else {
+                max[split_dim] = node.split_val;
+            }
+            rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+            });
+
+            // only visit the further subtree if it could contain an item that is
+            // closer than the current worst result by more than a factor of (1 + epsilon)
+            if results.len() < qty || rd * rd_scale < results.peek().unwrap().distance {
+                off[split_dim] = new_off;
+                self.approx_nearest_n_recurse::<D>(
+                    query,
+                    qty,
+                    rd_scale,
+                    further_node_idx,
+                    next_split_dim,
+                    results,
+                    off,
+                    rd,
+                    min,
+                    max,
+                );
+                off[split_dim] = old_off;
+            }
+            min[split_dim] = old_min;
+            max[split_dim] = old_max;
+        } else {
+            let leaf_node = self
+                .leaves
+                .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+            leaf_node
+                .content_points
+                .iter()
+                .take(leaf_node.size.az::<usize>())
+                .enumerate()
+                .for_each(|(idx, entry)| {
+                    let distance: D::Output = D::dist(query, entry);
+                    let item = unsafe { *leaf_node.content_items.get_unchecked(idx) };
+                    let element = NearestNeighbour { distance, item };
+
+                    if results.len() < qty {
+                        results.push(element)
+                    } else {
+                        let mut top = results.peek_mut().unwrap();
+                        if element.distance < top.distance {
+                            *top = element;
+                        }
+                    }
+                });
+        }
+    }
+}}}
@@ -8,17 +8,21 @@ macro_rules! generate_best_n_within {
     pub fn best_n_within<D>(
         &self,
         query: &[A; K],
-        dist: A,
+        dist: D::Output,
         max_qty: usize,
-    ) -> impl Iterator<Item = BestNeighbour<A, T>>
+    ) -> impl Iterator<Item = BestNeighbour<D::Output, T>>
     where
         D: DistanceMetric<A, K>,
+        D::Output: Axis,
     {
         let mut off = [A::zero(); K];
-        let mut best_items: BinaryHeap<BestNeighbour<A, T>> = BinaryHeap::new();
+        let mut min = [A::min_value(); K];
+        let mut max = [A::max_value(); K];
+        let mut best_items: BinaryHeap<BestNeighbour<D::Output, T>> = BinaryHeap::new();
 
         unsafe {
-            self.best_n_within_recurse::<D>(
+            self.best_n_within_recurse(
+                &StatelessMetric::<D>::new(),
                 query,
                 dist,
                 max_qty,
@@ -26,7 +30,9 @@ macro_rules! generate_best_n_within {
                 0,
                 &mut best_items,
                 &mut off,
-                A::zero(),
+                D::Output::zero(),
+                &mut min,
+                &mut max,
             );
         }
 
@@ -34,18 +40,23 @@ macro_rules! generate_best_n_within {
     }
 
     #[allow(clippy::too_many_arguments)]
-    unsafe fn best_n_within_recurse<D>(
+    pub(crate) unsafe fn best_n_within_recurse<D, N>(
         &self,
+        metric: &D,
         query: &[A; K],
-        radius: A,
+        radius: D::Output,
         max_qty: usize,
         curr_node_idx: IDX,
         split_dim: usize,
-        best_items: &mut BinaryHeap<BestNeighbour<A, T>>,
+        best_items: &mut BinaryHeap<N>,
         off: &mut [A; K],
-        rd: A,
+        rd: D::Output,
+        min: &mut [A; K],
+        max: &mut [A; K],
     ) where
-        D: DistanceMetric<A, K>,
+        D: StatefulDistanceMetric<A, K>,
+        D::Output: Axis,
+        N: FromLeafEntry<A, T, K, D::Output>,
     {
         if is_stem_index(curr_node_idx) {
             let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
@@ -62,7 +73,8 @@ macro_rules! generate_best_n_within {
                 };
             let next_split_dim = (split_dim + 1).rem(K);
 
-            self.best_n_within_recurse::<D>(
+            self.best_n_within_recurse::<D, N>(
+                metric,
                 query,
                 radius,
                 max_qty,
@@ -71,13 +83,25 @@ macro_rules! generate_best_n_within {
                 best_items,
                 off,
                 rd,
+                min,
+                max,
             );
 
-            rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+            let old_min = min[split_dim];
+            let old_max = max[split_dim];
+            if query[split_dim] < node.split_val {
+                min[split_dim] = node.split_val;
+            } else {
+                max[split_dim] = node.split_val;
+            }
+            rd = metric.lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                Axis::rd_accumulate(rd, metric.dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+            });
 
             if rd <= radius {
                 off[split_dim] = new_off;
-                self.best_n_within_recurse::<D>(
+                self.best_n_within_recurse::<D, N>(
+                    metric,
                     query,
                     radius,
                     max_qty,
@@ -86,56 +110,63 @@ macro_rules! generate_best_n_within {
                     best_items,
                     off,
                     rd,
+                    min,
+                    max,
                 );
                 off[split_dim] = old_off;
             }
+            min[split_dim] = old_min;
+            max[split_dim] = old_max;
         } else {
             let leaf_node = self
                 .leaves
                 .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
 
-            Self::process_leaf_node::<D>(query, radius, max_qty, best_items, leaf_node);
+            Self::process_leaf_node::<D, N>(metric, query, radius, max_qty, best_items, leaf_node);
         }
     }
 
     #[inline]
-    unsafe fn process_leaf_node<D>(
+    unsafe fn process_leaf_node<D, N>(
+        metric: &D,
         query: &[A; K],
-        radius: A,
+        radius: D::Output,
         max_qty: usize,
-        best_items: &mut BinaryHeap<BestNeighbour<A, T>>,
+        best_items: &mut BinaryHeap<N>,
         leaf_node: &$leafnode<A, T, K, B, IDX>,
     ) where
-        D: DistanceMetric<A, K>,
+        D: StatefulDistanceMetric<A, K>,
+        D::Output: Axis,
+        N: FromLeafEntry<A, T, K, D::Output>,
     {
         leaf_node
             .content_points
             .iter()
             .take(leaf_node.size.az::<usize>())
-            .map(|entry| D::dist(query, entry))
             .enumerate()
-            .filter(|(_, distance)| *distance <= radius)
-            .for_each(|(idx, distance)| {
-                Self::get_item_and_add_if_good(max_qty, best_items, leaf_node, idx, distance)
+            .map(|(idx, entry)| (idx, entry, metric.dist(query, entry)))
+            .filter(|(_, _, distance)| *distance <= radius)
+            .for_each(|(idx, entry, distance)| {
+                Self::get_item_and_add_if_good(max_qty, best_items, leaf_node, idx, entry, distance)
             });
     }
 
     #[inline]
-    unsafe fn get_item_and_add_if_good(
+    unsafe fn get_item_and_add_if_good<O, N: FromLeafEntry<A, T, K, O>>(
         max_qty: usize,
-        best_items: &mut BinaryHeap<BestNeighbour<A, T>>,
+        best_items: &mut BinaryHeap<N>,
         leaf_node: &$leafnode<A, T, K, B, IDX>,
         idx: usize,
-        distance: A,
+        point: &[A; K],
+        distance: O,
     ) {
         let item = *leaf_node.content_items.get_unchecked(idx.az::<usize>());
         if best_items.len() < max_qty {
-            best_items.push(BestNeighbour{ distance, item });
+            best_items.push(N::from_leaf_entry(distance, item, point));
         } else {
             let mut top = best_items.peek_mut().unwrap();
-            if item < top.item {
-                top.item = item;
-                top.distance = distance;
+            if item < top.item() {
+                *top = N::from_leaf_entry(distance, item, point);
             }
         }
     }
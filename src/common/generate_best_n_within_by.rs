@@ -0,0 +1,161 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_best_n_within_by {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn best_n_within_by<D, F, S>(
+                &self,
+                query: &[A; K],
+                dist: D::Output,
+                max_qty: usize,
+                score: F,
+            ) -> impl Iterator<Item = BestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                F: Fn(T, D::Output) -> S,
+                S: Ord,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut best_items: BinaryHeap<ScoredNeighbour<S, BestNeighbour<D::Output, T>>> = BinaryHeap::new();
+
+                if max_qty > 0 {
+                    unsafe {
+                        self.best_n_within_by_recurse::<D, F, S>(
+                            query,
+                            dist,
+                            max_qty,
+                            self.root_index,
+                            0,
+                            &mut best_items,
+                            &mut off,
+                            D::Output::zero(),
+                            &mut min,
+                            &mut max,
+                            &score,
+                        );
+                    }
+                }
+
+                best_items
+                    .into_sorted_vec()
+                    .into_iter()
+                    .map(|scored| scored.neighbour)
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn best_n_within_by_recurse<D, F, S>(
+                &self,
+                query: &[A; K],
+                radius: D::Output,
+                max_qty: usize,
+                curr_node_idx: IDX,
+                split_dim: usize,
+                best_items: &mut BinaryHeap<ScoredNeighbour<S, BestNeighbour<D::Output, T>>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                score: &F,
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                F: Fn(T, D::Output) -> S,
+                S: Ord,
+            {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let mut rd = rd;
+                    let old_off = off[split_dim];
+                    let new_off = query[split_dim].saturating_dist(node.split_val);
+
+                    let [closer_node_idx, further_node_idx] =
+                        if *query.get_unchecked(split_dim) < node.split_val {
+                            [node.left, node.right]
+                        } else {
+                            [node.right, node.left]
+                        };
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    self.best_n_within_by_recurse::<D, F, S>(
+                        query,
+                        radius,
+                        max_qty,
+                        closer_node_idx,
+                        next_split_dim,
+                        best_items,
+                        off,
+                        rd,
+                        min,
+                        max,
+                        score,
+                    );
+
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
+
+                    if rd <= radius {
+                        off[split_dim] = new_off;
+                        self.best_n_within_by_recurse::<D, F, S>(
+                            query,
+                            radius,
+                            max_qty,
+                            further_node_idx,
+                            next_split_dim,
+                            best_items,
+                            off,
+                            rd,
+                            min,
+                            max,
+                            score,
+                        );
+                        off[split_dim] = old_off;
+                    }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .take(leaf_node.size.az::<usize>())
+                        .map(|entry| D::dist(query, entry))
+                        .enumerate()
+                        .filter(|(_, distance)| *distance <= radius)
+                        .for_each(|(idx, distance)| {
+                            let item = *leaf_node.content_items.get_unchecked(idx);
+                            let element = ScoredNeighbour {
+                                score: score(item, distance),
+                                neighbour: BestNeighbour { distance, item },
+                            };
+
+                            if best_items.len() < max_qty {
+                                best_items.push(element);
+                            } else {
+                                let mut top = best_items.peek_mut().unwrap();
+                                if element.score < top.score {
+                                    *top = element;
+                                }
+                            }
+                        });
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,46 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_best_n_within_with_metric {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn best_n_within_with_metric<D>(
+                &self,
+                metric: &D,
+                query: &[A; K],
+                dist: D::Output,
+                max_qty: usize,
+            ) -> impl Iterator<Item = BestNeighbour<D::Output, T>>
+            where
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut best_items: BinaryHeap<BestNeighbour<D::Output, T>> = BinaryHeap::new();
+
+                if max_qty > 0 {
+                    unsafe {
+                        self.best_n_within_recurse(
+                            metric,
+                            query,
+                            dist,
+                            max_qty,
+                            self.root_index,
+                            0,
+                            &mut best_items,
+                            &mut off,
+                            D::Output::zero(),
+                            &mut min,
+                            &mut max,
+                        );
+                    }
+                }
+
+                best_items.into_iter()
+            }
+        }
+    };
+}
@@ -0,0 +1,46 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_best_n_within_with_point {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn best_n_within_with_point<D>(
+                &self,
+                query: &[A; K],
+                dist: D::Output,
+                max_qty: usize,
+            ) -> impl Iterator<Item = PointNeighbour<A, T, K, D::Output>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut best_items: BinaryHeap<ScoredNeighbour<T, PointNeighbour<A, T, K, D::Output>>> =
+                    BinaryHeap::new();
+
+                if max_qty > 0 {
+                    unsafe {
+                        self.best_n_within_recurse(
+                            &StatelessMetric::<D>::new(),
+                            query,
+                            dist,
+                            max_qty,
+                            self.root_index,
+                            0,
+                            &mut best_items,
+                            &mut off,
+                            D::Output::zero(),
+                            &mut min,
+                            &mut max,
+                        );
+                    }
+                }
+
+                best_items.into_iter().map(|scored| scored.neighbour)
+            }
+        }
+    };
+}
@@ -0,0 +1,181 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_count_within {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn count_within<D>(&self, query: &[A; K], dist: D::Output) -> usize
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                unsafe {
+                    self.count_within_recurse::<D>(
+                        query,
+                        dist,
+                        self.root_index,
+                        0,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                    )
+                }
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn count_within_recurse<D>(
+                &self,
+                query: &[A; K],
+                radius: D::Output,
+                curr_node_idx: IDX,
+                split_dim: usize,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) -> usize
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if Self::count_within_box_is_inside::<D>(query, radius, min, max) {
+                    return self.count_within_subtree_size(curr_node_idx);
+                }
+
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let mut rd = rd;
+                    let old_off = off[split_dim];
+                    let new_off = query[split_dim].saturating_dist(node.split_val);
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+
+                    let is_left = *query.get_unchecked(split_dim) < node.split_val;
+                    let [closer_node_idx, further_node_idx] = if is_left {
+                        [node.left, node.right]
+                    } else {
+                        [node.right, node.left]
+                    };
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    if is_left {
+                        if node.split_val < old_max {
+                            max[split_dim] = node.split_val;
+                        }
+                    } else if node.split_val > old_min {
+                        min[split_dim] = node.split_val;
+                    }
+                    let mut count = self.count_within_recurse::<D>(
+                        query,
+                        radius,
+                        closer_node_idx,
+                        next_split_dim,
+                        off,
+                        rd,
+                        min,
+                        max,
+                    );
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+
+                    if is_left {
+                        if node.split_val > old_min {
+                            min[split_dim] = node.split_val;
+                        }
+                    } else if node.split_val < old_max {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
+
+                    if rd <= radius {
+                        off[split_dim] = new_off;
+                        count += self.count_within_recurse::<D>(
+                            query,
+                            radius,
+                            further_node_idx,
+                            next_split_dim,
+                            off,
+                            rd,
+                            min,
+                            max,
+                        );
+                        off[split_dim] = old_off;
+                    }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+
+                    count
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .take(leaf_node.size.az::<usize>())
+                        .filter(|entry| D::dist(query, entry) < radius)
+                        .count()
+                }
+            }
+
+            /// Returns true if every point inside the box bounded by `min` and `max`
+            /// is closer to `query` than `radius`. Sides of the box that have not yet
+            /// been bounded by a split are treated as unbounded.
+            fn count_within_box_is_inside<D>(query: &[A; K], radius: D::Output, min: &[A; K], max: &[A; K]) -> bool
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if let Some(max_dist) = D::upper_bound_to_box(query, min, max) {
+                    return max_dist < radius;
+                }
+                // a metric that bounds boxes itself isn't necessarily built from its per-axis
+                // distances, so they can't be relied on to bound it from above either
+                if D::lower_bound_to_box(query, min, max).is_some() {
+                    return false;
+                }
+
+                let mut max_dist = D::Output::zero();
+
+                for dim in 0..K {
+                    if min[dim] == A::min_value() || max[dim] == A::max_value() {
+                        return false;
+                    }
+
+                    let to_min = query[dim].saturating_dist(min[dim]);
+                    let to_max = query[dim].saturating_dist(max[dim]);
+                    let furthest = if to_min > to_max { to_min } else { to_max };
+
+                    max_dist = Axis::rd_accumulate(max_dist, D::dist1(furthest, A::zero()), D::ACCUMULATION);
+                }
+
+                max_dist < radius
+            }
+
+            unsafe fn count_within_subtree_size(&self, curr_node_idx: IDX) -> usize {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    self.count_within_subtree_size(node.left)
+                        + self.count_within_subtree_size(node.right)
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node.size.az::<usize>()
+                }
+            }
+        }
+    };
+}
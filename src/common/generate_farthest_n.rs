@@ -0,0 +1,151 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_farthest_n {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn farthest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut result: BinaryHeap<Reverse<NearestNeighbour<D::Output, T>>> =
+                    BinaryHeap::with_capacity(qty);
+
+                if qty > 0 {
+                    unsafe {
+                        self.farthest_n_recurse::<D>(
+                            query,
+                            qty,
+                            self.root_index,
+                            0,
+                            &mut result,
+                            &mut min,
+                            &mut max,
+                        )
+                    }
+                }
+
+                result.into_sorted_vec().into_iter().map(|Reverse(nn)| nn).collect()
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn farthest_n_recurse<D>(
+                &self,
+                query: &[A; K],
+                qty: usize,
+                curr_node_idx: IDX,
+                split_dim: usize,
+                results: &mut BinaryHeap<Reverse<NearestNeighbour<D::Output, T>>>,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if results.len() >= qty {
+                    let upper_bound = Self::farthest_n_max_dist_to_box::<D>(query, min, max);
+                    if upper_bound <= results.peek().unwrap().0.distance {
+                        return;
+                    }
+                }
+
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+                    let next_split_dim = (split_dim + 1).rem(K);
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+
+                    // the child on the opposite side of the split from the query
+                    // is more likely to hold the farthest points, so visit it first
+                    if *query.get_unchecked(split_dim) < node.split_val {
+                        if node.split_val > old_min {
+                            min[split_dim] = node.split_val;
+                        }
+                        self.farthest_n_recurse::<D>(query, qty, node.right, next_split_dim, results, min, max);
+                        min[split_dim] = old_min;
+
+                        if node.split_val < old_max {
+                            max[split_dim] = node.split_val;
+                        }
+                        self.farthest_n_recurse::<D>(query, qty, node.left, next_split_dim, results, min, max);
+                        max[split_dim] = old_max;
+                    } else {
+                        if node.split_val < old_max {
+                            max[split_dim] = node.split_val;
+                        }
+                        self.farthest_n_recurse::<D>(query, qty, node.left, next_split_dim, results, min, max);
+                        max[split_dim] = old_max;
+
+                        if node.split_val > old_min {
+                            min[split_dim] = node.split_val;
+                        }
+                        self.farthest_n_recurse::<D>(query, qty, node.right, next_split_dim, results, min, max);
+                        min[split_dim] = old_min;
+                    }
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .take(leaf_node.size.az::<usize>())
+                        .enumerate()
+                        .for_each(|(idx, entry)| {
+                            let distance: D::Output = D::dist(query, entry);
+                            let item = *leaf_node.content_items.get_unchecked(idx);
+                            let element = NearestNeighbour { distance, item };
+
+                            if results.len() < qty {
+                                results.push(Reverse(element));
+                            } else {
+                                let mut top = results.peek_mut().unwrap();
+                                if element.distance > top.0.distance {
+                                    *top = Reverse(element);
+                                }
+                            }
+                        });
+                }
+            }
+
+            /// Returns an upper bound on the distance from `query` to any point inside
+            /// the box bounded by `min` and `max`, or the maximum value of `A` if any
+            /// side of the box has not yet been bounded by a split.
+            fn farthest_n_max_dist_to_box<D>(query: &[A; K], min: &[A; K], max: &[A; K]) -> D::Output
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if let Some(max_dist) = D::upper_bound_to_box(query, min, max) {
+                    return max_dist;
+                }
+                // a metric that bounds boxes itself isn't necessarily built from its per-axis
+                // distances, so they can't be relied on to bound it from above either
+                if D::lower_bound_to_box(query, min, max).is_some() {
+                    return D::Output::max_value();
+                }
+
+                let mut max_dist = D::Output::zero();
+
+                for dim in 0..K {
+                    if min[dim] == A::min_value() || max[dim] == A::max_value() {
+                        return D::Output::max_value();
+                    }
+
+                    let to_min = query[dim].saturating_dist(min[dim]);
+                    let to_max = query[dim].saturating_dist(max[dim]);
+                    let furthest = if to_min > to_max { to_min } else { to_max };
+
+                    max_dist = Axis::rd_accumulate(max_dist, D::dist1(furthest, A::zero()), D::ACCUMULATION);
+                }
+
+                max_dist
+            }
+        }
+    };
+}
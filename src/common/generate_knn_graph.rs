@@ -0,0 +1,173 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_knn_graph {
+    ($comments:tt) => {
+    doc_comment! {
+    concat!$comments,
+    pub fn knn_graph<D>(&self, k: usize, exclude_self: bool) -> KnnGraph<D::Output, T>
+    where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        let mut rows = Vec::with_capacity(
+            self.leaves.iter().map(|leaf_node| leaf_node.size.az::<usize>()).sum(),
+        );
+
+        for (leaf_idx, leaf_node) in self.leaves.iter().enumerate() {
+            let leaf_size = leaf_node.size.az::<usize>();
+
+            for point_idx in 0..leaf_size {
+                let query = &leaf_node.content_points[point_idx];
+                let mut results: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(k);
+
+                if k > 0 {
+                    // every other point in the same leaf is a likely neighbour, so checking
+                    // them first gives a tight bound with which to prune the rest of the tree
+                    for other_idx in 0..leaf_size {
+                        if exclude_self && other_idx == point_idx {
+                            continue;
+                        }
+                        let distance = D::dist(query, &leaf_node.content_points[other_idx]);
+                        let item = leaf_node.content_items[other_idx];
+                        Self::knn_graph_add_to_heap(k, NearestNeighbour { distance, item }, &mut results);
+                    }
+
+                    let mut off = [A::zero(); K];
+                    let mut min = [A::min_value(); K];
+                    let mut max = [A::max_value(); K];
+                    unsafe {
+                        self.knn_graph_recurse::<D>(
+                            query,
+                            k,
+                            leaf_idx,
+                            self.root_index,
+                            0,
+                            &mut results,
+                            &mut off,
+                            D::Output::zero(),
+                            &mut min,
+                            &mut max,
+                        );
+                    }
+                }
+
+                rows.push((leaf_node.content_items[point_idx], results.into_sorted_vec()));
+            }
+        }
+
+        KnnGraph::from_rows(rows)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn knn_graph_recurse<D>(
+        &self,
+        query: &[A; K],
+        k: usize,
+        home_leaf_idx: usize,
+        curr_node_idx: IDX,
+        split_dim: usize,
+        results: &mut BinaryHeap<NearestNeighbour<D::Output, T>>,
+        off: &mut [A; K],
+        rd: D::Output,
+        min: &mut [A; K],
+        max: &mut [A; K],
+    ) where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        if is_stem_index(curr_node_idx) {
+            let node = &self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+            let mut rd = rd;
+            let old_off = off[split_dim];
+            let new_off = query[split_dim].saturating_dist(node.split_val);
+
+            let [closer_node_idx, further_node_idx] =
+                if *query.get_unchecked(split_dim) < node.split_val {
+                    [node.left, node.right]
+                } else {
+                    [node.right, node.left]
+                };
+            let next_split_dim = (split_dim + 1).rem(K);
+
+            self.knn_graph_recurse::<D>(
+                query,
+                k,
+                home_leaf_idx,
+                closer_node_idx,
+                next_split_dim,
+                results,
+                off,
+                rd,
+                min,
+                max,
+            );
+
+            let old_min = min[split_dim];
+            let old_max = max[split_dim];
+            if query[split_dim] < node.split_val {
+                min[split_dim] = node.split_val;
+            } else {
+                max[split_dim] = node.split_val;
+            }
+            rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+            });
+
+            if results.len() < k || rd < results.peek().unwrap().distance {
+                off[split_dim] = new_off;
+                self.knn_graph_recurse::<D>(
+                    query,
+                    k,
+                    home_leaf_idx,
+                    further_node_idx,
+                    next_split_dim,
+                    results,
+                    off,
+                    rd,
+                    min,
+                    max,
+                );
+                off[split_dim] = old_off;
+            }
+            min[split_dim] = old_min;
+            max[split_dim] = old_max;
+        } else {
+            let leaf_idx = (curr_node_idx - IDX::leaf_offset()).az::<usize>();
+
+            // the contents of the home leaf have already been checked
+            if leaf_idx == home_leaf_idx {
+                return;
+            }
+
+            let leaf_node = self.leaves.get_unchecked(leaf_idx);
+
+            leaf_node
+                .content_points
+                .iter()
+                .take(leaf_node.size.az::<usize>())
+                .enumerate()
+                .for_each(|(idx, entry)| {
+                    let distance: D::Output = D::dist(query, entry);
+                    let item = *leaf_node.content_items.get_unchecked(idx);
+                    Self::knn_graph_add_to_heap(k, NearestNeighbour { distance, item }, results);
+                });
+        }
+    }
+
+    #[inline]
+    fn knn_graph_add_to_heap<O: PartialOrd>(
+        k: usize,
+        element: NearestNeighbour<O, T>,
+        results: &mut BinaryHeap<NearestNeighbour<O, T>>,
+    ) {
+        if results.len() < k {
+            results.push(element)
+        } else {
+            let mut top = results.peek_mut().unwrap();
+            if element.distance < top.distance {
+                *top = element;
+            }
+        }
+    }
+}}}
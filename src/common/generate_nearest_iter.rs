@@ -0,0 +1,146 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_iter {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_iter<D>(&'a self, query: &'a [A; K]) -> NearestIter<'a, D::Output, T>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let gen = Gn::new_scoped(move |mut gen_scope| {
+                    let mut candidates: BinaryHeap<NearestIterCandidate<A, T, IDX, K, D::Output>> =
+                        BinaryHeap::new();
+
+                    candidates.push(NearestIterCandidate {
+                        distance: D::Output::zero(),
+                        kind: NearestIterCandidateKind::Node {
+                            node_idx: self.root_index,
+                            split_dim: 0,
+                            off: [A::zero(); K],
+                            min: [A::min_value(); K],
+                            max: [A::max_value(); K],
+                        },
+                    });
+
+                    while let Some(candidate) = candidates.pop() {
+                        match candidate.kind {
+                            NearestIterCandidateKind::Item(item) => {
+                                gen_scope.yield_(NearestNeighbour {
+                                    distance: candidate.distance,
+                                    item,
+                                });
+                            }
+                            NearestIterCandidateKind::Node {
+                                node_idx,
+                                split_dim,
+                                off,
+                                min,
+                                max,
+                            } => unsafe {
+                                self.nearest_iter_expand::<D>(
+                                    query,
+                                    node_idx,
+                                    split_dim,
+                                    off,
+                                    candidate.distance,
+                                    min,
+                                    max,
+                                    &mut candidates,
+                                );
+                            },
+                        }
+                    }
+
+                    done!();
+                });
+
+                NearestIter::new(gen)
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn nearest_iter_expand<D>(
+                &self,
+                query: &[A; K],
+                curr_node_idx: IDX,
+                split_dim: usize,
+                off: [A; K],
+                rd: D::Output,
+                min: [A; K],
+                max: [A; K],
+                candidates: &mut BinaryHeap<NearestIterCandidate<A, T, IDX, K, D::Output>>,
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let old_off = off[split_dim];
+                    let new_off = query[split_dim].saturating_dist(node.split_val);
+
+                    let [closer_node_idx, further_node_idx] =
+                        if *query.get_unchecked(split_dim) < node.split_val {
+                            [node.left, node.right]
+                        } else {
+                            [node.right, node.left]
+                        };
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    candidates.push(NearestIterCandidate {
+                        distance: rd,
+                        kind: NearestIterCandidateKind::Node {
+                            node_idx: closer_node_idx,
+                            split_dim: next_split_dim,
+                            off,
+                            min,
+                            max,
+                        },
+                    });
+
+                    let mut further_off = off;
+                    further_off[split_dim] = new_off;
+                    let mut further_min = min;
+                    let mut further_max = max;
+                    if query[split_dim] < node.split_val {
+                        further_min[split_dim] = node.split_val;
+                    } else {
+                        further_max[split_dim] = node.split_val;
+                    }
+                    candidates.push(NearestIterCandidate {
+                        distance: D::lower_bound_to_box(query, &further_min, &further_max).unwrap_or_else(|| {
+                            Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                        }),
+                        kind: NearestIterCandidateKind::Node {
+                            node_idx: further_node_idx,
+                            split_dim: next_split_dim,
+                            off: further_off,
+                            min: further_min,
+                            max: further_max,
+                        },
+                    });
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .enumerate()
+                        .take(leaf_node.size.az::<usize>())
+                        .for_each(|(idx, entry)| {
+                            candidates.push(NearestIterCandidate {
+                                distance: D::dist(query, entry),
+                                kind: NearestIterCandidateKind::Item(
+                                    *leaf_node.content_items.get_unchecked(idx),
+                                ),
+                            });
+                        });
+                }
+            }
+        }
+    };
+}
@@ -5,21 +5,29 @@ macro_rules! generate_nearest_n {
     doc_comment! {
     concat!$comments,
     #[inline]
-    pub fn nearest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<A, T>>
+    pub fn nearest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<D::Output, T>>
     where
         D: DistanceMetric<A, K>,
+        D::Output: Axis,
     {
         let mut off = [A::zero(); K];
-        let mut result: BinaryHeap<NearestNeighbour<A, T>> = BinaryHeap::with_capacity(qty);
+        let mut min = [A::min_value(); K];
+        let mut max = [A::max_value(); K];
+        let mut result: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(qty);
 
         unsafe {
-            self.nearest_n_recurse::<D>(
+            self.nearest_n_recurse(
+                &StatelessMetric::<D>::new(),
                 query,
+                qty,
                 self.root_index,
                 0,
                 &mut result,
                 &mut off,
-                A::zero(),
+                D::Output::zero(),
+                &mut min,
+                &mut max,
+                &|_| true,
             )
         }
 
@@ -27,16 +35,24 @@ macro_rules! generate_nearest_n {
     }
 
     #[allow(clippy::too_many_arguments)]
-    unsafe fn nearest_n_recurse<D>(
+    pub(crate) unsafe fn nearest_n_recurse<D, N, F>(
         &self,
+        metric: &D,
         query: &[A; K],
+        qty: usize,
         curr_node_idx: IDX,
         split_dim: usize,
-        results: &mut BinaryHeap<NearestNeighbour<A, T>>,
+        results: &mut BinaryHeap<N>,
         off: &mut [A; K],
-        rd: A,
+        rd: D::Output,
+        min: &mut [A; K],
+        max: &mut [A; K],
+        filter: &F,
     ) where
-        D: DistanceMetric<A, K>,
+        D: StatefulDistanceMetric<A, K>,
+        D::Output: Axis,
+        N: FromLeafEntry<A, T, K, D::Output>,
+        F: Fn(T) -> bool,
     {
         if is_stem_index(curr_node_idx) {
             let node = &self.stems.get_unchecked(curr_node_idx.az::<usize>());
@@ -53,29 +69,50 @@ macro_rules! generate_nearest_n {
                 };
             let next_split_dim = (split_dim + 1).rem(K);
 
-            self.nearest_n_recurse::<D>(
+            self.nearest_n_recurse::<D, N, F>(
+                metric,
                 query,
+                qty,
                 closer_node_idx,
                 next_split_dim,
                 results,
                 off,
                 rd,
+                min,
+                max,
+                filter,
             );
 
-            rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+            let old_min = min[split_dim];
+            let old_max = max[split_dim];
+            if query[split_dim] < node.split_val {
+                min[split_dim] = node.split_val;
+            } else {
+                max[split_dim] = node.split_val;
+            }
+            rd = metric.lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                Axis::rd_accumulate(rd, metric.dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+            });
 
-            if Self::dist_belongs_in_heap(rd, results) {
+            if Self::dist_belongs_in_heap(rd, results, qty) {
                 off[split_dim] = new_off;
-                self.nearest_n_recurse::<D>(
+                self.nearest_n_recurse::<D, N, F>(
+                    metric,
                     query,
+                    qty,
                     further_node_idx,
                     next_split_dim,
                     results,
                     off,
                     rd,
+                    min,
+                    max,
+                    filter,
                 );
                 off[split_dim] = old_off;
             }
+            min[split_dim] = old_min;
+            max[split_dim] = old_max;
         } else {
             let leaf_node = self
                 .leaves
@@ -87,15 +124,18 @@ macro_rules! generate_nearest_n {
                 .take(leaf_node.size.az::<usize>())
                 .enumerate()
                 .for_each(|(idx, entry)| {
-                    let distance: A = D::dist(query, entry);
-                    if Self::dist_belongs_in_heap(distance, results) {
+                    let distance: D::Output = metric.dist(query, entry);
+                    if Self::dist_belongs_in_heap(distance, results, qty) {
                         let item = unsafe { *leaf_node.content_items.get_unchecked(idx) };
-                        let element = NearestNeighbour { distance, item };
-                        if results.len() < results.capacity() {
+                        if !filter(item) {
+                            return;
+                        }
+                        let element = N::from_leaf_entry(distance, item, entry);
+                        if results.len() < qty {
                             results.push(element)
                         } else {
                             let mut top = results.peek_mut().unwrap();
-                            if element.distance < top.distance {
+                            if element.distance() < top.distance() {
                                 *top = element;
                             }
                         }
@@ -105,7 +145,7 @@ macro_rules! generate_nearest_n {
     }
 
     #[inline]
-    fn dist_belongs_in_heap(dist: A, heap: &BinaryHeap<NearestNeighbour<A, T>>) -> bool {
-        heap.is_empty() || dist < heap.peek().unwrap().distance || heap.len() < heap.capacity()
+    fn dist_belongs_in_heap<O: PartialOrd, N: Neighbour<O, T>>(dist: O, heap: &BinaryHeap<N>, qty: usize) -> bool {
+        heap.len() < qty || heap.peek().is_some_and(|top| dist < top.distance())
     }
 }}}
@@ -0,0 +1,20 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_batch {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_batch<D>(&self, queries: &[[A; K]], qty: usize) -> BatchResults<D::Output, T>
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis,
+                    Self: Sync,
+            {
+                BatchResults::from_par_queries(queries, |query, results| {
+                    self.nearest_n_into::<D>(query, qty, results)
+                })
+            }
+        }
+    };
+}
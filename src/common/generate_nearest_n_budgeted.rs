@@ -0,0 +1,156 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_budgeted {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_budgeted<D>(
+                &self,
+                query: &[A; K],
+                qty: usize,
+                budget: SearchBudget,
+            ) -> BudgetedResult<Vec<NearestNeighbour<D::Output, T>>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut budget = BudgetTracker::new(budget);
+                if qty == 0 {
+                    return budget.into_result(Vec::new());
+                }
+
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut result: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(qty);
+
+                unsafe {
+                    self.nearest_n_budgeted_recurse::<D>(
+                        query,
+                        qty,
+                        self.root_index,
+                        0,
+                        &mut result,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &mut budget,
+                    )
+                }
+
+                budget.into_result(result.into_sorted_vec())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn nearest_n_budgeted_recurse<D>(
+                &self,
+                query: &[A; K],
+                qty: usize,
+                curr_node_idx: IDX,
+                split_dim: usize,
+                results: &mut BinaryHeap<NearestNeighbour<D::Output, T>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                budget: &mut BudgetTracker,
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if is_stem_index(curr_node_idx) {
+                    if !budget.visit_stem() {
+                        return;
+                    }
+
+                    let node = &self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let mut rd = rd;
+                    let old_off = off[split_dim];
+                    let new_off = query[split_dim].saturating_dist(node.split_val);
+
+                    let [closer_node_idx, further_node_idx] =
+                        if *query.get_unchecked(split_dim) < node.split_val {
+                            [node.left, node.right]
+                        } else {
+                            [node.right, node.left]
+                        };
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    self.nearest_n_budgeted_recurse::<D>(
+                        query,
+                        qty,
+                        closer_node_idx,
+                        next_split_dim,
+                        results,
+                        off,
+                        rd,
+                        min,
+                        max,
+                        budget,
+                    );
+
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
+
+                    if results.len() < qty || rd < results.peek().unwrap().distance {
+                        off[split_dim] = new_off;
+                        self.nearest_n_budgeted_recurse::<D>(
+                            query,
+                            qty,
+                            further_node_idx,
+                            next_split_dim,
+                            results,
+                            off,
+                            rd,
+                            min,
+                            max,
+                            budget,
+                        );
+                        off[split_dim] = old_off;
+                    }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+                } else {
+                    if !budget.visit_leaf() {
+                        return;
+                    }
+
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .take(leaf_node.size.az::<usize>())
+                        .enumerate()
+                        .for_each(|(idx, entry)| {
+                            let distance: D::Output = D::dist(query, entry);
+                            let item = unsafe { *leaf_node.content_items.get_unchecked(idx) };
+                            let element = NearestNeighbour { distance, item };
+
+                            if results.len() < qty {
+                                results.push(element)
+                            } else {
+                                let mut top = results.peek_mut().unwrap();
+                                if element.distance < top.distance {
+                                    *top = element;
+                                }
+                            }
+                        });
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,17 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_excluding {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_excluding<D>(&self, query: &[A; K], qty: usize, exclude: &[T]) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                self.nearest_n_filtered::<D, _>(query, qty, |item| !exclude.contains(&item))
+            }
+        }
+    };
+}
@@ -0,0 +1,39 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_filtered {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_filtered<D, F>(&self, query: &[A; K], qty: usize, filter: F) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                F: Fn(T) -> bool,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut result: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(qty);
+
+                unsafe {
+                    self.nearest_n_recurse(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        qty,
+                        self.root_index,
+                        0,
+                        &mut result,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &filter,
+                    )
+                }
+
+                result.into_sorted_vec()
+            }
+        }
+    };
+}
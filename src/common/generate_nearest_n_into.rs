@@ -0,0 +1,43 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_into {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_into<D>(&self, query: &[A; K], qty: usize, results: &mut Vec<NearestNeighbour<D::Output, T>>)
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                // reuse the caller's allocation as the backing storage of the heap
+                results.clear();
+                let mut heap = BinaryHeap::from(std::mem::take(results));
+
+                if qty > 0 {
+                    unsafe {
+                        self.nearest_n_recurse(
+                            &StatelessMetric::<D>::new(),
+                            query,
+                            qty,
+                            self.root_index,
+                            0,
+                            &mut heap,
+                            &mut off,
+                            D::Output::zero(),
+                            &mut min,
+                            &mut max,
+                            &|_| true,
+                        )
+                    }
+                }
+
+                *results = heap.into_sorted_vec();
+            }
+        }
+    };
+}
@@ -0,0 +1,36 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_with_metric {
+    ($comments:tt) => {
+    doc_comment! {
+    concat!$comments,
+    #[inline]
+    pub fn nearest_n_with_metric<D>(&self, metric: &D, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<D::Output, T>>
+    where
+        D: StatefulDistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        let mut off = [A::zero(); K];
+        let mut min = [A::min_value(); K];
+        let mut max = [A::max_value(); K];
+        let mut result: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(qty);
+
+        unsafe {
+            self.nearest_n_recurse(
+                metric,
+                query,
+                qty,
+                self.root_index,
+                0,
+                &mut result,
+                &mut off,
+                D::Output::zero(),
+                &mut min,
+                &mut max,
+                &|_| true,
+            )
+        }
+
+        result.into_sorted_vec()
+    }
+}}}
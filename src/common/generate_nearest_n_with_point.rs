@@ -0,0 +1,38 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_with_point {
+    ($comments:tt) => {
+    doc_comment! {
+    concat!$comments,
+    #[inline]
+    pub fn nearest_n_with_point<D>(&self, query: &[A; K], qty: usize) -> Vec<PointNeighbour<A, T, K, D::Output>>
+    where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        let mut off = [A::zero(); K];
+        let mut min = [A::min_value(); K];
+        let mut max = [A::max_value(); K];
+        let mut result: BinaryHeap<PointNeighbour<A, T, K, D::Output>> = BinaryHeap::with_capacity(qty);
+
+        if qty > 0 {
+            unsafe {
+                self.nearest_n_recurse(
+                    &StatelessMetric::<D>::new(),
+                    query,
+                    qty,
+                    self.root_index,
+                    0,
+                    &mut result,
+                    &mut off,
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                    &|_| true,
+                )
+            }
+        }
+
+        result.into_sorted_vec()
+    }
+}}}
@@ -0,0 +1,34 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_n_within_into_collection {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_within_into_collection<D, C>(&self, query: &[A; K], dist: D::Output, results: &mut C)
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                C: ResultCollection<D::Output, T>,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                unsafe {
+                    self.nearest_n_within_unsorted_recurse::<D, C>(
+                        query,
+                        dist,
+                        self.root_index,
+                        0,
+                        results,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                    );
+                }
+            }
+        }
+    };
+}
@@ -6,26 +6,32 @@ macro_rules! generate_nearest_n_within_unsorted {
             concat!$comments,
 
             #[inline]
-            pub fn nearest_n_within<D>(&self, query: &[A; K], dist: A, max_items: usize, sorted: bool) -> Vec<NearestNeighbour<A, T>>
+            pub fn nearest_n_within<D>(&self, query: &[A; K], dist: D::Output, max_items: usize, sorted: bool) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 if sorted && max_items < usize::MAX {
                     if max_items <= MAX_VEC_RESULT_SIZE {
-                        self.nearest_n_within_stub::<D, SortedVec<NearestNeighbour<A, T>>>(query, dist, max_items, sorted)
+                        self.nearest_n_within_stub::<D, SortedVec<NearestNeighbour<D::Output, T>>>(query, dist, max_items, sorted)
                     } else {
-                        self.nearest_n_within_stub::<D, BinaryHeap<NearestNeighbour<A, T>>>(query, dist, max_items, sorted)
+                        self.nearest_n_within_stub::<D, BinaryHeap<NearestNeighbour<D::Output, T>>>(query, dist, max_items, sorted)
                     }
                 } else {
-                    self.nearest_n_within_stub::<D, Vec<NearestNeighbour<A,T>>>(query, dist, 0, sorted)
+                    self.nearest_n_within_stub::<D, Vec<NearestNeighbour<D::Output, T>>>(query, dist, 0, sorted)
                 }
             }
 
-            fn nearest_n_within_stub<D: DistanceMetric<A, K>, H: ResultCollection<A, T>>(
-                &self, query: &[A; K], dist: A, res_capacity: usize, sorted: bool
-            ) -> Vec<NearestNeighbour<A, T>> {
+            fn nearest_n_within_stub<D: DistanceMetric<A, K>, H: ResultCollection<D::Output, T>>(
+                &self, query: &[A; K], dist: D::Output, res_capacity: usize, sorted: bool
+            ) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D::Output: Axis,
+            {
                 let mut matching_items = H::new_with_capacity(res_capacity);
                 let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
 
                 unsafe {
                     self.nearest_n_within_unsorted_recurse::<D, H>(
@@ -35,7 +41,9 @@ macro_rules! generate_nearest_n_within_unsorted {
                         0,
                         &mut matching_items,
                         &mut off,
-                        A::zero(),
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
                     );
                 }
 
@@ -47,17 +55,20 @@ macro_rules! generate_nearest_n_within_unsorted {
             }
 
             #[allow(clippy::too_many_arguments)]
-            unsafe fn nearest_n_within_unsorted_recurse<D, R: ResultCollection<A, T>>(
+            pub(crate) unsafe fn nearest_n_within_unsorted_recurse<D, R: ResultCollection<D::Output, T>>(
                 &self,
                 query: &[A; K],
-                radius: A,
+                radius: D::Output,
                 curr_node_idx: IDX,
                 split_dim: usize,
                 matching_items: &mut R,
                 off: &mut [A; K],
-                rd: A,
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
             ) where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 if is_stem_index(curr_node_idx) {
                     let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
@@ -82,9 +93,20 @@ macro_rules! generate_nearest_n_within_unsorted {
                         matching_items,
                         off,
                         rd,
+                        min,
+                        max,
                     );
 
-                    rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
 
                     if rd <= radius {
                         off[split_dim] = new_off;
@@ -96,9 +118,13 @@ macro_rules! generate_nearest_n_within_unsorted {
                             matching_items,
                             off,
                             rd,
+                            min,
+                            max,
                         );
                         off[split_dim] = old_off;
                     }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
                 } else {
                     let leaf_node = self
                         .leaves
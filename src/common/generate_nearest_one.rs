@@ -5,36 +5,50 @@ macro_rules! generate_nearest_one {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn nearest_one<D>(&self, query: &[A; K]) -> NearestNeighbour<A, T>
+            pub fn nearest_one<D>(&self, query: &[A; K]) -> NearestNeighbour<D::Output, T>
                 where
                     D: DistanceMetric<A, K>,
+                    D::Output: Axis,
             {
                 let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
 
                 unsafe {
-                    self.nearest_one_recurse::<D>(
+                    self.nearest_one_recurse(
+                        &StatelessMetric::<D>::new(),
                         query,
                         self.root_index,
                         0,
-                        NearestNeighbour { distance: A::max_value(), item: T::zero() },
+                        NearestNeighbour { distance: D::Output::max_value(), item: T::zero() },
                         &mut off,
-                        A::zero(),
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &|_| true,
                     )
                 }
             }
 
             #[allow(clippy::too_many_arguments)]
-            unsafe fn nearest_one_recurse<D>(
+            pub(crate) unsafe fn nearest_one_recurse<D, N, F>(
                 &self,
+                metric: &D,
                 query: &[A; K],
                 curr_node_idx: IDX,
                 split_dim: usize,
-                mut nearest: NearestNeighbour<A, T>,
+                mut nearest: N,
                 off: &mut [A; K],
-                rd: A,
-            ) -> NearestNeighbour<A, T>
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                filter: &F,
+            ) -> N
                 where
-                    D: DistanceMetric<A, K>,
+                    D: StatefulDistanceMetric<A, K>,
+                    D::Output: Axis,
+                    N: FromLeafEntry<A, T, K, D::Output>,
+                    F: Fn(T) -> bool,
             {
                 if is_stem_index(curr_node_idx) {
                     let node = &self.stems.get_unchecked(curr_node_idx.az::<usize>());
@@ -51,30 +65,47 @@ macro_rules! generate_nearest_one {
                         };
                     let next_split_dim = (split_dim + 1).rem(K);
 
-                    let nearest_neighbour = self.nearest_one_recurse::<D>(
+                    let nearest_neighbour = self.nearest_one_recurse::<D, N, F>(
+                        metric,
                         query,
                         closer_node_idx,
                         next_split_dim,
                         nearest,
                         off,
                         rd,
+                        min,
+                        max,
+                        filter,
                     );
 
                     if nearest_neighbour < nearest {
                         nearest = nearest_neighbour;
                     }
 
-                    rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = metric.lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, metric.dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
 
-                    if rd <= nearest.distance {
+                    if rd <= nearest.distance() {
                         off[split_dim] = new_off;
-                        let result = self.nearest_one_recurse::<D>(
+                        let result = self.nearest_one_recurse::<D, N, F>(
+                            metric,
                             query,
                             further_node_idx,
                             next_split_dim,
                             nearest,
                             off,
                             rd,
+                            min,
+                            max,
+                            filter,
                         );
                         off[split_dim] = old_off;
 
@@ -82,15 +113,19 @@ macro_rules! generate_nearest_one {
                             nearest = result;
                         }
                     }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
                 } else {
                     let leaf_node = self
                         .leaves
                         .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
 
-                    Self::search_content_for_nearest::<D>(
+                    Self::search_content_for_nearest::<D, N, F>(
+                        metric,
                         query,
                         &mut nearest,
                         leaf_node,
+                        filter,
                     );
                 }
 
@@ -98,12 +133,17 @@ macro_rules! generate_nearest_one {
             }
 
             #[inline]
-            fn search_content_for_nearest<D>(
+            pub(crate) fn search_content_for_nearest<D, N, F>(
+                metric: &D,
                 query: &[A; K],
-                nearest: &mut NearestNeighbour<A, T>,
+                nearest: &mut N,
                 leaf_node: &$leafnode<A, T, K, B, IDX>,
+                filter: &F,
             ) where
-                D: DistanceMetric<A, K>,
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+                N: FromLeafEntry<A, T, K, D::Output>,
+                F: Fn(T) -> bool,
             {
                 leaf_node
                     .content_points
@@ -111,10 +151,12 @@ macro_rules! generate_nearest_one {
                     .enumerate()
                     .take(leaf_node.size.az::<usize>())
                     .for_each(|(idx, entry)| {
-                        let dist = D::dist(query, entry);
-                        if dist < nearest.distance {
-                            nearest.distance = dist;
-                            nearest.item = unsafe { *leaf_node.content_items.get_unchecked(idx) };
+                        let dist = metric.dist(query, entry);
+                        if dist < nearest.distance() {
+                            let item = unsafe { *leaf_node.content_items.get_unchecked(idx) };
+                            if filter(item) {
+                                *nearest = N::from_leaf_entry(dist, item, entry);
+                            }
                         }
                     });
             }
@@ -0,0 +1,24 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_one_batch {
+    ($comments:tt) => {
+        generate_nearest_one_batch!($comments, Axis);
+    };
+    ($comments:tt, $($output_bound:tt)+) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_one_batch<D>(&self, queries: &[[A; K]]) -> Vec<NearestNeighbour<D::Output, T>>
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: $($output_bound)+,
+                    Self: Sync,
+            {
+                queries
+                    .par_iter()
+                    .map(|query| self.nearest_one::<D>(query))
+                    .collect()
+            }
+        }
+    };
+}
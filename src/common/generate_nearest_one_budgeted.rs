@@ -0,0 +1,138 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_one_budgeted {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_one_budgeted<D>(
+                &self,
+                query: &[A; K],
+                budget: SearchBudget,
+            ) -> BudgetedResult<Option<NearestNeighbour<D::Output, T>>>
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut nearest = NearestNeighbour { distance: D::Output::max_value(), item: T::zero() };
+                let mut budget = BudgetTracker::new(budget);
+
+                unsafe {
+                    self.nearest_one_budgeted_recurse::<D>(
+                        query,
+                        self.root_index,
+                        0,
+                        &mut nearest,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &mut budget,
+                    )
+                }
+
+                // the search only keeps items strictly nearer than the initial nearest,
+                // so it is unchanged if the budget ran out before any leaf was searched
+                budget.into_result((nearest.distance < D::Output::max_value()).then_some(nearest))
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn nearest_one_budgeted_recurse<D>(
+                &self,
+                query: &[A; K],
+                curr_node_idx: IDX,
+                split_dim: usize,
+                nearest: &mut NearestNeighbour<D::Output, T>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                budget: &mut BudgetTracker,
+            )
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis,
+            {
+                if is_stem_index(curr_node_idx) {
+                    if !budget.visit_stem() {
+                        return;
+                    }
+
+                    let node = &self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let mut rd = rd;
+                    let old_off = off[split_dim];
+                    let new_off = query[split_dim].saturating_dist(node.split_val);
+
+                    let [closer_node_idx, further_node_idx] =
+                        if *query.get_unchecked(split_dim) < node.split_val {
+                            [node.left, node.right]
+                        } else {
+                            [node.right, node.left]
+                        };
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    self.nearest_one_budgeted_recurse::<D>(
+                        query,
+                        closer_node_idx,
+                        next_split_dim,
+                        nearest,
+                        off,
+                        rd,
+                        min,
+                        max,
+                        budget,
+                    );
+
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
+
+                    if rd <= nearest.distance {
+                        off[split_dim] = new_off;
+                        self.nearest_one_budgeted_recurse::<D>(
+                            query,
+                            further_node_idx,
+                            next_split_dim,
+                            nearest,
+                            off,
+                            rd,
+                            min,
+                            max,
+                            budget,
+                        );
+                        off[split_dim] = old_off;
+                    }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+                } else {
+                    if !budget.visit_leaf() {
+                        return;
+                    }
+
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    Self::search_content_for_nearest(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        nearest,
+                        leaf_node,
+                        &|_| true,
+                    );
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,39 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_one_filtered {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_one_filtered<D, F>(&self, query: &[A; K], filter: F) -> Option<NearestNeighbour<D::Output, T>>
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis,
+                    F: Fn(T) -> bool,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                let nearest = unsafe {
+                    self.nearest_one_recurse(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        self.root_index,
+                        0,
+                        NearestNeighbour { distance: D::Output::max_value(), item: T::zero() },
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &filter,
+                    )
+                };
+
+                // only an item that passed the filter can have replaced the initial
+                // nearest, as the search only keeps items strictly nearer than it
+                (nearest.distance < D::Output::max_value()).then_some(nearest)
+            }
+        }
+    };
+}
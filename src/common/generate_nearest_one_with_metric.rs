@@ -0,0 +1,34 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_one_with_metric {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_one_with_metric<D>(&self, metric: &D, query: &[A; K]) -> NearestNeighbour<D::Output, T>
+                where
+                    D: StatefulDistanceMetric<A, K>,
+                    D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                unsafe {
+                    self.nearest_one_recurse(
+                        metric,
+                        query,
+                        self.root_index,
+                        0,
+                        NearestNeighbour { distance: D::Output::max_value(), item: T::zero() },
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &|_| true,
+                    )
+                }
+            }
+        }
+    };
+}
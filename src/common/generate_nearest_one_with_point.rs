@@ -0,0 +1,34 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_nearest_one_with_point {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_one_with_point<D>(&self, query: &[A; K]) -> PointNeighbour<A, T, K, D::Output>
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                unsafe {
+                    self.nearest_one_recurse(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        self.root_index,
+                        0,
+                        PointNeighbour { distance: D::Output::max_value(), item: T::zero(), point: [A::zero(); K] },
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &|_| true,
+                    )
+                }
+            }
+        }
+    };
+}
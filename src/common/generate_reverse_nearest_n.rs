@@ -0,0 +1,227 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_reverse_nearest_n {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn reverse_nearest_n<D>(&self, query: &[A; K], k: usize) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                if k > 0 {
+                    unsafe {
+                        let mut subtree_sizes = vec![0; self.stems.len()];
+                        self.reverse_nearest_n_subtree_sizes(self.root_index, &mut subtree_sizes);
+
+                        self.reverse_nearest_n_recurse::<D>(
+                            query,
+                            k,
+                            self.root_index,
+                            0,
+                            &subtree_sizes,
+                            &mut Vec::new(),
+                            &mut matching_items,
+                            &mut off,
+                            D::Output::zero(),
+                            &mut min,
+                            &mut max,
+                        );
+                    }
+                }
+
+                matching_items.sort();
+                matching_items
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn reverse_nearest_n_recurse<D>(
+                &self,
+                query: &[A; K],
+                k: usize,
+                curr_node_idx: IDX,
+                split_dim: usize,
+                subtree_sizes: &[usize],
+                leaf_dists: &mut Vec<D::Output>,
+                matching_items: &mut Vec<NearestNeighbour<D::Output, T>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                // if the subtree holds more than k points, each of them has k
+                // neighbours no further away than the diameter of its bounding box,
+                // so none of them can have a query point beyond that among their k nearest
+                if rd > Self::reverse_nearest_n_box_diameter::<D>(query, min, max)
+                    && self.reverse_nearest_n_subtree_size(curr_node_idx, subtree_sizes) > k
+                {
+                    return;
+                }
+
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let old_off = off[split_dim];
+                    let new_off = query[split_dim].saturating_dist(node.split_val);
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+
+                    let is_left = *query.get_unchecked(split_dim) < node.split_val;
+                    let [closer_node_idx, further_node_idx] = if is_left {
+                        [node.left, node.right]
+                    } else {
+                        [node.right, node.left]
+                    };
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    if is_left {
+                        if node.split_val < old_max {
+                            max[split_dim] = node.split_val;
+                        }
+                    } else if node.split_val > old_min {
+                        min[split_dim] = node.split_val;
+                    }
+                    self.reverse_nearest_n_recurse::<D>(
+                        query,
+                        k,
+                        closer_node_idx,
+                        next_split_dim,
+                        subtree_sizes,
+                        leaf_dists,
+                        matching_items,
+                        off,
+                        rd,
+                        min,
+                        max,
+                    );
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+
+                    if is_left {
+                        if node.split_val > old_min {
+                            min[split_dim] = node.split_val;
+                        }
+                    } else if node.split_val < old_max {
+                        max[split_dim] = node.split_val;
+                    }
+                    let rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
+
+                    off[split_dim] = new_off;
+                    self.reverse_nearest_n_recurse::<D>(
+                        query,
+                        k,
+                        further_node_idx,
+                        next_split_dim,
+                        subtree_sizes,
+                        leaf_dists,
+                        matching_items,
+                        off,
+                        rd,
+                        min,
+                        max,
+                    );
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+                    off[split_dim] = old_off;
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    let points = &leaf_node.content_points[..leaf_node.size.az::<usize>()];
+
+                    points.iter().enumerate().for_each(|(idx, entry)| {
+                        let distance = D::dist(query, entry);
+
+                        // if the leaf holds more than k points, the k nearest to each of them
+                        // are no further away than the k nearest within the leaf, which are
+                        // much cheaper to find than running a query from the point
+                        if points.len() > k {
+                            leaf_dists.clear();
+                            leaf_dists.extend(points.iter().map(|other| D::dist(entry, other)));
+                            let (_, kth_dist, _) = leaf_dists.select_nth_unstable_by(k, |a, b| {
+                                a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+                            });
+                            if distance > *kth_dist {
+                                return;
+                            }
+                        }
+
+                        // the nearest k + 1 items to a stored point include the point itself
+                        let nearest = self.nearest_n::<D>(entry, k + 1);
+                        if nearest.len() <= k || distance <= nearest[k].distance {
+                            let item = *leaf_node.content_items.get_unchecked(idx);
+                            matching_items.push(NearestNeighbour { distance, item });
+                        }
+                    });
+                }
+            }
+
+            /// Returns the distance between opposite corners of the box bounded by `min`
+            /// and `max`, or the maximum value of `A` if any side of the box has not
+            /// yet been bounded by a split or the metric implements `lower_bound_to_box`.
+            fn reverse_nearest_n_box_diameter<D>(query: &[A; K], min: &[A; K], max: &[A; K]) -> D::Output
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                // the diameter is built from per-axis distances, which a metric that bounds
+                // boxes itself isn't necessarily made of
+                if D::lower_bound_to_box(query, min, max).is_some() {
+                    return D::Output::max_value();
+                }
+
+                let mut diameter = D::Output::zero();
+
+                for dim in 0..K {
+                    if min[dim] == A::min_value() || max[dim] == A::max_value() {
+                        return D::Output::max_value();
+                    }
+
+                    diameter = Axis::rd_accumulate(diameter, D::dist1(max[dim], min[dim]), D::ACCUMULATION);
+                }
+
+                diameter
+            }
+
+            /// Records the number of points under every stem in `subtree_sizes`, so that
+            /// the pruning checks don't have to walk the subtree each time.
+            unsafe fn reverse_nearest_n_subtree_sizes(&self, curr_node_idx: IDX, subtree_sizes: &mut [usize]) -> usize {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    let size = self.reverse_nearest_n_subtree_sizes(node.left, subtree_sizes)
+                        + self.reverse_nearest_n_subtree_sizes(node.right, subtree_sizes);
+                    subtree_sizes[curr_node_idx.az::<usize>()] = size;
+
+                    size
+                } else {
+                    self.reverse_nearest_n_subtree_size(curr_node_idx, subtree_sizes)
+                }
+            }
+
+            unsafe fn reverse_nearest_n_subtree_size(&self, curr_node_idx: IDX, subtree_sizes: &[usize]) -> usize {
+                if is_stem_index(curr_node_idx) {
+                    subtree_sizes[curr_node_idx.az::<usize>()]
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node.size.az::<usize>()
+                }
+            }
+        }
+    };
+}
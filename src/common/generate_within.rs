@@ -5,9 +5,10 @@ macro_rules! generate_within {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn within<D>(&self, query: &[A; K], dist: A) -> Vec<NearestNeighbour<A, T>>
+            pub fn within<D>(&self, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 let mut matching_items = self.within_unsorted::<D>(query, dist);
                 matching_items.sort();
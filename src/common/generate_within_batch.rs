@@ -0,0 +1,20 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_batch {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_batch<D>(&self, queries: &[[A; K]], dist: D::Output) -> BatchResults<D::Output, T>
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis,
+                    Self: Sync,
+            {
+                BatchResults::from_par_queries(queries, |query, results| {
+                    self.within_into::<D>(query, dist, results)
+                })
+            }
+        }
+    };
+}
@@ -0,0 +1,15 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_box {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_box(&self, min: &[A; K], max: &[A; K]) -> Vec<T> {
+                let mut matching_items = self.within_box_unsorted(min, max);
+                matching_items.sort();
+                matching_items
+            }
+        }
+    };
+}
@@ -0,0 +1,79 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_box_unsorted {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_box_unsorted(&self, min: &[A; K], max: &[A; K]) -> Vec<T> {
+                let mut matching_items = Vec::new();
+
+                unsafe {
+                    self.within_box_unsorted_recurse(
+                        min,
+                        max,
+                        self.root_index,
+                        0,
+                        &mut matching_items,
+                    );
+                }
+
+                matching_items
+            }
+
+            unsafe fn within_box_unsorted_recurse(
+                &self,
+                min: &[A; K],
+                max: &[A; K],
+                curr_node_idx: IDX,
+                split_dim: usize,
+                matching_items: &mut Vec<T>,
+            ) {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    if *min.get_unchecked(split_dim) < node.split_val {
+                        self.within_box_unsorted_recurse(
+                            min,
+                            max,
+                            node.left,
+                            next_split_dim,
+                            matching_items,
+                        );
+                    }
+
+                    if *max.get_unchecked(split_dim) >= node.split_val {
+                        self.within_box_unsorted_recurse(
+                            min,
+                            max,
+                            node.right,
+                            next_split_dim,
+                            matching_items,
+                        );
+                    }
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .enumerate()
+                        .take(leaf_node.size.az::<usize>())
+                        .for_each(|(idx, entry)| {
+                            let is_inside = entry
+                                .iter()
+                                .zip(min.iter().zip(max.iter()))
+                                .all(|(val, (lo, hi))| val >= lo && val <= hi);
+
+                            if is_inside {
+                                matching_items.push(*leaf_node.content_items.get_unchecked(idx));
+                            }
+                        });
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,87 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_box_unsorted_iter {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_box_unsorted_iter(
+                &'a self,
+                min: &'a [A; K],
+                max: &'a [A; K],
+            ) -> WithinBoxUnsortedIter<'a, T> {
+                let gen = Gn::new_scoped(move |gen_scope| {
+                    unsafe {
+                        self.within_box_unsorted_iter_recurse(
+                            min,
+                            max,
+                            self.root_index,
+                            0,
+                            gen_scope,
+                        );
+                    }
+
+                    done!();
+                });
+
+                WithinBoxUnsortedIter::new(gen)
+            }
+
+            unsafe fn within_box_unsorted_iter_recurse(
+                &'a self,
+                min: &[A; K],
+                max: &[A; K],
+                curr_node_idx: IDX,
+                split_dim: usize,
+                mut gen_scope: Scope<'a, (), T>,
+            ) -> Scope<'a, (), T> {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    if *min.get_unchecked(split_dim) < node.split_val {
+                        gen_scope = self.within_box_unsorted_iter_recurse(
+                            min,
+                            max,
+                            node.left,
+                            next_split_dim,
+                            gen_scope,
+                        );
+                    }
+
+                    if *max.get_unchecked(split_dim) >= node.split_val {
+                        gen_scope = self.within_box_unsorted_iter_recurse(
+                            min,
+                            max,
+                            node.right,
+                            next_split_dim,
+                            gen_scope,
+                        );
+                    }
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .enumerate()
+                        .take(leaf_node.size.az::<usize>())
+                        .for_each(|(idx, entry)| {
+                            let is_inside = entry
+                                .iter()
+                                .zip(min.iter().zip(max.iter()))
+                                .all(|(val, (lo, hi))| val >= lo && val <= hi);
+
+                            if is_inside {
+                                gen_scope.yield_(*leaf_node.content_items.get_unchecked(idx));
+                            }
+                        });
+                }
+
+                gen_scope
+            }
+        }
+    };
+}
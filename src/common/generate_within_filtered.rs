@@ -0,0 +1,40 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_filtered {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_filtered<D, F>(&self, query: &[A; K], dist: D::Output, filter: F) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                F: Fn(T) -> bool,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                unsafe {
+                    self.within_unsorted_recurse(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        dist,
+                        self.root_index,
+                        0,
+                        &mut matching_items,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &filter,
+                    );
+                }
+
+                matching_items.sort();
+                matching_items
+            }
+        }
+    };
+}
@@ -0,0 +1,118 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_region {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_region<R>(&self, region: &R) -> Vec<T>
+            where
+                R: QueryRegion<A, K>,
+            {
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                unsafe {
+                    self.within_region_recurse(
+                        region,
+                        self.root_index,
+                        0,
+                        &mut min,
+                        &mut max,
+                        &mut matching_items,
+                    );
+                }
+
+                matching_items
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            unsafe fn within_region_recurse<R>(
+                &self,
+                region: &R,
+                curr_node_idx: IDX,
+                split_dim: usize,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                matching_items: &mut Vec<T>,
+            ) where
+                R: QueryRegion<A, K>,
+            {
+                if !region.intersects_box(min, max) {
+                    return;
+                }
+
+                if region.contains_box(min, max) {
+                    self.within_region_add_all(curr_node_idx, matching_items);
+                    return;
+                }
+
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+                    let next_split_dim = (split_dim + 1).rem(K);
+
+                    let old_max = max[split_dim];
+                    if node.split_val < old_max {
+                        max[split_dim] = node.split_val;
+                    }
+                    self.within_region_recurse(
+                        region,
+                        node.left,
+                        next_split_dim,
+                        min,
+                        max,
+                        matching_items,
+                    );
+                    max[split_dim] = old_max;
+
+                    let old_min = min[split_dim];
+                    if node.split_val > old_min {
+                        min[split_dim] = node.split_val;
+                    }
+                    self.within_region_recurse(
+                        region,
+                        node.right,
+                        next_split_dim,
+                        min,
+                        max,
+                        matching_items,
+                    );
+                    min[split_dim] = old_min;
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .enumerate()
+                        .take(leaf_node.size.az::<usize>())
+                        .for_each(|(idx, entry)| {
+                            if region.contains_point(entry) {
+                                matching_items.push(*leaf_node.content_items.get_unchecked(idx));
+                            }
+                        });
+                }
+            }
+
+            unsafe fn within_region_add_all(&self, curr_node_idx: IDX, matching_items: &mut Vec<T>) {
+                if is_stem_index(curr_node_idx) {
+                    let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
+
+                    self.within_region_add_all(node.left, matching_items);
+                    self.within_region_add_all(node.right, matching_items);
+                } else {
+                    let leaf_node = self
+                        .leaves
+                        .get_unchecked((curr_node_idx - IDX::leaf_offset()).az::<usize>());
+
+                    matching_items.extend_from_slice(
+                        &leaf_node.content_items[..leaf_node.size.az::<usize>()],
+                    );
+                }
+            }
+        }
+    };
+}
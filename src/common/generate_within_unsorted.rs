@@ -5,22 +5,29 @@ macro_rules! generate_within_unsorted {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn within_unsorted<D>(&self, query: &[A; K], dist: A) -> Vec<NearestNeighbour<A, T>>
+            pub fn within_unsorted<D>(&self, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
                 let mut matching_items = Vec::new();
 
                 unsafe {
-                    self.within_unsorted_recurse::<D>(
+                    self.within_unsorted_recurse(
+                        &StatelessMetric::<D>::new(),
                         query,
                         dist,
                         self.root_index,
                         0,
                         &mut matching_items,
                         &mut off,
-                        A::zero(),
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &|_| true,
                     );
                 }
 
@@ -28,17 +35,24 @@ macro_rules! generate_within_unsorted {
             }
 
             #[allow(clippy::too_many_arguments)]
-            unsafe fn within_unsorted_recurse<D>(
+            pub(crate) unsafe fn within_unsorted_recurse<D, N, F>(
                 &self,
+                metric: &D,
                 query: &[A; K],
-                radius: A,
+                radius: D::Output,
                 curr_node_idx: IDX,
                 split_dim: usize,
-                matching_items: &mut Vec<NearestNeighbour<A, T>>,
+                matching_items: &mut Vec<N>,
                 off: &mut [A; K],
-                rd: A,
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                filter: &F,
             ) where
-                D: DistanceMetric<A, K>,
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+                N: FromLeafEntry<A, T, K, D::Output>,
+                F: Fn(T) -> bool,
             {
                 if is_stem_index(curr_node_idx) {
                     let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
@@ -55,7 +69,8 @@ macro_rules! generate_within_unsorted {
                         };
                     let next_split_dim = (split_dim + 1).rem(K);
 
-                    self.within_unsorted_recurse::<D>(
+                    self.within_unsorted_recurse::<D, N, F>(
+                        metric,
                         query,
                         radius,
                         closer_node_idx,
@@ -63,13 +78,26 @@ macro_rules! generate_within_unsorted {
                         matching_items,
                         off,
                         rd,
+                        min,
+                        max,
+                        filter,
                     );
 
-                    rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = metric.lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, metric.dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
 
                     if rd <= radius {
                         off[split_dim] = new_off;
-                        self.within_unsorted_recurse::<D>(
+                        self.within_unsorted_recurse::<D, N, F>(
+                            metric,
                             query,
                             radius,
                             further_node_idx,
@@ -77,9 +105,14 @@ macro_rules! generate_within_unsorted {
                             matching_items,
                             off,
                             rd,
+                            min,
+                            max,
+                            filter,
                         );
                         off[split_dim] = old_off;
                     }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
                 } else {
                     let leaf_node = self
                         .leaves
@@ -91,13 +124,13 @@ macro_rules! generate_within_unsorted {
                         .enumerate()
                         .take(leaf_node.size.az::<usize>())
                         .for_each(|(idx, entry)| {
-                            let distance = D::dist(query, entry);
+                            let distance = metric.dist(query, entry);
 
                             if distance < radius {
-                                matching_items.push(NearestNeighbour {
-                                    distance,
-                                    item: *leaf_node.content_items.get_unchecked(idx.az::<usize>()),
-                                })
+                                let item = *leaf_node.content_items.get_unchecked(idx.az::<usize>());
+                                if filter(item) {
+                                    matching_items.push(N::from_leaf_entry(distance, item, entry))
+                                }
                             }
                         });
                 }
@@ -0,0 +1,36 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_unsorted_into {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_unsorted_into<D>(&self, query: &[A; K], dist: D::Output, results: &mut Vec<NearestNeighbour<D::Output, T>>)
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                results.clear();
+
+                unsafe {
+                    self.within_unsorted_recurse(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        dist,
+                        self.root_index,
+                        0,
+                        results,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &|_| true,
+                    );
+                }
+            }
+        }
+    };
+}
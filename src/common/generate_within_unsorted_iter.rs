@@ -8,12 +8,15 @@ macro_rules! generate_within_unsorted_iter {
             pub fn within_unsorted_iter<D>(
                 &'a self,
                 query: &'a [A; K],
-                dist: A,
-            ) -> WithinUnsortedIter<'a, A, T>
+                dist: D::Output,
+            ) -> WithinUnsortedIter<'a, D::Output, T>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
 
                 let gen = Gn::new_scoped(move |gen_scope| {
                     unsafe {
@@ -24,7 +27,9 @@ macro_rules! generate_within_unsorted_iter {
                             0,
                             gen_scope,
                             &mut off,
-                            A::zero(),
+                            D::Output::zero(),
+                            &mut min,
+                            &mut max,
                         );
                     }
 
@@ -38,15 +43,18 @@ macro_rules! generate_within_unsorted_iter {
             unsafe fn within_unsorted_iter_recurse<D>(
                 &'a self,
                 query: &[A; K],
-                radius: A,
+                radius: D::Output,
                 curr_node_idx: IDX,
                 split_dim: usize,
-                mut gen_scope: Scope<'a, (), NearestNeighbour<A, T>>,
+                mut gen_scope: Scope<'a, (), NearestNeighbour<D::Output, T>>,
                 off: &mut [A; K],
-                rd: A,
-            ) -> Scope<(), NearestNeighbour<A, T>>
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) -> Scope<'a, (), NearestNeighbour<D::Output, T>>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 if is_stem_index(curr_node_idx) {
                     let node = self.stems.get_unchecked(curr_node_idx.az::<usize>());
@@ -71,9 +79,20 @@ macro_rules! generate_within_unsorted_iter {
                         gen_scope,
                         off,
                         rd,
+                        min,
+                        max,
                     );
 
-                    rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                    let old_min = min[split_dim];
+                    let old_max = max[split_dim];
+                    if query[split_dim] < node.split_val {
+                        min[split_dim] = node.split_val;
+                    } else {
+                        max[split_dim] = node.split_val;
+                    }
+                    rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    });
 
                     if rd <= radius {
                         off[split_dim] = new_off;
@@ -85,9 +104,13 @@ macro_rules! generate_within_unsorted_iter {
                             gen_scope,
                             off,
                             rd,
+                            min,
+                            max,
                         );
                         off[split_dim] = old_off;
                     }
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
                 } else {
                     let leaf_node = self
                         .leaves
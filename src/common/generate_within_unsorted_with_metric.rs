@@ -0,0 +1,38 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_unsorted_with_metric {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_unsorted_with_metric<D>(&self, metric: &D, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                unsafe {
+                    self.within_unsorted_recurse(
+                        metric,
+                        query,
+                        dist,
+                        self.root_index,
+                        0,
+                        &mut matching_items,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &|_| true,
+                    );
+                }
+
+                matching_items
+            }
+        }
+    };
+}
@@ -0,0 +1,19 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_with_metric {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_with_metric<D>(&self, metric: &D, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut matching_items = self.within_unsorted_with_metric(metric, query, dist);
+                matching_items.sort();
+                matching_items
+            }
+        }
+    };
+}
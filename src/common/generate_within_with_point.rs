@@ -0,0 +1,19 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_within_with_point {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_with_point<D>(&self, query: &[A; K], dist: D::Output) -> Vec<PointNeighbour<A, T, K, D::Output>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut matching_items = self.within_unsorted_with_point::<D>(query, dist);
+                matching_items.sort();
+                matching_items
+            }
+        }
+    };
+}
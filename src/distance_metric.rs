@@ -1,11 +1,61 @@
 //! The trait that needs to be implemented by any distance metrics
 
+use std::marker::PhantomData;
+
+/// How the contributions of each axis are combined into a distance by a metric.
+///
+/// The queries combine per-axis lower bounds in the same way when pruning.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Accumulation {
+    /// contributions are added together, as with Manhattan or squared Euclidean distance
+    Sum,
+    /// the largest contribution is the distance, as with Chebyshev distance
+    Max,
+}
+
 /// Trait that needs to be implemented by any potential distance
 /// metric to be used within queries
+///
+/// # Migrating from Kiddo 4
+///
+/// [`Output`](Self::Output) has no default, so metrics written for earlier
+/// versions need to declare it. Setting it to the co-ordinate type keeps
+/// their behaviour unchanged:
+///
+/// ```rust
+/// use kiddo::distance_metric::DistanceMetric;
+///
+/// pub struct MyManhattan {}
+///
+/// impl<const K: usize> DistanceMetric<f64, K> for MyManhattan {
+///     type Output = f64;
+///
+///     fn dist(a: &[f64; K], b: &[f64; K]) -> f64 {
+///         a.iter().zip(b.iter()).map(|(a, b)| (a - b).abs()).sum()
+///     }
+///
+///     fn dist1(a: f64, b: f64) -> f64 {
+///         (a - b).abs()
+///     }
+/// }
+/// ```
 pub trait DistanceMetric<A, const K: usize> {
+    /// the type of the distances returned by this metric.
+    ///
+    /// This is usually `A`, but can be a wider type than the co-ordinates, such
+    /// as `f64` distances between `f32` points, so that the squared distances
+    /// between points with large co-ordinates don't overflow or lose precision.
+    /// The queries return distances, and take radii, of this type, and need it
+    /// to implement the `Axis` trait of the tree being queried.
+    type Output;
+
+    /// how the per-axis distances returned by `dist1` are combined. Defaults to
+    /// [`Accumulation::Sum`].
+    const ACCUMULATION: Accumulation = Accumulation::Sum;
+
     /// returns the distance between two K-d points, as measured
     /// by a particular distance metric
-    fn dist(a: &[A; K], b: &[A; K]) -> A;
+    fn dist(a: &[A; K], b: &[A; K]) -> Self::Output;
 
     /// returns the distance between two points along a single axis,
     /// as measured by a particular distance metric.
@@ -13,5 +63,195 @@ pub trait DistanceMetric<A, const K: usize> {
     /// (needs to be implemented as it is used by the NN query implementations
     /// to extend the min acceptable distance for a node when recursing
     /// back up the tree)
-    fn dist1(a: A, b: A) -> A;
+    fn dist1(a: A, b: A) -> Self::Output;
+
+    /// returns a lower bound on the distance between two points whose
+    /// co-ordinates along axis `dim` are `a` and `b`.
+    ///
+    /// This is what the queries use when pruning. It defaults to `dist1`, which is
+    /// correct for metrics that treat every axis the same way. Metrics whose axes
+    /// have different meanings (such as latitude and longitude) can override it.
+    #[inline]
+    fn dist1_on_axis(a: A, b: A, _dim: usize) -> Self::Output {
+        Self::dist1(a, b)
+    }
+
+    /// returns a lower bound on the distance between `query` and any point
+    /// inside the box bounded by `min` and `max`, or `None` to have the queries
+    /// build one from `dist1_on_axis` and [`ACCUMULATION`](Self::ACCUMULATION).
+    ///
+    /// The queries call this whenever they consider visiting a node that doesn't
+    /// contain the query point. The box is guaranteed to contain every point in that
+    /// node, but may be larger: sides that have not yet been bounded by a split are
+    /// `A::min_value()` or `A::max_value()`. When this returns `Some`, the per-axis
+    /// bound is not used, so metrics that don't split into a contribution from each
+    /// axis (such as geodesic distances) can implement this instead.
+    ///
+    /// Defaults to `None`. The periodic queries and the node-to-node bounds in
+    /// [`dual_tree`](crate::dual_tree) don't have a single box to pass here, and
+    /// always build their lower bounds from `dist1_on_axis`.
+    #[inline]
+    fn lower_bound_to_box(_query: &[A; K], _min: &[A; K], _max: &[A; K]) -> Option<Self::Output> {
+        None
+    }
+
+    /// returns an upper bound on the distance between `query` and any point
+    /// inside the box bounded by `min` and `max`, or `None` to have the queries
+    /// build one from `dist1` and [`ACCUMULATION`](Self::ACCUMULATION).
+    ///
+    /// This is used by `count_within`, `farthest_n` and `within_annulus`. Sides of
+    /// the box that have not yet been bounded by a split are `A::min_value()` or
+    /// `A::max_value()`, as with [`lower_bound_to_box`](Self::lower_bound_to_box).
+    ///
+    /// Defaults to `None`. A metric that implements `lower_bound_to_box` isn't
+    /// assumed to be built from `dist1`, so unless it implements this too, these
+    /// queries treat the distance to the far side of every box as unknown, which
+    /// is correct but stops them from skipping or bulk-counting whole nodes.
+    /// `reverse_nearest_n` prunes such metrics by their lower bounds only.
+    #[inline]
+    fn upper_bound_to_box(_query: &[A; K], _min: &[A; K], _max: &[A; K]) -> Option<Self::Output> {
+        None
+    }
+}
+
+/// Trait that needs to be implemented by distance metrics that carry runtime
+/// parameters, such as per-axis weights, for use with the `_with_metric` queries.
+///
+/// Unlike [`DistanceMetric`], the methods take `&self`, so the metric is passed
+/// to the query as a value. Every [`DistanceMetric`] implements this trait too,
+/// so `&SquaredEuclidean {}` can be passed to the `_with_metric` queries as well.
+pub trait StatefulDistanceMetric<A, const K: usize> {
+    /// the type of the distances returned by this metric.
+    /// See [`DistanceMetric::Output`].
+    type Output;
+
+    /// how the per-axis distances returned by `dist1` are combined.
+    /// See [`DistanceMetric::ACCUMULATION`].
+    const ACCUMULATION: Accumulation = Accumulation::Sum;
+
+    /// returns the distance between two K-d points, as measured
+    /// by this metric
+    fn dist(&self, a: &[A; K], b: &[A; K]) -> Self::Output;
+
+    /// returns the distance between two points along a single axis,
+    /// as measured by this metric. See [`DistanceMetric::dist1`].
+    fn dist1(&self, a: A, b: A) -> Self::Output;
+
+    /// returns a lower bound on the distance between two points whose
+    /// co-ordinates along axis `dim` are `a` and `b`. Defaults to `dist1`.
+    /// See [`DistanceMetric::dist1_on_axis`].
+    #[inline]
+    fn dist1_on_axis(&self, a: A, b: A, _dim: usize) -> Self::Output {
+        self.dist1(a, b)
+    }
+
+    /// returns a lower bound on the distance between `query` and any point
+    /// inside the box bounded by `min` and `max`, or `None` (the default) to
+    /// use `dist1_on_axis`. See [`DistanceMetric::lower_bound_to_box`].
+    #[inline]
+    fn lower_bound_to_box(
+        &self,
+        _query: &[A; K],
+        _min: &[A; K],
+        _max: &[A; K],
+    ) -> Option<Self::Output> {
+        None
+    }
+
+    /// returns an upper bound on the distance between `query` and any point
+    /// inside the box bounded by `min` and `max`, or `None` (the default) to
+    /// use `dist1`. See [`DistanceMetric::upper_bound_to_box`].
+    #[inline]
+    fn upper_bound_to_box(
+        &self,
+        _query: &[A; K],
+        _min: &[A; K],
+        _max: &[A; K],
+    ) -> Option<Self::Output> {
+        None
+    }
+}
+
+impl<A, const K: usize, D: DistanceMetric<A, K>> StatefulDistanceMetric<A, K> for D {
+    type Output = D::Output;
+
+    const ACCUMULATION: Accumulation = D::ACCUMULATION;
+
+    #[inline]
+    fn dist(&self, a: &[A; K], b: &[A; K]) -> Self::Output {
+        D::dist(a, b)
+    }
+
+    #[inline]
+    fn dist1(&self, a: A, b: A) -> Self::Output {
+        D::dist1(a, b)
+    }
+
+    #[inline]
+    fn dist1_on_axis(&self, a: A, b: A, dim: usize) -> Self::Output {
+        D::dist1_on_axis(a, b, dim)
+    }
+
+    #[inline]
+    fn lower_bound_to_box(
+        &self,
+        query: &[A; K],
+        min: &[A; K],
+        max: &[A; K],
+    ) -> Option<Self::Output> {
+        D::lower_bound_to_box(query, min, max)
+    }
+
+    #[inline]
+    fn upper_bound_to_box(
+        &self,
+        query: &[A; K],
+        min: &[A; K],
+        max: &[A; K],
+    ) -> Option<Self::Output> {
+        D::upper_bound_to_box(query, min, max)
+    }
+}
+
+/// A value standing in for the stateless metric `D`, so that the queries that
+/// take a metric as a type parameter can share their searches with the
+/// `_with_metric` queries.
+pub(crate) struct StatelessMetric<D>(PhantomData<D>);
+
+impl<D> StatelessMetric<D> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A, const K: usize, D: DistanceMetric<A, K>> DistanceMetric<A, K> for StatelessMetric<D> {
+    type Output = D::Output;
+
+    const ACCUMULATION: Accumulation = D::ACCUMULATION;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> Self::Output {
+        D::dist(a, b)
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> Self::Output {
+        D::dist1(a, b)
+    }
+
+    #[inline]
+    fn dist1_on_axis(a: A, b: A, dim: usize) -> Self::Output {
+        D::dist1_on_axis(a, b, dim)
+    }
+
+    #[inline]
+    fn lower_bound_to_box(query: &[A; K], min: &[A; K], max: &[A; K]) -> Option<Self::Output> {
+        D::lower_bound_to_box(query, min, max)
+    }
+
+    #[inline]
+    fn upper_bound_to_box(query: &[A; K], min: &[A; K], max: &[A; K]) -> Option<Self::Output> {
+        D::upper_bound_to_box(query, min, max)
+    }
 }
@@ -0,0 +1,692 @@
+//! Dual-tree spatial joins between a pair of trees
+//!
+//! Rather than issuing one query per point, these traverse both trees at once, pruning
+//! whole pairs of nodes whose bounding boxes are too far apart to contain a match.
+use az::{Az, Cast};
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+use std::marker::PhantomData;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{is_stem_index, Content, Index};
+
+/// Trait implemented by any tree that can take part in a [`DualTree`] join.
+///
+/// Implemented for the float [`KdTree`] and [`ImmutableKdTree`], as well as
+/// their archived `rkyv` equivalents.
+pub trait DualTreeIndex<A: Axis, const K: usize> {
+    /// the type of the items stored in the tree
+    type Item: Content;
+
+    #[doc(hidden)]
+    type NodeId: Copy;
+
+    #[doc(hidden)]
+    fn dual_tree_root(&self) -> Self::NodeId;
+
+    /// returns `None` for leaves, or the split value and left / right children for stems
+    #[doc(hidden)]
+    fn dual_tree_children(&self, node: Self::NodeId) -> Option<(A, Self::NodeId, Self::NodeId)>;
+
+    /// appends the points and items stored in the leaf `node` onto `contents`
+    #[doc(hidden)]
+    fn dual_tree_leaf_contents(&self, node: Self::NodeId, contents: &mut Vec<([A; K], Self::Item)>);
+}
+
+macro_rules! impl_dual_tree_index_float {
+    ($tree:ident, $($bounds:tt)*) => {
+        impl<$($bounds)*> DualTreeIndex<A, K> for $tree<A, T, K, B, IDX>
+        where
+            usize: Cast<IDX>,
+        {
+            type Item = T;
+            type NodeId = IDX;
+
+            fn dual_tree_root(&self) -> IDX {
+                self.root_index
+            }
+
+            fn dual_tree_children(&self, node: IDX) -> Option<(A, IDX, IDX)> {
+                if !is_stem_index(node) {
+                    return None;
+                }
+
+                let stem = &self.stems[node.az::<usize>()];
+                Some((stem.split_val, stem.left, stem.right))
+            }
+
+            fn dual_tree_leaf_contents(&self, node: IDX, contents: &mut Vec<([A; K], T)>) {
+                let leaf_node = &self.leaves[(node - IDX::leaf_offset()).az::<usize>()];
+
+                contents.extend(
+                    leaf_node
+                        .content_points
+                        .iter()
+                        .zip(leaf_node.content_items.iter())
+                        .take(leaf_node.size.az::<usize>())
+                        .map(|(point, item)| (*point, *item)),
+                );
+            }
+        }
+    };
+}
+
+impl_dual_tree_index_float!(
+    KdTree,
+    A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>
+);
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl_dual_tree_index_float!(
+    ArchivedKdTree,
+    A: Axis + rkyv::Archive<Archived = A>,
+    T: Content + rkyv::Archive<Archived = T>,
+    const K: usize,
+    const B: usize,
+    IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>
+);
+
+macro_rules! impl_dual_tree_index_immutable {
+    ($tree:ident, $($bounds:tt)*) => {
+        impl<$($bounds)*> DualTreeIndex<A, K> for $tree<A, T, K, B> {
+            type Item = T;
+            type NodeId = usize;
+
+            fn dual_tree_root(&self) -> usize {
+                1
+            }
+
+            fn dual_tree_children(&self, node: usize) -> Option<(A, usize, usize)> {
+                if node >= self.stems.len() {
+                    return None;
+                }
+
+                let left_child_idx = node << 1;
+                Some((self.stems[node], left_child_idx, left_child_idx + 1))
+            }
+
+            fn dual_tree_leaf_contents(&self, node: usize, contents: &mut Vec<([A; K], T)>) {
+                let Some(leaf_node) = self.leaf_for_node_idx(node) else {
+                    return;
+                };
+
+                contents.extend((0..leaf_node.size as usize).map(|idx| {
+                    (
+                        std::array::from_fn(|dim| leaf_node.content_points[dim][idx]),
+                        leaf_node.content_items[idx],
+                    )
+                }));
+            }
+        }
+    };
+}
+
+impl_dual_tree_index_immutable!(
+    ImmutableKdTree,
+    A: Axis, T: Content, const K: usize, const B: usize
+);
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl_dual_tree_index_immutable!(
+    ArchivedImmutableKdTree,
+    A: Axis + rkyv::Archive<Archived = A>,
+    T: Content + rkyv::Archive<Archived = T>,
+    const K: usize,
+    const B: usize
+);
+
+/// A pair of items, one from each tree, found by [`DualTree::join_within`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct JoinPair<A, TA, TB> {
+    /// the item from the first tree
+    pub a: TA,
+    /// the item from the second tree
+    pub b: TB,
+    /// the distance between the two items according to the supplied distance metric
+    pub distance: A,
+}
+
+/// Joins the contents of two trees against each other.
+///
+/// The trees can be any combination of the float [`KdTree`], [`ImmutableKdTree`]
+/// and their archived equivalents, as long as they have the same axis type and
+/// number of dimensions.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::{ImmutableKdTree, KdTree, SquaredEuclidean};
+/// use kiddo::dual_tree::DualTree;
+///
+/// let mut tree_a: KdTree<f64, 2> = KdTree::new();
+/// tree_a.add(&[0.0, 0.0], 100);
+/// tree_a.add(&[10.0, 10.0], 101);
+///
+/// let tree_b: ImmutableKdTree<f64, 2> =
+///     ImmutableKdTree::new_from_slice(&[[0.5, 0.0], [10.0, 11.0], [50.0, 50.0]]);
+///
+/// let join = DualTree::new(&tree_a, &tree_b);
+///
+/// let mut pairs: Vec<_> = join
+///     .join_within::<SquaredEuclidean>(2.0)
+///     .into_iter()
+///     .map(|pair| (pair.a, pair.b))
+///     .collect();
+/// pairs.sort();
+/// assert_eq!(pairs, vec![(100, 0), (101, 1)]);
+///
+/// let nearest = join.all_nearest::<SquaredEuclidean>();
+/// assert_eq!(nearest[0].0, 100);
+/// assert_eq!(nearest[0].1.item, 0);
+/// assert_eq!(nearest[1].0, 101);
+/// assert_eq!(nearest[1].1.item, 1);
+/// ```
+pub struct DualTree<'a, A, const K: usize, TA, TB> {
+    tree_a: &'a TA,
+    tree_b: &'a TB,
+    _axis: PhantomData<A>,
+}
+
+/// The bounding box of a node while traversing a tree
+#[derive(Clone, Copy)]
+struct NodeBounds<A, N, const K: usize> {
+    node: N,
+    split_dim: usize,
+    min: [A; K],
+    max: [A; K],
+}
+
+impl<A: Axis, N: Copy, const K: usize> NodeBounds<A, N, K> {
+    fn root(node: N) -> Self {
+        NodeBounds {
+            node,
+            split_dim: 0,
+            min: [A::neg_infinity(); K],
+            max: [A::infinity(); K],
+        }
+    }
+
+    fn children(&self, split_val: A, left: N, right: N) -> [Self; 2] {
+        let dim = self.split_dim;
+        let next_split_dim = (dim + 1) % K;
+
+        let mut left_bounds = NodeBounds {
+            node: left,
+            split_dim: next_split_dim,
+            ..*self
+        };
+        let mut right_bounds = NodeBounds {
+            node: right,
+            split_dim: next_split_dim,
+            ..*self
+        };
+
+        // unused stems of an ImmutableKdTree have an infinite split value, so
+        // don't let that invert the bounds of the empty side of them
+        left_bounds.max[dim] = if split_val > self.min[dim] {
+            split_val
+        } else {
+            self.min[dim]
+        };
+        right_bounds.min[dim] = if split_val < self.max[dim] {
+            split_val
+        } else {
+            self.max[dim]
+        };
+
+        [left_bounds, right_bounds]
+    }
+
+    /// a lower bound on the distance between any point in this node and any point in `other`
+    fn min_dist_to<D>(&self, other_min: &[A; K], other_max: &[A; K]) -> D::Output
+    where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        let mut rd = D::Output::zero();
+        for dim in 0..K {
+            let gap = if self.min[dim] > other_max[dim] {
+                self.min[dim] - other_max[dim]
+            } else if other_min[dim] > self.max[dim] {
+                other_min[dim] - self.max[dim]
+            } else {
+                A::zero()
+            };
+            rd = Axis::rd_accumulate(rd, D::dist1_on_axis(gap, A::zero(), dim), D::ACCUMULATION);
+        }
+        rd
+    }
+}
+
+impl<'a, A, const K: usize, TA, TB> DualTree<'a, A, K, TA, TB>
+where
+    A: Axis,
+    TA: DualTreeIndex<A, K>,
+    TB: DualTreeIndex<A, K>,
+{
+    /// Creates a `DualTree` that joins the contents of `tree_a` against those of `tree_b`
+    pub fn new(tree_a: &'a TA, tree_b: &'a TB) -> Self {
+        DualTree {
+            tree_a,
+            tree_b,
+            _axis: PhantomData,
+        }
+    }
+
+    /// Finds every pair of items `(a, b)`, with `a` from the first tree and `b` from the
+    /// second, that are within `dist` of each other using the specified distance metric.
+    ///
+    /// Pairs are returned in no particular order.
+    pub fn join_within<D>(&self, dist: D::Output) -> Vec<JoinPair<D::Output, TA::Item, TB::Item>>
+    where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        let mut results = Vec::new();
+        let mut contents_a = Vec::new();
+        let mut contents_b = Vec::new();
+
+        self.join_within_recurse::<D>(
+            NodeBounds::root(self.tree_a.dual_tree_root()),
+            NodeBounds::root(self.tree_b.dual_tree_root()),
+            0,
+            0,
+            dist,
+            &mut results,
+            &mut contents_a,
+            &mut contents_b,
+        );
+
+        results
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::type_complexity)]
+    fn join_within_recurse<D>(
+        &self,
+        node_a: NodeBounds<A, TA::NodeId, K>,
+        node_b: NodeBounds<A, TB::NodeId, K>,
+        depth_a: usize,
+        depth_b: usize,
+        dist: D::Output,
+        results: &mut Vec<JoinPair<D::Output, TA::Item, TB::Item>>,
+        contents_a: &mut Vec<([A; K], TA::Item)>,
+        contents_b: &mut Vec<([A; K], TB::Item)>,
+    ) where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        if node_a.min_dist_to::<D>(&node_b.min, &node_b.max) >= dist {
+            return;
+        }
+
+        let children_a = self.tree_a.dual_tree_children(node_a.node);
+        let children_b = self.tree_b.dual_tree_children(node_b.node);
+
+        match (children_a, children_b) {
+            (None, None) => {
+                contents_a.clear();
+                contents_b.clear();
+                self.tree_a.dual_tree_leaf_contents(node_a.node, contents_a);
+                self.tree_b.dual_tree_leaf_contents(node_b.node, contents_b);
+
+                for (point_a, item_a) in contents_a.iter() {
+                    for (point_b, item_b) in contents_b.iter() {
+                        let distance = D::dist(point_a, point_b);
+                        if distance < dist {
+                            results.push(JoinPair {
+                                a: *item_a,
+                                b: *item_b,
+                                distance,
+                            });
+                        }
+                    }
+                }
+            }
+
+            // descend the shallower of the two trees, so that the pairs of nodes being
+            // compared stay roughly the same size as each other
+            (Some((split_val, left, right)), children_b)
+                if children_b.is_none() || depth_a <= depth_b =>
+            {
+                for child in node_a.children(split_val, left, right) {
+                    self.join_within_recurse::<D>(
+                        child,
+                        node_b,
+                        depth_a + 1,
+                        depth_b,
+                        dist,
+                        results,
+                        contents_a,
+                        contents_b,
+                    );
+                }
+            }
+
+            (_, Some((split_val, left, right))) => {
+                for child in node_b.children(split_val, left, right) {
+                    self.join_within_recurse::<D>(
+                        node_a,
+                        child,
+                        depth_a,
+                        depth_b + 1,
+                        dist,
+                        results,
+                        contents_a,
+                        contents_b,
+                    );
+                }
+            }
+
+            (Some(_), None) => unreachable!(),
+        }
+    }
+
+    /// Finds the nearest item in the second tree to every item in the first tree,
+    /// using the specified distance metric.
+    ///
+    /// Each leaf of the first tree is matched against the second tree as a whole, with
+    /// nodes of the second tree being pruned if they are further from the bounding box of
+    /// the leaf than the current nearest match of every item in the leaf.
+    ///
+    /// Results are returned sorted by the item from the first tree. If the second tree
+    /// is empty, no results are returned.
+    #[allow(clippy::type_complexity)]
+    pub fn all_nearest<D>(&self) -> Vec<(TA::Item, NearestNeighbour<D::Output, TB::Item>)>
+    where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        let mut results = Vec::new();
+        let mut contents_a = Vec::new();
+        let mut contents_b = Vec::new();
+        let mut nearest = Vec::new();
+
+        let mut stack = vec![NodeBounds::<A, TA::NodeId, K>::root(
+            self.tree_a.dual_tree_root(),
+        )];
+        while let Some(node_a) = stack.pop() {
+            if let Some((split_val, left, right)) = self.tree_a.dual_tree_children(node_a.node) {
+                stack.extend(node_a.children(split_val, left, right));
+                continue;
+            }
+
+            contents_a.clear();
+            self.tree_a
+                .dual_tree_leaf_contents(node_a.node, &mut contents_a);
+            if contents_a.is_empty() {
+                continue;
+            }
+
+            // the actual bounds of the leaf's contents are much tighter than the
+            // bounds implied by the splits above it
+            let mut leaf_min = contents_a[0].0;
+            let mut leaf_max = contents_a[0].0;
+            for (point, _) in contents_a.iter() {
+                for dim in 0..K {
+                    if point[dim] < leaf_min[dim] {
+                        leaf_min[dim] = point[dim];
+                    }
+                    if point[dim] > leaf_max[dim] {
+                        leaf_max[dim] = point[dim];
+                    }
+                }
+            }
+
+            nearest.clear();
+            nearest.resize(
+                contents_a.len(),
+                NearestNeighbour {
+                    distance: D::Output::infinity(),
+                    item: <TB::Item as Zero>::zero(),
+                },
+            );
+
+            let mut found = false;
+            self.all_nearest_recurse::<D>(
+                NodeBounds::root(self.tree_b.dual_tree_root()),
+                &leaf_min,
+                &leaf_max,
+                &contents_a,
+                &mut nearest,
+                &mut contents_b,
+                &mut found,
+            );
+
+            if found {
+                results.extend(
+                    contents_a
+                        .iter()
+                        .zip(nearest.iter())
+                        .map(|((_, item), nearest)| (*item, *nearest)),
+                );
+            }
+        }
+
+        results.sort_by_key(|(item, _)| *item);
+        results
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::type_complexity)]
+    fn all_nearest_recurse<D>(
+        &self,
+        node_b: NodeBounds<A, TB::NodeId, K>,
+        leaf_min: &[A; K],
+        leaf_max: &[A; K],
+        contents_a: &[([A; K], TA::Item)],
+        nearest: &mut [NearestNeighbour<D::Output, TB::Item>],
+        contents_b: &mut Vec<([A; K], TB::Item)>,
+        found: &mut bool,
+    ) where
+        D: DistanceMetric<A, K>,
+        D::Output: Axis,
+    {
+        match self.tree_b.dual_tree_children(node_b.node) {
+            None => {
+                contents_b.clear();
+                self.tree_b.dual_tree_leaf_contents(node_b.node, contents_b);
+                *found |= !contents_b.is_empty();
+
+                for ((point_a, _), nearest) in contents_a.iter().zip(nearest.iter_mut()) {
+                    for (point_b, item_b) in contents_b.iter() {
+                        let distance = D::dist(point_a, point_b);
+                        if distance < nearest.distance {
+                            nearest.distance = distance;
+                            nearest.item = *item_b;
+                        }
+                    }
+                }
+            }
+            Some((split_val, left, right)) => {
+                let mut children = node_b
+                    .children(split_val, left, right)
+                    .map(|child| (child.min_dist_to::<D>(leaf_min, leaf_max), child));
+
+                // visit the closer child first, as it will tighten the bound the most
+                if children[1].0 < children[0].0 {
+                    children.swap(0, 1);
+                }
+
+                for (min_dist, child) in children {
+                    let bound = nearest.iter().map(|nearest| nearest.distance).fold(
+                        D::Output::zero(),
+                        |acc, distance| if distance > acc { distance } else { acc },
+                    );
+
+                    if min_dist <= bound {
+                        self.all_nearest_recurse::<D>(
+                            child, leaf_min, leaf_max, contents_a, nearest, contents_b, found,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::dual_tree::{DualTree, DualTreeIndex};
+    use crate::float::distance::{Manhattan, SquaredEuclidean};
+    use crate::float::kdtree::KdTree;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    fn random_points(qty: usize) -> Vec<[AX; 3]> {
+        let mut rng = rand::thread_rng();
+        (0..qty)
+            .map(|_| {
+                [
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                ]
+            })
+            .collect()
+    }
+
+    fn mutable_tree(points: &[[AX; 3]]) -> KdTree<AX, u32, 3, 8, u32> {
+        let mut tree: KdTree<AX, u32, 3, 8, u32> = KdTree::new();
+        points
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        tree
+    }
+
+    fn assert_join_matches_linear_search<TA, TB>(
+        tree_a: &TA,
+        points_a: &[[AX; 3]],
+        tree_b: &TB,
+        points_b: &[[AX; 3]],
+        dist: AX,
+    ) where
+        TA: DualTreeIndex<AX, 3, Item = u32>,
+        TB: DualTreeIndex<AX, 3, Item = u32>,
+    {
+        let mut result: Vec<_> = DualTree::new(tree_a, tree_b)
+            .join_within::<Manhattan>(dist)
+            .into_iter()
+            .map(|pair| (pair.a, pair.b))
+            .collect();
+        result.sort();
+
+        let mut expected = vec![];
+        for (idx_a, point_a) in points_a.iter().enumerate() {
+            for (idx_b, point_b) in points_b.iter().enumerate() {
+                if Manhattan::dist(point_a, point_b) < dist {
+                    expected.push((idx_a as u32, idx_b as u32));
+                }
+            }
+        }
+
+        assert_eq!(result, expected);
+    }
+
+    fn assert_all_nearest_matches_linear_search<TA, TB>(
+        tree_a: &TA,
+        points_a: &[[AX; 3]],
+        tree_b: &TB,
+        points_b: &[[AX; 3]],
+    ) where
+        TA: DualTreeIndex<AX, 3, Item = u32>,
+        TB: DualTreeIndex<AX, 3, Item = u32>,
+    {
+        let result = DualTree::new(tree_a, tree_b).all_nearest::<SquaredEuclidean>();
+
+        assert_eq!(result.len(), points_a.len());
+        for (idx_a, (item_a, nearest)) in result.into_iter().enumerate() {
+            assert_eq!(item_a, idx_a as u32);
+
+            let expected = points_b
+                .iter()
+                .map(|point_b| SquaredEuclidean::dist(&points_a[idx_a], point_b))
+                .fold(AX::INFINITY, AX::min);
+
+            assert_eq!(nearest.distance, expected);
+            assert_eq!(
+                SquaredEuclidean::dist(&points_a[idx_a], &points_b[nearest.item as usize]),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn can_join_within_any_combination_of_trees() {
+        let points_a = random_points(300);
+        let points_b = random_points(200);
+
+        let mutable_a = mutable_tree(&points_a);
+        let mutable_b = mutable_tree(&points_b);
+        let immutable_a: ImmutableKdTree<AX, u32, 3, 8> =
+            ImmutableKdTree::new_from_slice(&points_a);
+        let immutable_b: ImmutableKdTree<AX, u32, 3, 8> =
+            ImmutableKdTree::new_from_slice(&points_b);
+
+        for dist in [0.0, 0.1, 0.3] {
+            assert_join_matches_linear_search(&mutable_a, &points_a, &mutable_b, &points_b, dist);
+            assert_join_matches_linear_search(&mutable_a, &points_a, &immutable_b, &points_b, dist);
+            assert_join_matches_linear_search(&immutable_a, &points_a, &mutable_b, &points_b, dist);
+            assert_join_matches_linear_search(
+                &immutable_a,
+                &points_a,
+                &immutable_b,
+                &points_b,
+                dist,
+            );
+        }
+    }
+
+    #[test]
+    fn can_find_all_nearest_for_any_combination_of_trees() {
+        let points_a = random_points(300);
+        let points_b = random_points(200);
+
+        let mutable_a = mutable_tree(&points_a);
+        let mutable_b = mutable_tree(&points_b);
+        let immutable_a: ImmutableKdTree<AX, u32, 3, 8> =
+            ImmutableKdTree::new_from_slice(&points_a);
+        let immutable_b: ImmutableKdTree<AX, u32, 3, 8> =
+            ImmutableKdTree::new_from_slice(&points_b);
+
+        assert_all_nearest_matches_linear_search(&mutable_a, &points_a, &mutable_b, &points_b);
+        assert_all_nearest_matches_linear_search(&mutable_a, &points_a, &immutable_b, &points_b);
+        assert_all_nearest_matches_linear_search(&immutable_a, &points_a, &mutable_b, &points_b);
+        assert_all_nearest_matches_linear_search(&immutable_a, &points_a, &immutable_b, &points_b);
+    }
+
+    #[test]
+    fn all_nearest_against_empty_tree_returns_nothing() {
+        let points_a = random_points(10);
+        let tree_a = mutable_tree(&points_a);
+        let tree_b: KdTree<AX, u32, 3, 8, u32> = KdTree::new();
+
+        let result = DualTree::new(&tree_a, &tree_b).all_nearest::<SquaredEuclidean>();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn can_join_trees_large_scale() {
+        let points_a = random_points(2_000);
+        let points_b = random_points(2_000);
+
+        let tree_a = mutable_tree(&points_a);
+        let tree_b: ImmutableKdTree<AX, u32, 3, 32> = ImmutableKdTree::new_from_slice(&points_b);
+
+        assert_join_matches_linear_search(&tree_a, &points_a, &tree_b, &points_b, 0.05);
+        assert_all_nearest_matches_linear_search(&tree_a, &points_a, &tree_b, &points_b);
+        assert_all_nearest_matches_linear_search(&tree_b, &points_b, &tree_a, &points_a);
+    }
+}
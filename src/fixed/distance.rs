@@ -5,7 +5,9 @@
 // #[cfg(any(target_arch = "x86_64"))]
 // use std::arch::x86_64::*;
 
-use crate::distance_metric::DistanceMetric;
+use std::marker::PhantomData;
+
+use crate::distance_metric::{Accumulation, DistanceMetric};
 use crate::fixed::kdtree::Axis;
 
 /// Returns the squared euclidean distance between two points. When you only
@@ -13,6 +15,9 @@ use crate::fixed::kdtree::Axis;
 /// the points, this metric is beneficial because it avoids the expensive square
 /// root computation.
 ///
+/// Distances saturate at the maximum value of the fixed point type. Use
+/// [`WideManhattan`] to compute them in a wider type instead.
+///
 /// # Examples
 ///
 /// ```rust
@@ -33,6 +38,8 @@ use crate::fixed::kdtree::Axis;
 pub struct Manhattan {}
 
 impl<A: Axis, const K: usize> DistanceMetric<A, K> for Manhattan {
+    type Output = A;
+
     #[inline]
     fn dist(a: &[A; K], b: &[A; K]) -> A {
         a.iter()
@@ -62,6 +69,9 @@ impl<A: Axis, const K: usize> DistanceMetric<A, K> for Manhattan {
 /// Faster than Euclidean distance due to not needing a square root, but still
 /// preserves the same distance ordering as with Euclidean distance.
 ///
+/// Distances saturate at the maximum value of the fixed point type. Use
+/// [`WideSquaredEuclidean`] to compute them in a wider type instead.
+///
 /// # Examples
 ///
 /// ```rust
@@ -83,6 +93,8 @@ impl<A: Axis, const K: usize> DistanceMetric<A, K> for Manhattan {
 pub struct SquaredEuclidean {}
 
 impl<A: Axis, const K: usize> DistanceMetric<A, K> for SquaredEuclidean {
+    type Output = A;
+
     #[inline]
     fn dist(a: &[A; K], b: &[A; K]) -> A {
         a.iter()
@@ -100,3 +112,315 @@ impl<A: Axis, const K: usize> DistanceMetric<A, K> for SquaredEuclidean {
         diff * diff
     }
 }
+
+/// Returns the Chebyshev / "chessboard" distance between two points: the
+/// largest distance between them along any single axis.
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed::types::extra::U0;
+/// use fixed::FixedU16;
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::fixed::distance::Chebyshev;
+/// type Fxd = FixedU16<U0>;
+///
+/// let ZERO = Fxd::from_num(0);
+/// let ONE = Fxd::from_num(1);
+/// let TWO = Fxd::from_num(2);
+///
+/// assert_eq!(ZERO, Chebyshev::dist(&[ZERO, ZERO], &[ZERO, ZERO]));
+/// assert_eq!(ONE, Chebyshev::dist(&[ZERO, ZERO], &[ONE, ZERO]));
+/// assert_eq!(TWO, Chebyshev::dist(&[ZERO, ZERO], &[ONE, TWO]));
+/// ```
+pub struct Chebyshev {}
+
+impl<A: Axis, const K: usize> DistanceMetric<A, K> for Chebyshev {
+    type Output = A;
+
+    const ACCUMULATION: Accumulation = Accumulation::Max;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> A {
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a_val, &b_val)| a_val.dist(b_val))
+            .fold(A::ZERO, |acc, dist| acc.max(dist))
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> A {
+        a.dist(b)
+    }
+}
+
+/// Returns the Minkowski distance of order `P` between two points, raised to
+/// the power `P`.
+///
+/// The root is not taken, as it is expensive and does not change the ordering of
+/// distances. `Minkowski<1>` is equivalent to [`Manhattan`] and `Minkowski<2>` to
+/// [`SquaredEuclidean`]. `P` must be at least 1. Distances saturate at the
+/// maximum value of the fixed point type.
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed::types::extra::U0;
+/// use fixed::FixedU16;
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::fixed::distance::Minkowski;
+/// type Fxd = FixedU16<U0>;
+///
+/// let ZERO = Fxd::from_num(0);
+/// let ONE = Fxd::from_num(1);
+/// let TWO = Fxd::from_num(2);
+/// let NINE = Fxd::from_num(9);
+///
+/// assert_eq!(ZERO, Minkowski::<3>::dist(&[ZERO, ZERO], &[ZERO, ZERO]));
+/// assert_eq!(ONE, Minkowski::<3>::dist(&[ZERO, ZERO], &[ONE, ZERO]));
+/// assert_eq!(NINE, Minkowski::<3>::dist(&[ZERO, ZERO], &[ONE, TWO]));
+/// ```
+pub struct Minkowski<const P: u32> {}
+
+impl<const P: u32> Minkowski<P> {
+    #[inline]
+    fn powered_dist1<A: Axis>(a: A, b: A) -> A {
+        let diff = a.dist(b);
+
+        (1..P).fold(diff, |acc, _| acc.saturating_mul(diff))
+    }
+}
+
+impl<A: Axis, const K: usize, const P: u32> DistanceMetric<A, K> for Minkowski<P> {
+    type Output = A;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> A {
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a_val, &b_val)| Self::powered_dist1(a_val, b_val))
+            .fold(A::ZERO, |acc, dist| acc.saturating_add(dist))
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> A {
+        Self::powered_dist1(a, b)
+    }
+}
+
+/// Returns the Manhattan distance between two points, computed in the wider
+/// fixed point type `O` rather than in the type of the co-ordinates.
+///
+/// [`Manhattan`] saturates at the maximum value of the co-ordinate type, so
+/// distances between far apart points can become indistinguishable from each other.
+/// Choosing an `O` with more integer bits, and at least as many fractional bits,
+/// as the co-ordinates avoids this, eg `FixedU32<U14>` for `FixedU16<U14>`
+/// co-ordinates. Distances still saturate, but at the maximum value of `O`.
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed::types::extra::U14;
+/// use fixed::{FixedU16, FixedU32};
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::fixed::distance::{Manhattan, WideManhattan};
+/// type Fxd = FixedU16<U14>;
+/// type Wide = FixedU32<U14>;
+///
+/// let ZERO = Fxd::from_num(0);
+/// let THREE = Fxd::from_num(3);
+///
+/// assert_eq!(Manhattan::dist(&[ZERO, ZERO], &[THREE, THREE]), Fxd::MAX);
+/// assert_eq!(
+///     WideManhattan::<Wide>::dist(&[ZERO, ZERO], &[THREE, THREE]),
+///     Wide::from_num(6)
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WideManhattan<O> {
+    _output: PhantomData<O>,
+}
+
+impl<A: Axis, O: Axis, const K: usize> DistanceMetric<A, K> for WideManhattan<O> {
+    type Output = O;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> O {
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a_val, &b_val)| <Self as DistanceMetric<A, K>>::dist1(a_val, b_val))
+            .fold(O::ZERO, |acc, dist| acc.saturating_add(dist))
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> O {
+        O::saturating_from_num(a.dist(b))
+    }
+}
+
+/// Returns the squared euclidean distance between two points, computed in the
+/// wider fixed point type `O` rather than in the type of the co-ordinates.
+///
+/// [`SquaredEuclidean`] saturates at the maximum value of the co-ordinate type,
+/// which for squared distances is reached quickly. Choosing an `O` with twice as
+/// many fractional bits as the co-ordinates, and enough integer bits to hold the sum
+/// of `K` squares, keeps every distance exact, eg `FixedU64<U28>` for
+/// `FixedU16<U14>` co-ordinates. Distances still saturate, but at the maximum value of `O`.
+///
+/// # Examples
+///
+/// ```rust
+/// use fixed::types::extra::{U14, U28};
+/// use fixed::{FixedU16, FixedU64};
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::fixed::distance::WideSquaredEuclidean;
+/// type Fxd = FixedU16<U14>;
+/// type Wide = FixedU64<U28>;
+///
+/// let ZERO = Fxd::from_num(0);
+/// let THREE = Fxd::from_num(3);
+///
+/// assert_eq!(
+///     WideSquaredEuclidean::<Wide>::dist(&[ZERO, ZERO], &[THREE, THREE]),
+///     Wide::from_num(18)
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WideSquaredEuclidean<O> {
+    _output: PhantomData<O>,
+}
+
+impl<A: Axis, O: Axis, const K: usize> DistanceMetric<A, K> for WideSquaredEuclidean<O> {
+    type Output = O;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> O {
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a_val, &b_val)| <Self as DistanceMetric<A, K>>::dist1(a_val, b_val))
+            .fold(O::ZERO, |acc, dist| acc.saturating_add(dist))
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> O {
+        let diff = O::saturating_from_num(a.dist(b));
+        diff.saturating_mul(diff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed::kdtree::KdTree;
+    use crate::test_utils::rand_data_fixed_u16_point;
+    use fixed::types::extra::{U14, U28};
+    use fixed::{FixedU16, FixedU32, FixedU64};
+
+    type Fxd = FixedU16<U14>;
+    type Wide = FixedU64<U28>;
+
+    const TREE_SIZE: usize = 10_000;
+    const NUM_QUERIES: usize = 100;
+    const N: usize = 10;
+
+    #[test]
+    fn can_query_with_chebyshev() {
+        assert_queries_match_linear_search::<Chebyshev>(Fxd::from_num(0.05));
+    }
+
+    #[test]
+    fn can_query_with_wide_metrics() {
+        assert_queries_match_linear_search::<WideManhattan<FixedU32<U14>>>(FixedU32::from_num(0.2));
+        assert_queries_match_linear_search::<WideSquaredEuclidean<Wide>>(Wide::from_num(0.01));
+    }
+
+    #[test]
+    fn wide_squared_euclidean_orders_distances_over_the_full_range() {
+        let content_to_add: Vec<[Fxd; 4]> = (0..1000)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 32, u32> = KdTree::with_capacity(1000);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        let query_point = [Fxd::ZERO; 4];
+
+        let mut expected: Vec<(Wide, u32)> = content_to_add
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| {
+                (
+                    <WideSquaredEuclidean<Wide> as DistanceMetric<Fxd, 4>>::dist(&query_point, p),
+                    idx as u32,
+                )
+            })
+            .collect();
+        expected.sort();
+
+        // most of these distances are too large for the co-ordinate type
+        assert!(expected[500].0 > Wide::from_num(Fxd::MAX));
+
+        let nearest = tree.nearest_n::<WideSquaredEuclidean<Wide>>(&query_point, 1000);
+        assert!(nearest
+            .windows(2)
+            .all(|pair| pair[0].distance <= pair[1].distance));
+
+        let mut found: Vec<(Wide, u32)> =
+            nearest.into_iter().map(|n| (n.distance, n.item)).collect();
+        found.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn can_query_with_minkowski() {
+        assert_queries_match_linear_search::<Minkowski<1>>(Fxd::from_num(0.2));
+        assert_queries_match_linear_search::<Minkowski<3>>(Fxd::from_num(0.001));
+    }
+
+    fn assert_queries_match_linear_search<D>(radius: D::Output)
+    where
+        D: DistanceMetric<Fxd, 4>,
+        D::Output: Axis,
+    {
+        let content_to_add: Vec<[Fxd; 4]> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand_data_fixed_u16_point::<U14, 4>();
+
+            let mut expected: Vec<D::Output> = content_to_add
+                .iter()
+                .map(|p| D::dist(&query_point, p))
+                .collect();
+            expected.sort();
+
+            assert_eq!(tree.nearest_one::<D>(&query_point).distance, expected[0]);
+
+            let nearest: Vec<_> = tree
+                .nearest_n::<D>(&query_point, N)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            assert_eq!(nearest, expected[..N]);
+
+            let within: Vec<_> = tree
+                .within::<D>(&query_point, radius)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            let expected_within: Vec<_> =
+                expected.iter().copied().filter(|&d| d < radius).collect();
+            assert_eq!(within, expected_within);
+        }
+    }
+}
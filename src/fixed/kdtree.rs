@@ -9,6 +9,7 @@ use fixed::traits::Fixed;
 use std::cmp::PartialEq;
 use std::fmt::Debug;
 
+use crate::distance_metric::Accumulation;
 use crate::iter::TreeIter;
 use crate::{
     iter::IterableTreeData,
@@ -25,6 +26,23 @@ use serde::{Deserialize, Serialize};
 pub trait Axis: Fixed + Default + Debug + Copy + Sync + Send {
     /// used in query methods to update the rd value. Basically a saturating add for Fixed and an add for Float
     fn rd_update(rd: Self, delta: Self) -> Self;
+
+    /// used in query methods to update the rd value according to how the distance
+    /// metric combines the distances along each axis: `rd_update` for
+    /// [`Accumulation::Sum`], or the larger of the two for [`Accumulation::Max`]
+    #[inline]
+    fn rd_accumulate(rd: Self, delta: Self, accumulation: Accumulation) -> Self {
+        match accumulation {
+            Accumulation::Sum => Self::rd_update(rd, delta),
+            Accumulation::Max => {
+                if delta > rd {
+                    delta
+                } else {
+                    rd
+                }
+            }
+        }
+    }
 }
 impl<T: Fixed + Default + Debug + Copy + Sync + Send> Axis for T {
     #[inline]
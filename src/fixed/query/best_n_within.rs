@@ -1,10 +1,12 @@
 use az::{Az, Cast};
+use num_traits::Zero;
 use std::collections::BinaryHeap;
 use std::ops::Rem;
 
 use crate::best_neighbour::BestNeighbour;
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{DistanceMetric, StatefulDistanceMetric, StatelessMetric};
 use crate::fixed::kdtree::{Axis, KdTree, LeafNode};
+use crate::neighbour::FromLeafEntry;
 use crate::types::{is_stem_index, Content, Index};
 
 use crate::generate_best_n_within;
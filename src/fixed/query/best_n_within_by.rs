@@ -0,0 +1,191 @@
+use az::{Az, Cast};
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::best_neighbour::{BestNeighbour, ScoredNeighbour};
+use crate::distance_metric::DistanceMetric;
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_best_n_within_by;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_best_n_within_by!(
+        (r#"Finds the "best" `max_qty` elements within `dist` of `query`, ranked by a
+caller-supplied `score` function rather than by the item values themselves.
+
+`score` is called with the item and its distance from `query` for every element
+within `dist`, and the elements with the lowest scores are kept. Wrap the score
+in [`std::cmp::Reverse`] to keep the highest-scoring elements instead.
+Results are returned best-first.
+
+# Examples
+
+```rust
+    use std::cmp::Reverse;
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+
+    type Fxd = FixedU16<U0>;
+
+    let population = |item: u32| if item == 101 { 5000 } else { 20 };
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+
+    let mut best = tree.best_n_within_by::<SquaredEuclidean, _, _>(
+        &[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)],
+        Fxd::from_num(10),
+        1,
+        |item, _| Reverse(population(item)),
+    );
+
+    assert_eq!(best.next().unwrap().item, 101);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::{Axis, KdTree};
+    use crate::test_utils::{rand_data_fixed_u16_entry, rand_data_fixed_u16_point};
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+    use std::cmp::Reverse;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_best_n_items_within_radius_by_score() {
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([Fxd; 4], u32); 16] = [
+            ([n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)], 9),
+            ([n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)], 4),
+            ([n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)], 12),
+            ([n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)], 7),
+            ([n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)], 13),
+            ([n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)], 6),
+            ([n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)], 2),
+            ([n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)], 14),
+            ([n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)], 3),
+            ([n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)], 10),
+            ([n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)], 16),
+            ([n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)], 1),
+            ([n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)], 15),
+            ([n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)], 5),
+            ([n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)], 8),
+            ([n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        // a side table of scores, indexed by item
+        let population: Vec<u32> = (0..=16).map(|_| rand::random()).collect();
+        let max_qty = 5;
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query = [
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+            ];
+            let radius = n(rng.gen_range(0f32..2f32));
+
+            let score = |item: u32, _| Reverse(population[item as usize]);
+            let expected = linear_search(&content_to_add, &query, radius, max_qty, score);
+
+            let result: Vec<_> = tree
+                .best_n_within_by::<Manhattan, _, _>(&query, radius, max_qty, score)
+                .map(|best| score(best.item, best.distance))
+                .collect();
+            assert_eq!(result, expected);
+
+            let score = |item: u32, _| item;
+            let expected = linear_search(&content_to_add, &query, radius, max_qty, score);
+
+            let result: Vec<_> = tree
+                .best_n_within_by::<Manhattan, _, _>(&query, radius, max_qty, score)
+                .map(|best| best.item)
+                .collect();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_best_n_items_within_radius_by_score_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        let max_qty = 5;
+
+        let content_to_add: Vec<([Fxd; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_entry::<U14, u32, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let population: Vec<u32> = (0..TREE_SIZE).map(|_| rand::random()).collect();
+        let score = |item: u32, _| Reverse(population[item as usize % TREE_SIZE]);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand_data_fixed_u16_point::<U14, 4>();
+            let radius = n(0.6);
+            let expected = linear_search(&content_to_add, &query_point, radius, max_qty, score);
+
+            let result: Vec<_> = tree
+                .best_n_within_by::<Manhattan, _, _>(&query_point, radius, max_qty, score)
+                .map(|best| score(best.item, best.distance))
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize, F: Fn(u32, A) -> S, S: Ord + Copy>(
+        content: &[([A; K], u32)],
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        score: F,
+    ) -> Vec<S> {
+        let mut scores = vec![];
+
+        for &(p, item) in content {
+            let distance = Manhattan::dist(query, &p);
+            if distance <= radius {
+                scores.push(score(item, distance));
+            }
+        }
+
+        scores.sort_unstable();
+        scores.truncate(max_qty);
+
+        scores
+    }
+}
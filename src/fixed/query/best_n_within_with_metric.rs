@@ -0,0 +1,169 @@
+use az::Cast;
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+
+use crate::best_neighbour::BestNeighbour;
+use crate::distance_metric::StatefulDistanceMetric;
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::types::{Content, Index};
+
+use crate::generate_best_n_within_with_metric;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_best_n_within_with_metric!(
+        (r#"Finds the "best" `n` elements within `dist` of `query`, using the supplied
+`metric` instance to measure distances.
+
+Behaves like [`best_n_within`](Self::best_n_within), but allows the metric to carry
+parameters that are chosen at runtime, such as per-axis weights. "Best" is
+determined by the lowest item values.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+
+    let mut best = tree.best_n_within_with_metric(&SquaredEuclidean {}, &[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], Fxd::from_num(10), 1);
+
+    assert_eq!(best.next().unwrap().item, 100);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::StatefulDistanceMetric;
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::{Axis, KdTree};
+    use crate::test_utils::rand_data_fixed_u16_point;
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_best_n_items_within_radius_with_metric() {
+        let metric = Manhattan {};
+
+        let content_to_add: [[Fxd; 4]; 16] = [
+            [n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)],
+            [n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)],
+            [n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)],
+            [n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)],
+            [n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)],
+            [n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)],
+            [n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)],
+            [n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)],
+            [n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)],
+            [n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)],
+            [n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)],
+            [n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)],
+            [n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)],
+            [n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)],
+            [n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)],
+            [n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)],
+        ];
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        assert_eq!(tree.size(), 16);
+
+        let max_qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+            ];
+            let radius = n(rng.gen_range(0f32..2f32));
+            let expected = linear_search(&metric, &content_to_add, &query_point, radius, max_qty);
+
+            let result: Vec<_> = tree
+                .best_n_within_with_metric(&metric, &query_point, radius, max_qty)
+                .collect();
+
+            let mut result_items: Vec<_> = result.iter().map(|found| found.item).collect();
+            result_items.sort_unstable();
+            assert_eq!(result_items, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_best_n_items_within_radius_with_metric_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        let max_qty = 10;
+
+        let metric = Manhattan {};
+
+        let content_to_add: Vec<[Fxd; 4]> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand_data_fixed_u16_point::<U14, 4>();
+            let radius = n(0.6);
+            let expected = linear_search(&metric, &content_to_add, &query_point, radius, max_qty);
+
+            let result: Vec<_> = tree
+                .best_n_within_with_metric(&metric, &query_point, radius, max_qty)
+                .collect();
+
+            let mut result_items: Vec<_> = result.iter().map(|found| found.item).collect();
+            result_items.sort_unstable();
+            assert_eq!(result_items, expected);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize, D: StatefulDistanceMetric<A, K, Output = A>>(
+        metric: &D,
+        content: &[[A; K]],
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+    ) -> Vec<u32> {
+        let mut items: Vec<u32> = content
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| metric.dist(query, p) <= radius)
+            .map(|(item, _)| item as u32)
+            .collect();
+
+        items.sort_unstable();
+        items.truncate(max_qty);
+
+        items
+    }
+}
@@ -0,0 +1,154 @@
+use az::{Az, Cast};
+use num_traits::Zero;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_count_within;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_count_within!(
+        (r#"Counts the elements within `dist` of `query`, using the specified
+distance metric function.
+
+Equivalent to `within_unsorted(query, dist).len()`, but does not allocate.
+Subtrees that lie entirely within `dist` of `query` are counted without
+calculating the distance to each of their points.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+    tree.add(&[Fxd::from_num(20), Fxd::from_num(30), Fxd::from_num(60)], 102);
+
+    let count = tree.count_within::<SquaredEuclidean>(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], Fxd::from_num(10));
+
+    assert_eq!(count, 2);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::{Axis, KdTree};
+    use crate::test_utils::{rand_data_fixed_u16_entry, rand_data_fixed_u16_point};
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_count_items_within() {
+        let mut tree: KdTree<Fxd, u32, 4, 5, u32> = KdTree::new();
+
+        let content_to_add: [([Fxd; 4], u32); 16] = [
+            ([n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)], 9),
+            ([n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)], 4),
+            ([n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)], 12),
+            ([n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)], 7),
+            ([n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)], 13),
+            ([n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)], 6),
+            ([n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)], 2),
+            ([n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)], 14),
+            ([n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)], 3),
+            ([n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)], 10),
+            ([n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)], 16),
+            ([n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)], 1),
+            ([n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)], 15),
+            ([n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)], 5),
+            ([n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)], 8),
+            ([n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [n(0.78f32), n(0.55f32), n(0.78f32), n(0.55f32)];
+
+        assert_eq!(tree.count_within::<Manhattan>(&query_point, n(3.9)), 16);
+        assert_eq!(tree.count_within::<Manhattan>(&query_point, n(0.0)), 0);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+            ];
+            let radius = n(rng.gen_range(0f32..2f32));
+            let expected = linear_count(&content_to_add, &query_point, radius);
+
+            let result = tree.count_within::<Manhattan>(&query_point, radius);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_count_items_within_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([Fxd; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_entry::<U14, u32, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let query_points: Vec<[Fxd; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+
+        let mut rng = rand::thread_rng();
+        for query_point in query_points {
+            let radius = n(rng.gen_range(0f32..2f32));
+            let expected = linear_count(&content_to_add, &query_point, radius);
+
+            let result = tree.count_within::<Manhattan>(&query_point, radius);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_count<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        query_point: &[A; K],
+        radius: A,
+    ) -> usize {
+        content
+            .iter()
+            .filter(|(p, _)| Manhattan::dist(query_point, p) < radius)
+            .count()
+    }
+}
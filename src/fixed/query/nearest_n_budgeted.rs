@@ -0,0 +1,195 @@
+use az::{Az, Cast};
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::search_budget::{BudgetTracker, BudgetedResult, SearchBudget};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_nearest_n_budgeted;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_nearest_n_budgeted!(
+        (r#"Finds the nearest `qty` elements to `query`, using the specified
+distance metric function, doing no more work than is allowed by `budget`.
+
+If the budget runs out before the search completes, the closest elements found so far
+are returned and `exact` is `false` in the returned [`BudgetedResult`]. Fewer than `qty`
+elements may be returned if the budget runs out before enough leaves have been searched.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+    use kiddo::search_budget::SearchBudget;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+
+    let nearest = tree.nearest_n_budgeted::<SquaredEuclidean>(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 1, SearchBudget::with_max_leaves(4));
+
+    assert!(nearest.exact);
+    assert_eq!(nearest.result.len(), 1);
+    assert_eq!(nearest.result[0].distance, Fxd::from_num(0));
+    assert_eq!(nearest.result[0].item, 100);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::{Axis, KdTree};
+    use crate::search_budget::SearchBudget;
+    use crate::test_utils::{rand_data_fixed_u16_entry, rand_data_fixed_u16_point};
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_nearest_n_items_budgeted() {
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([Fxd; 4], u32); 16] = [
+            ([n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)], 9),
+            ([n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)], 4),
+            ([n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)], 12),
+            ([n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)], 7),
+            ([n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)], 13),
+            ([n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)], 6),
+            ([n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)], 2),
+            ([n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)], 14),
+            ([n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)], 3),
+            ([n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)], 10),
+            ([n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)], 16),
+            ([n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)], 1),
+            ([n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)], 15),
+            ([n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)], 5),
+            ([n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)], 8),
+            ([n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [n(0.78f32), n(0.55f32), n(0.78f32), n(0.55f32)];
+
+        let result =
+            tree.nearest_n_budgeted::<Manhattan>(&query_point, 0, SearchBudget::unlimited());
+        assert!(result.exact);
+        assert!(result.result.is_empty());
+
+        let result =
+            tree.nearest_n_budgeted::<Manhattan>(&query_point, 3, SearchBudget::with_max_leaves(0));
+        assert!(!result.exact);
+        assert!(result.result.is_empty());
+
+        let qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+            ];
+            let budget = SearchBudget::new(rng.gen_range(0..6), rng.gen_range(0..8));
+            let expected = linear_search(&content_to_add, qty, &query_point);
+
+            let result = tree.nearest_n_budgeted::<Manhattan>(&query_point, qty, budget);
+            let result_dists: Vec<_> = result.result.iter().map(|n| n.distance).collect();
+
+            assert_budgeted_result(&result_dists, result.exact, &expected);
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_n_items_budgeted_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let content_to_add: Vec<([Fxd; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_entry::<U14, u32, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let query_points: Vec<[Fxd; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+
+        for query_point in query_points {
+            let expected = linear_search(&content_to_add, N, &query_point);
+
+            for budget in [
+                SearchBudget::unlimited(),
+                SearchBudget::with_max_leaves(4),
+                SearchBudget::new(usize::MAX, 20),
+            ] {
+                let result = tree.nearest_n_budgeted::<Manhattan>(&query_point, N, budget);
+                let result_dists: Vec<_> = result.result.iter().map(|n| n.distance).collect();
+
+                if budget == SearchBudget::unlimited() {
+                    assert!(result.exact);
+                }
+                assert_budgeted_result(&result_dists, result.exact, &expected);
+            }
+        }
+    }
+
+    fn assert_budgeted_result(result: &[Fxd], exact: bool, expected: &[Fxd]) {
+        if exact {
+            assert_eq!(result, expected);
+        } else {
+            assert!(result.len() <= expected.len());
+            for (result_dist, expected_dist) in result.iter().zip(expected) {
+                assert!(result_dist >= expected_dist);
+            }
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        qty: usize,
+        query_point: &[A; K],
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .map(|(p, _)| Manhattan::dist(query_point, p))
+            .collect();
+
+        dists.sort();
+        dists.truncate(qty);
+
+        dists
+    }
+}
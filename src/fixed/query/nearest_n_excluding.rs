@@ -0,0 +1,172 @@
+use az::Cast;
+
+use crate::distance_metric::DistanceMetric;
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{Content, Index};
+
+use crate::generate_nearest_n_excluding;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_nearest_n_excluding!(
+        (r#"Finds the nearest `qty` elements to `query` whose items are not in `exclude`,
+using the specified distance metric function.
+
+Excluded items are skipped during the search rather than removed afterwards,
+so up to `qty` other items are always returned. This is useful when querying
+the neighbours of an element that is itself stored in the tree.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+
+    let nearest: Vec<_> = tree.nearest_n_excluding::<SquaredEuclidean>(
+        &[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)],
+        1,
+        &[100],
+    );
+
+    assert_eq!(nearest.len(), 1);
+    assert_eq!(nearest[0].distance, Fxd::from_num(3));
+    assert_eq!(nearest[0].item, 101);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::{Axis, KdTree};
+    use crate::test_utils::rand_data_fixed_u16_point;
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_nearest_n_excluding_items() {
+        let points: [[Fxd; 4]; 16] = [
+            [n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)],
+            [n(0.4f32), n(0.5f32), n(0.4f32), n(0.51f32)],
+            [n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)],
+            [n(0.7f32), n(0.2f32), n(0.7f32), n(0.22f32)],
+            [n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)],
+            [n(0.6f32), n(0.3f32), n(0.6f32), n(0.33f32)],
+            [n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)],
+            [n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)],
+            [n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)],
+            [n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)],
+            [n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)],
+            [n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)],
+            [n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)],
+            [n(0.5f32), n(0.4f32), n(0.5f32), n(0.44f32)],
+            [n(0.8f32), n(0.1f32), n(0.8f32), n(0.15f32)],
+            [n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)],
+        ];
+
+        // every point is stored twice, under item idx and item idx + 16
+        let content_to_add: Vec<_> = points
+            .iter()
+            .chain(points.iter())
+            .enumerate()
+            .map(|(idx, point)| (*point, idx as u32))
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::new();
+        for (point, item) in &content_to_add {
+            tree.add(point, *item);
+        }
+
+        let qty = 5;
+        for (point, item) in &content_to_add {
+            let exclude = [*item];
+            let expected = linear_search(&content_to_add, qty, point, &exclude);
+
+            let result = tree.nearest_n_excluding::<Manhattan>(point, qty, &exclude);
+
+            let result_dists: Vec<_> = result.iter().map(|n| n.distance).collect();
+            assert_eq!(result_dists, expected);
+            assert!(result.iter().all(|n| n.item != *item));
+            assert_eq!(result[0].item, (item + 16) % 32);
+        }
+
+        let point = &content_to_add[0].0;
+        let exclude = [0, 16];
+        let expected = linear_search(&content_to_add, qty, point, &exclude);
+        let result = tree.nearest_n_excluding::<Manhattan>(point, qty, &exclude);
+        let result_dists: Vec<_> = result.iter().map(|n| n.distance).collect();
+        assert_eq!(result_dists, expected);
+        assert!(result.iter().all(|n| !exclude.contains(&n.item)));
+    }
+
+    #[test]
+    fn can_query_nearest_n_excluding_items_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let points: Vec<[Fxd; 4]> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+        let content_to_add: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(idx, point)| (*point, idx as u32))
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        for (point, item) in content_to_add.iter().take(NUM_QUERIES) {
+            let exclude = [*item];
+            let expected = linear_search(&content_to_add, N, point, &exclude);
+
+            let result = tree.nearest_n_excluding::<Manhattan>(point, N, &exclude);
+
+            let result_dists: Vec<_> = result.iter().map(|n| n.distance).collect();
+            assert_eq!(result_dists, expected);
+            assert!(result.iter().all(|n| n.item != *item));
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        qty: usize,
+        query_point: &[A; K],
+        exclude: &[u32],
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .filter(|(_, item)| !exclude.contains(item))
+            .map(|(p, _)| Manhattan::dist(query_point, p))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(qty);
+
+        dists
+    }
+}
@@ -0,0 +1,140 @@
+use az::Cast;
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+
+use crate::distance_metric::{DistanceMetric, StatelessMetric};
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{Content, Index};
+
+use crate::generate_nearest_n_into;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_nearest_n_into!(
+        (r#"Finds the nearest `qty` elements to `query`, according to the specified
+distance metric function, writing them into `results`.
+
+Behaves like [`nearest_n`](Self::nearest_n), but `results` is cleared and reused rather
+than a new `Vec` being allocated, so repeated queries into the same `Vec` do not need to
+allocate once it has grown large enough. Results are sorted nearest-first.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+
+    let mut results = Vec::new();
+    tree.nearest_n_into::<SquaredEuclidean>(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 1, &mut results);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].item, 100);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::KdTree;
+    use crate::test_utils::rand_data_fixed_u16_point;
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_nearest_n_items_into_reused_vec() {
+        let content_to_add: [[Fxd; 4]; 16] = [
+            [n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)],
+            [n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)],
+            [n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)],
+            [n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)],
+            [n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)],
+            [n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)],
+            [n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)],
+            [n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)],
+            [n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)],
+            [n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)],
+            [n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)],
+            [n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)],
+            [n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)],
+            [n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)],
+            [n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)],
+            [n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)],
+        ];
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        assert_eq!(tree.size(), 16);
+
+        // start with more capacity than needed, so that the allocation
+        // being reused can't limit the number of results
+        let mut results = Vec::with_capacity(64);
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let qty = rng.gen_range(0usize..10usize);
+            let query_point = [
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+            ];
+            let expected = tree.nearest_n::<Manhattan>(&query_point, qty);
+
+            tree.nearest_n_into::<Manhattan>(&query_point, qty, &mut results);
+
+            assert_eq!(results, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_n_items_into_reused_vec_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let content_to_add: Vec<[Fxd; 4]> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut results = Vec::new();
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand_data_fixed_u16_point::<U14, 4>();
+            let expected = tree.nearest_n::<Manhattan>(&query_point, N);
+
+            tree.nearest_n_into::<Manhattan>(&query_point, N, &mut results);
+
+            assert_eq!(results, expected);
+        }
+    }
+}
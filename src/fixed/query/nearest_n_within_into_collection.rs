@@ -0,0 +1,125 @@
+use az::Cast;
+use num_traits::Zero;
+
+use crate::distance_metric::DistanceMetric;
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::result_collection::ResultCollection;
+use crate::types::{Content, Index};
+
+use crate::generate_nearest_n_within_into_collection;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_nearest_n_within_into_collection!(
+        (r#"Finds the elements within `dist` of `query`, using the specified
+distance metric function, and adds them to `results`.
+
+Behaves like [`nearest_n_within`](Self::nearest_n_within), but the results are kept by
+any [`ResultCollection`](crate::result_collection::ResultCollection), which decides which
+of them to keep and how to store them. `results` is not cleared first.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+    use kiddo::nearest_neighbour::NearestNeighbour;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+
+    let mut results: Vec<NearestNeighbour<Fxd, u32>> = Vec::new();
+    tree.nearest_n_within_into_collection::<SquaredEuclidean, _>(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], Fxd::from_num(10), &mut results);
+
+    assert_eq!(results.len(), 2);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::KdTree;
+    use crate::nearest_neighbour::NearestNeighbour;
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+    use sorted_vec::SortedVec;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_items_within_radius_into_collection() {
+        let content_to_add: [[Fxd; 4]; 16] = [
+            [n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)],
+            [n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)],
+            [n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)],
+            [n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)],
+            [n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)],
+            [n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)],
+            [n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)],
+            [n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)],
+            [n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)],
+            [n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)],
+            [n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)],
+            [n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)],
+            [n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)],
+            [n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)],
+            [n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)],
+            [n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)],
+        ];
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        assert_eq!(tree.size(), 16);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+                n(rng.gen_range(0f32..1f32)),
+            ];
+            let radius = n(rng.gen_range(0f32..2f32));
+            let max_qty = rng.gen_range(1usize..10usize);
+
+            let mut expected = tree.within_unsorted::<Manhattan>(&query_point, radius);
+            expected.sort();
+            let mut results: Vec<NearestNeighbour<Fxd, u32>> = Vec::new();
+            tree.nearest_n_within_into_collection::<Manhattan, _>(
+                &query_point,
+                radius,
+                &mut results,
+            );
+            results.sort();
+            assert_eq!(results, expected);
+
+            let expected = tree.nearest_n_within::<Manhattan>(&query_point, radius, max_qty, true);
+            let mut results = SortedVec::with_capacity(max_qty);
+            tree.nearest_n_within_into_collection::<Manhattan, _>(
+                &query_point,
+                radius,
+                &mut results,
+            );
+            assert_eq!(results.into_vec(), expected);
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use az::{Az, Cast};
+use num_traits::{Bounded, Zero};
 use std::ops::Rem;
 
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{DistanceMetric, StatefulDistanceMetric, StatelessMetric};
 use crate::fixed::kdtree::{Axis, KdTree, LeafNode};
 use crate::nearest_neighbour::NearestNeighbour;
+use crate::neighbour::FromLeafEntry;
 use crate::types::{is_stem_index, Content, Index};
 
 use crate::generate_nearest_one;
@@ -0,0 +1,142 @@
+use az::Cast;
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::distance_metric::DistanceMetric;
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{Content, Index};
+
+use crate::generate_nearest_one_batch;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_nearest_one_batch!(
+        (r#"Finds the nearest element to each of `queries`, using the specified
+distance metric function, running the queries in parallel using Rayon.
+
+Results are returned in the same order as `queries`.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::fixed::distance::SquaredEuclidean;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+
+    let nearest = tree.nearest_one_batch::<SquaredEuclidean>(&[
+        [Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)],
+        [Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)],
+    ]);
+
+    assert_eq!(nearest.len(), 2);
+    assert_eq!(nearest[0].item, 100);
+    assert_eq!(nearest[1].item, 101);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::distance::Manhattan;
+    use crate::fixed::kdtree::KdTree;
+    use crate::test_utils::{rand_data_fixed_u16_entry, rand_data_fixed_u16_point};
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_nearest_one_batch() {
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([Fxd; 4], u32); 16] = [
+            ([n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)], 9),
+            ([n(0.4f32), n(0.5f32), n(0.4f32), n(0.51f32)], 4),
+            ([n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)], 12),
+            ([n(0.7f32), n(0.2f32), n(0.7f32), n(0.22f32)], 7),
+            ([n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)], 13),
+            ([n(0.6f32), n(0.3f32), n(0.6f32), n(0.33f32)], 6),
+            ([n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)], 2),
+            ([n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)], 14),
+            ([n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)], 3),
+            ([n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)], 10),
+            ([n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)], 16),
+            ([n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)], 1),
+            ([n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)], 15),
+            ([n(0.5f32), n(0.4f32), n(0.5f32), n(0.44f32)], 5),
+            ([n(0.8f32), n(0.1f32), n(0.8f32), n(0.15f32)], 8),
+            ([n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let mut rng = rand::thread_rng();
+        let query_points: Vec<[Fxd; 4]> = (0..1000)
+            .map(|_| {
+                [
+                    n(rng.gen_range(0f32..1f32)),
+                    n(rng.gen_range(0f32..1f32)),
+                    n(rng.gen_range(0f32..1f32)),
+                    n(rng.gen_range(0f32..1f32)),
+                ]
+            })
+            .collect();
+
+        let result = tree.nearest_one_batch::<Manhattan>(&query_points);
+
+        assert_eq!(result.len(), query_points.len());
+        for (query_point, result) in query_points.iter().zip(result) {
+            assert_eq!(result, tree.nearest_one::<Manhattan>(query_point));
+        }
+
+        let result = tree.nearest_one_batch::<Manhattan>(&[]);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn can_query_nearest_one_batch_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 1_000;
+
+        let content_to_add: Vec<([Fxd; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_entry::<U14, u32, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let query_points: Vec<[Fxd; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand_data_fixed_u16_point::<U14, 4>())
+            .collect();
+
+        let result = tree.nearest_one_batch::<Manhattan>(&query_points);
+
+        assert_eq!(result.len(), query_points.len());
+        for (query_point, result) in query_points.iter().zip(result) {
+            assert_eq!(result, tree.nearest_one::<Manhattan>(query_point));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use az::{Az, Cast};
+use std::ops::Rem;
+
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_within_box_unsorted;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_within_box_unsorted!(
+        (r#"Finds all elements whose points lie within the axis-aligned box
+spanning from `min` to `max` (inclusive on all sides).
+
+Results are returned in arbitrary order. Faster than `within_box`.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+
+    type Fxd = FixedU16<U0>;
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+    tree.add(&[Fxd::from_num(20), Fxd::from_num(30), Fxd::from_num(60)], 102);
+
+    let mut within = tree.within_box_unsorted(
+        &[Fxd::from_num(0), Fxd::from_num(0), Fxd::from_num(0)],
+        &[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)],
+    );
+
+    within.sort();
+    assert_eq!(within, vec![100, 101]);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::kdtree::{Axis, KdTree};
+    use crate::test_utils::rand_data_fixed_u16_entry;
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    #[test]
+    fn can_query_items_within_box_unsorted() {
+        let mut tree: KdTree<Fxd, u32, 4, 5, u32> = KdTree::new();
+
+        let content_to_add: [([Fxd; 4], u32); 16] = [
+            ([n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)], 9),
+            ([n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)], 4),
+            ([n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)], 12),
+            ([n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)], 7),
+            ([n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)], 13),
+            ([n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)], 6),
+            ([n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)], 2),
+            ([n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)], 14),
+            ([n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)], 3),
+            ([n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)], 10),
+            ([n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)], 16),
+            ([n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)], 1),
+            ([n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)], 15),
+            ([n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)], 5),
+            ([n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)], 8),
+            ([n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let min = [n(0.4f32), n(0.2f32), n(0.4f32), n(0.2f32)];
+        let max = [n(0.7f32), n(0.5f32), n(0.7f32), n(0.5f32)];
+
+        let mut result = tree.within_box_unsorted(&min, &max);
+        result.sort();
+        assert_eq!(result, vec![4, 5, 6, 7]);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let (min, max) = random_box(&mut rng);
+            let expected = linear_search(&content_to_add, &min, &max);
+
+            let mut result = tree.within_box_unsorted(&min, &max);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_box_unsorted_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([Fxd; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_entry::<U14, u32, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let (min, max) = random_box(&mut rng);
+            let expected = linear_search(&content_to_add, &min, &max);
+
+            let mut result = tree.within_box_unsorted(&min, &max);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn random_box<R: Rng>(rng: &mut R) -> ([Fxd; 4], [Fxd; 4]) {
+        let mut min = [Fxd::ZERO; 4];
+        let mut max = [Fxd::ZERO; 4];
+        for dim in 0..4 {
+            let a = n(rng.gen_range(0f32..1f32));
+            let b = n(rng.gen_range(0f32..1f32));
+            min[dim] = a.min(b);
+            max[dim] = a.max(b);
+        }
+        (min, max)
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        min: &[A; K],
+        max: &[A; K],
+    ) -> Vec<u32> {
+        let mut matching_items: Vec<u32> = content
+            .iter()
+            .filter(|(p, _)| (0..K).all(|dim| p[dim] >= min[dim] && p[dim] <= max[dim]))
+            .map(|&(_, item)| item)
+            .collect();
+
+        matching_items.sort();
+
+        matching_items
+    }
+}
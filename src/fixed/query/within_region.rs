@@ -0,0 +1,184 @@
+use az::{Az, Cast};
+use std::ops::Rem;
+
+use crate::fixed::kdtree::{Axis, KdTree};
+use crate::query_region::QueryRegion;
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_within_region;
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_within_region!(
+        (r#"Finds all elements whose points lie within `region`, which can be any
+type that implements [`QueryRegion`](`crate::query_region::QueryRegion`).
+
+Subtrees that do not intersect `region` are skipped, and the items of subtrees
+that lie entirely within `region` are returned without testing each point.
+Results are returned in arbitrary order.
+
+# Examples
+
+```rust
+    use fixed::FixedU16;
+    use fixed::types::extra::U0;
+    use kiddo::fixed::kdtree::KdTree;
+    use kiddo::query_region::QueryRegion;
+
+    type Fxd = FixedU16<U0>;
+
+    struct AboveZ(Fxd);
+
+    impl QueryRegion<Fxd, 3> for AboveZ {
+        fn intersects_box(&self, _min: &[Fxd; 3], max: &[Fxd; 3]) -> bool {
+            max[2] >= self.0
+        }
+
+        fn contains_box(&self, min: &[Fxd; 3], _max: &[Fxd; 3]) -> bool {
+            min[2] >= self.0
+        }
+
+        fn contains_point(&self, point: &[Fxd; 3]) -> bool {
+            point[2] >= self.0
+        }
+    }
+
+    let mut tree: KdTree<Fxd, u32, 3, 32, u32> = KdTree::new();
+
+    tree.add(&[Fxd::from_num(1), Fxd::from_num(2), Fxd::from_num(5)], 100);
+    tree.add(&[Fxd::from_num(2), Fxd::from_num(3), Fxd::from_num(6)], 101);
+    tree.add(&[Fxd::from_num(20), Fxd::from_num(30), Fxd::from_num(60)], 102);
+
+    let mut within = tree.within_region(&AboveZ(Fxd::from_num(6)));
+
+    within.sort();
+    assert_eq!(within, vec![101, 102]);
+```"#)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed::kdtree::KdTree;
+    use crate::query_region::QueryRegion;
+    use crate::test_utils::rand_data_fixed_u16_entry;
+    use fixed::types::extra::U14;
+    use fixed::FixedU16;
+    use rand::Rng;
+
+    type Fxd = FixedU16<U14>;
+
+    fn n(num: f32) -> Fxd {
+        Fxd::from_num(num)
+    }
+
+    /// Points whose first coordinate is at least their second, offset by `margin`
+    struct BelowDiagonal {
+        margin: Fxd,
+    }
+
+    impl QueryRegion<Fxd, 4> for BelowDiagonal {
+        fn intersects_box(&self, min: &[Fxd; 4], max: &[Fxd; 4]) -> bool {
+            max[0] >= min[1].saturating_add(self.margin)
+        }
+
+        fn contains_box(&self, min: &[Fxd; 4], max: &[Fxd; 4]) -> bool {
+            min[0] >= max[1].saturating_add(self.margin)
+        }
+
+        fn contains_point(&self, point: &[Fxd; 4]) -> bool {
+            point[0] >= point[1].saturating_add(self.margin)
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_region() {
+        let mut tree: KdTree<Fxd, u32, 4, 5, u32> = KdTree::new();
+
+        let content_to_add: [([Fxd; 4], u32); 16] = [
+            ([n(0.9f32), n(0.0f32), n(0.9f32), n(0.0f32)], 9),
+            ([n(0.4f32), n(0.5f32), n(0.4f32), n(0.5f32)], 4),
+            ([n(0.12f32), n(0.3f32), n(0.12f32), n(0.3f32)], 12),
+            ([n(0.7f32), n(0.2f32), n(0.7f32), n(0.2f32)], 7),
+            ([n(0.13f32), n(0.4f32), n(0.13f32), n(0.4f32)], 13),
+            ([n(0.6f32), n(0.3f32), n(0.6f32), n(0.3f32)], 6),
+            ([n(0.2f32), n(0.7f32), n(0.2f32), n(0.7f32)], 2),
+            ([n(0.14f32), n(0.5f32), n(0.14f32), n(0.5f32)], 14),
+            ([n(0.3f32), n(0.6f32), n(0.3f32), n(0.6f32)], 3),
+            ([n(0.10f32), n(0.1f32), n(0.10f32), n(0.1f32)], 10),
+            ([n(0.16f32), n(0.7f32), n(0.16f32), n(0.7f32)], 16),
+            ([n(0.1f32), n(0.8f32), n(0.1f32), n(0.8f32)], 1),
+            ([n(0.15f32), n(0.6f32), n(0.15f32), n(0.6f32)], 15),
+            ([n(0.5f32), n(0.4f32), n(0.5f32), n(0.4f32)], 5),
+            ([n(0.8f32), n(0.1f32), n(0.8f32), n(0.1f32)], 8),
+            ([n(0.11f32), n(0.2f32), n(0.11f32), n(0.2f32)], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let mut result = tree.within_region(&BelowDiagonal { margin: n(0.0) });
+        result.sort();
+        assert_eq!(result, vec![5, 6, 7, 8, 9, 10]);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let region = BelowDiagonal {
+                margin: n(rng.gen_range(0f32..1f32)),
+            };
+            let expected = linear_search(&content_to_add, &region);
+
+            let mut result = tree.within_region(&region);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_region_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([Fxd; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand_data_fixed_u16_entry::<U14, u32, 4>())
+            .collect();
+
+        let mut tree: KdTree<Fxd, u32, 4, 4, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let region = BelowDiagonal {
+                margin: n(rng.gen_range(0f32..1f32)),
+            };
+            let expected = linear_search(&content_to_add, &region);
+
+            let mut result = tree.within_region(&region);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<R: QueryRegion<Fxd, 4>>(content: &[([Fxd; 4], u32)], region: &R) -> Vec<u32> {
+        let mut matching_items: Vec<u32> = content
+            .iter()
+            .filter(|(p, _)| region.contains_point(p))
+            .map(|&(_, item)| item)
+            .collect();
+
+        matching_items.sort();
+
+        matching_items
+    }
+}
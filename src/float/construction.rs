@@ -1,7 +1,9 @@
+use crate::float::distance::normalise;
 use crate::float::kdtree::{Axis, KdTree, LeafNode, StemNode};
 use crate::mirror_select_nth_unstable_by::mirror_select_nth_unstable_by;
 use crate::types::{is_stem_index, Content, Index};
 use az::{Az, Cast};
+use num_traits::Float;
 use std::ops::Rem;
 
 impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
@@ -77,6 +79,35 @@ where
         self.size = self.size + T::one();
     }
 
+    /// Adds an item to the tree, first scaling its point to unit length.
+    ///
+    /// This is how points should be added when querying with
+    /// [`CosineOnUnitSphere`](crate::float::distance::CosineOnUnitSphere). Query
+    /// points need normalising too, with [`normalise`](crate::float::distance::normalise).
+    /// A point at the origin is added unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kiddo::KdTree;
+    /// use kiddo::float::distance::{normalise, CosineOnUnitSphere};
+    ///
+    /// let mut tree: KdTree<f64, 2> = KdTree::new();
+    ///
+    /// tree.add_normalised(&[3.0, 0.0], 100);
+    /// tree.add_normalised(&[0.0, 5.0], 101);
+    ///
+    /// let nearest = tree.nearest_one::<CosineOnUnitSphere>(&normalise(&[1.0, 2.0]));
+    /// assert_eq!(nearest.item, 101);
+    /// ```
+    #[inline]
+    pub fn add_normalised(&mut self, point: &[A; K], item: T)
+    where
+        A: Float,
+    {
+        self.add(&normalise(point), item);
+    }
+
     /// Removes an item from the tree.
     ///
     /// The first argument specifies co-ordinates of the point where the item is located.
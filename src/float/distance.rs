@@ -4,8 +4,9 @@
 // #[cfg(any(target_arch = "x86_64"))]
 // use std::arch::x86_64::*;
 
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{Accumulation, DistanceMetric};
 use crate::float::kdtree::Axis;
+use num_traits::Float;
 
 /// Returns the Manhattan / "taxi cab" distance between two points.
 ///
@@ -26,6 +27,8 @@ use crate::float::kdtree::Axis;
 pub struct Manhattan {}
 
 impl<A: Axis, const K: usize> DistanceMetric<A, K> for Manhattan {
+    type Output = A;
+
     #[inline]
     fn dist(a: &[A; K], b: &[A; K]) -> A {
         a.iter()
@@ -60,6 +63,8 @@ impl<A: Axis, const K: usize> DistanceMetric<A, K> for Manhattan {
 pub struct SquaredEuclidean {}
 
 impl<A: Axis, const K: usize> DistanceMetric<A, K> for SquaredEuclidean {
+    type Output = A;
+
     #[inline]
     fn dist(a: &[A; K], b: &[A; K]) -> A {
         a.iter()
@@ -73,3 +78,711 @@ impl<A: Axis, const K: usize> DistanceMetric<A, K> for SquaredEuclidean {
         (a - b) * (a - b)
     }
 }
+
+/// Returns the Chebyshev / "chessboard" distance between two points: the
+/// largest distance between them along any single axis.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::float::distance::Chebyshev;
+///
+/// assert_eq!(0f32, Chebyshev::dist(&[0f32, 0f32], &[0f32, 0f32]));
+/// assert_eq!(1f32, Chebyshev::dist(&[0f32, 0f32], &[1f32, 0f32]));
+/// assert_eq!(2f32, Chebyshev::dist(&[0f32, 0f32], &[1f32, 2f32]));
+/// ```
+pub struct Chebyshev {}
+
+impl<A: Axis, const K: usize> DistanceMetric<A, K> for Chebyshev {
+    type Output = A;
+
+    const ACCUMULATION: Accumulation = Accumulation::Max;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> A {
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a_val, &b_val)| (a_val - b_val).abs())
+            .fold(A::zero(), |acc, dist| acc.max(dist))
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> A {
+        (a - b).abs()
+    }
+}
+
+/// Returns the Minkowski distance of order `P` between two points, raised to
+/// the power `P`.
+///
+/// As with [`SquaredEuclidean`], the root is not taken, as it is expensive and
+/// does not change the ordering of distances. `Minkowski<1>` is equivalent to
+/// [`Manhattan`] and `Minkowski<2>` to [`SquaredEuclidean`]. `P` must be at least 1.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::float::distance::Minkowski;
+///
+/// assert_eq!(0f32, Minkowski::<3>::dist(&[0f32, 0f32], &[0f32, 0f32]));
+/// assert_eq!(1f32, Minkowski::<3>::dist(&[0f32, 0f32], &[1f32, 0f32]));
+/// assert_eq!(9f32, Minkowski::<3>::dist(&[0f32, 0f32], &[1f32, 2f32]));
+/// ```
+pub struct Minkowski<const P: u32> {}
+
+impl<A: Axis, const K: usize, const P: u32> DistanceMetric<A, K> for Minkowski<P> {
+    type Output = A;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> A {
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a_val, &b_val)| (a_val - b_val).abs().powi(P as i32))
+            .fold(A::zero(), std::ops::Add::add)
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> A {
+        (a - b).abs().powi(P as i32)
+    }
+}
+
+/// Returns the squared euclidean distance between two points, with the
+/// contribution of each axis multiplied by a weight chosen at runtime.
+///
+/// Using the reciprocal of the variance of each axis as its weight gives the
+/// squared Mahalanobis distance for a diagonal covariance matrix. Weights must
+/// not be negative.
+///
+/// As the weights are stored in the metric, it is used with the `_with_metric`
+/// queries rather than with those that take the metric as a type parameter.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::distance_metric::StatefulDistanceMetric;
+/// use kiddo::float::distance::WeightedSquaredEuclidean;
+///
+/// let metric = WeightedSquaredEuclidean::new([1f32, 4f32]);
+///
+/// assert_eq!(0f32, metric.dist(&[0f32, 0f32], &[0f32, 0f32]));
+/// assert_eq!(1f32, metric.dist(&[0f32, 0f32], &[1f32, 0f32]));
+/// assert_eq!(5f32, metric.dist(&[0f32, 0f32], &[1f32, 1f32]));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WeightedSquaredEuclidean<A, const K: usize> {
+    weights: [A; K],
+    min_weight: A,
+}
+
+impl<A: Axis, const K: usize> WeightedSquaredEuclidean<A, K> {
+    /// Creates a metric with the given per-axis weights
+    pub fn new(weights: [A; K]) -> Self {
+        let min_weight = weights
+            .iter()
+            .fold(A::infinity(), |acc, &weight| acc.min(weight));
+
+        Self {
+            weights,
+            min_weight,
+        }
+    }
+
+    /// the weight that the squared difference along each axis is multiplied by
+    pub fn weights(&self) -> &[A; K] {
+        &self.weights
+    }
+}
+
+// implemented for each float type rather than for any `A: Axis`, since every
+// `DistanceMetric` is already a `StatefulDistanceMetric`, and another crate could
+// implement `DistanceMetric` for this type with its own axis type
+macro_rules! impl_weighted_squared_euclidean {
+    ($t:ty) => {
+        impl<const K: usize> crate::distance_metric::StatefulDistanceMetric<$t, K>
+            for WeightedSquaredEuclidean<$t, K>
+        {
+            type Output = $t;
+
+            #[inline]
+            fn dist(&self, a: &[$t; K], b: &[$t; K]) -> $t {
+                a.iter()
+                    .zip(b.iter())
+                    .zip(self.weights.iter())
+                    .map(|((&a_val, &b_val), &weight)| weight * (a_val - b_val) * (a_val - b_val))
+                    .fold(num_traits::Zero::zero(), std::ops::Add::add)
+            }
+
+            /// Without knowing the axis, only the smallest weight gives a lower bound
+            #[inline]
+            fn dist1(&self, a: $t, b: $t) -> $t {
+                self.min_weight * (a - b) * (a - b)
+            }
+
+            #[inline]
+            fn dist1_on_axis(&self, a: $t, b: $t, dim: usize) -> $t {
+                self.weights[dim] * (a - b) * (a - b)
+            }
+        }
+    };
+}
+
+impl_weighted_squared_euclidean!(f32);
+impl_weighted_squared_euclidean!(f64);
+#[cfg(feature = "f16")]
+impl_weighted_squared_euclidean!(half::f16);
+
+/// Returns the cosine distance, `1 - cos(θ)`, between two unit-length vectors that
+/// are separated by an angle `θ`.
+///
+/// For unit vectors this is half of the squared Euclidean (chord) distance between
+/// them, which is how it is calculated. Unlike a distance calculated from the dot
+/// product, this splits into a contribution from each axis, so the bounds that the
+/// queries use to prune the tree remain correct.
+///
+/// The stored points and the query points must all be normalised to unit length, for
+/// example with [`normalise`], [`KdTree::add_normalised`](crate::float::kdtree::KdTree::add_normalised)
+/// or [`ImmutableKdTree::new_from_slice_normalised`](crate::immutable::float::kdtree::ImmutableKdTree::new_from_slice_normalised).
+/// Distances between vectors of other lengths are not cosine distances.
+///
+/// [`to_angle`](CosineOnUnitSphere::to_angle) and [`from_angle`](CosineOnUnitSphere::from_angle)
+/// convert between these distances and angles in radians.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::float::distance::CosineOnUnitSphere;
+///
+/// assert_eq!(0f64, CosineOnUnitSphere::dist(&[1f64, 0f64], &[1f64, 0f64]));
+/// assert_eq!(1f64, CosineOnUnitSphere::dist(&[1f64, 0f64], &[0f64, 1f64]));
+/// assert_eq!(2f64, CosineOnUnitSphere::dist(&[1f64, 0f64], &[-1f64, 0f64]));
+/// ```
+pub struct CosineOnUnitSphere {}
+
+impl CosineOnUnitSphere {
+    /// Converts a cosine distance into the angle between the two vectors, in radians
+    #[inline]
+    pub fn to_angle<A: Float>(dist: A) -> A {
+        (A::one() - dist).max(-A::one()).min(A::one()).acos()
+    }
+
+    /// Converts an angle between two vectors, in radians, into a cosine distance
+    #[inline]
+    pub fn from_angle<A: Float>(angle: A) -> A {
+        A::one() - angle.cos()
+    }
+}
+
+impl<A: Axis, const K: usize> DistanceMetric<A, K> for CosineOnUnitSphere {
+    type Output = A;
+
+    #[inline]
+    fn dist(a: &[A; K], b: &[A; K]) -> A {
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a_val, &b_val)| (a_val - b_val) * (a_val - b_val))
+            .fold(A::zero(), std::ops::Add::add)
+            / (A::one() + A::one())
+    }
+
+    #[inline]
+    fn dist1(a: A, b: A) -> A {
+        (a - b) * (a - b) / (A::one() + A::one())
+    }
+}
+
+/// Scales `point` to unit length, as required by [`CosineOnUnitSphere`].
+///
+/// A point at the origin has no direction, and is returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::float::distance::normalise;
+///
+/// assert_eq!(normalise(&[3f64, 4f64]), [0.6f64, 0.8f64]);
+/// assert_eq!(normalise(&[0f64, 0f64]), [0f64, 0f64]);
+/// ```
+pub fn normalise<A: Float, const K: usize>(point: &[A; K]) -> [A; K] {
+    let norm = point
+        .iter()
+        .fold(A::zero(), |acc, &val| acc + val * val)
+        .sqrt();
+
+    if norm == A::zero() {
+        return *point;
+    }
+
+    point.map(|val| val / norm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::float::kdtree::KdTree;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+
+    type AX = f64;
+
+    const TREE_SIZE: usize = 10_000;
+    const NUM_QUERIES: usize = 100;
+    const N: usize = 10;
+
+    #[test]
+    fn can_query_with_chebyshev() {
+        assert_queries_match_linear_search::<Chebyshev>(0.05);
+    }
+
+    #[test]
+    fn can_query_with_minkowski() {
+        assert_queries_match_linear_search::<Minkowski<1>>(0.2);
+        assert_queries_match_linear_search::<Minkowski<3>>(0.001);
+    }
+
+    #[test]
+    fn minkowski_matches_manhattan_and_squared_euclidean() {
+        for _ in 0..1000 {
+            let a = rand::random::<[AX; 4]>();
+            let b = rand::random::<[AX; 4]>();
+
+            assert_eq!(
+                <Minkowski<1> as DistanceMetric<AX, 4>>::dist(&a, &b),
+                <Manhattan as DistanceMetric<AX, 4>>::dist(&a, &b)
+            );
+            assert_eq!(
+                <Minkowski<2> as DistanceMetric<AX, 4>>::dist(&a, &b),
+                <SquaredEuclidean as DistanceMetric<AX, 4>>::dist(&a, &b)
+            );
+        }
+    }
+
+    #[test]
+    fn cosine_on_unit_sphere_matches_one_minus_cos() {
+        for _ in 0..1000 {
+            let a = normalise(&random_direction());
+            let b = normalise(&random_direction());
+            let cos: AX = a.iter().zip(b.iter()).map(|(a, b)| a * b).sum();
+
+            let dist = <CosineOnUnitSphere as DistanceMetric<AX, 4>>::dist(&a, &b);
+            assert!((dist - (1.0 - cos)).abs() < 1e-12);
+
+            let angle = CosineOnUnitSphere::to_angle(dist);
+            assert!((angle - cos.clamp(-1.0, 1.0).acos()).abs() < 1e-6);
+            assert!((CosineOnUnitSphere::from_angle(angle) - dist).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn can_query_with_cosine_on_unit_sphere() {
+        let content_to_add: Vec<[AX; 4]> = (0..TREE_SIZE).map(|_| random_direction()).collect();
+        let normalised: Vec<[AX; 4]> = content_to_add.iter().map(normalise).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add_normalised(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice_normalised(&content_to_add);
+
+        let radius = CosineOnUnitSphere::from_angle(0.3);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = normalise(&random_direction());
+
+            let mut expected: Vec<AX> = normalised
+                .iter()
+                .map(|p| <CosineOnUnitSphere as DistanceMetric<AX, 4>>::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected_within: Vec<AX> =
+                expected.iter().copied().filter(|&d| d < radius).collect();
+
+            let dists = |results: Vec<crate::NearestNeighbour<AX, u32>>| -> Vec<AX> {
+                results.into_iter().map(|n| n.distance).collect()
+            };
+
+            assert_eq!(
+                dists(tree.nearest_n::<CosineOnUnitSphere>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(tree.within::<CosineOnUnitSphere>(&query_point, radius)),
+                expected_within
+            );
+            assert_eq!(
+                dists(immutable_tree.nearest_n::<CosineOnUnitSphere>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(immutable_tree.within::<CosineOnUnitSphere>(&query_point, radius)),
+                expected_within
+            );
+        }
+    }
+
+    /// Chebyshev distance, but with no per-axis bound, so that the only pruning
+    /// the queries can do is through `lower_bound_to_box`
+    struct BoxBoundedChebyshev {}
+
+    impl DistanceMetric<AX, 4> for BoxBoundedChebyshev {
+        type Output = AX;
+
+        fn dist(a: &[AX; 4], b: &[AX; 4]) -> AX {
+            <Chebyshev as DistanceMetric<AX, 4>>::dist(a, b)
+        }
+
+        fn dist1(_a: AX, _b: AX) -> AX {
+            0.0
+        }
+
+        fn lower_bound_to_box(query: &[AX; 4], min: &[AX; 4], max: &[AX; 4]) -> Option<AX> {
+            Some((0..4).fold(0.0, |acc: AX, dim| {
+                acc.max(min[dim] - query[dim]).max(query[dim] - max[dim])
+            }))
+        }
+    }
+
+    #[test]
+    fn can_query_with_lower_bound_to_box_only() {
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        let radius = 0.05;
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+
+            let mut expected: Vec<AX> = content_to_add
+                .iter()
+                .map(|p| BoxBoundedChebyshev::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected_within: Vec<AX> =
+                expected.iter().copied().filter(|&d| d < radius).collect();
+
+            let dists = |results: Vec<crate::NearestNeighbour<AX, u32>>| -> Vec<AX> {
+                results.into_iter().map(|n| n.distance).collect()
+            };
+
+            assert_eq!(
+                tree.nearest_one::<BoxBoundedChebyshev>(&query_point)
+                    .distance,
+                expected[0]
+            );
+            assert_eq!(
+                dists(tree.nearest_n::<BoxBoundedChebyshev>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(tree.within::<BoxBoundedChebyshev>(&query_point, radius)),
+                expected_within
+            );
+            assert_eq!(
+                dists(
+                    tree.nearest_iter::<BoxBoundedChebyshev>(&query_point)
+                        .take(N)
+                        .collect()
+                ),
+                expected[..N]
+            );
+            assert_eq!(
+                immutable_tree
+                    .nearest_one::<BoxBoundedChebyshev>(&query_point)
+                    .distance,
+                expected[0]
+            );
+            assert_eq!(
+                dists(immutable_tree.nearest_n::<BoxBoundedChebyshev>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(immutable_tree.within::<BoxBoundedChebyshev>(&query_point, radius)),
+                expected_within
+            );
+        }
+    }
+
+    /// Chebyshev distance with a lower bound that never prunes anything, so the
+    /// queries visit both sides of every stem, including unused ones
+    struct UnprunedChebyshev {}
+
+    impl DistanceMetric<AX, 4> for UnprunedChebyshev {
+        type Output = AX;
+
+        fn dist(a: &[AX; 4], b: &[AX; 4]) -> AX {
+            <Chebyshev as DistanceMetric<AX, 4>>::dist(a, b)
+        }
+
+        fn dist1(_a: AX, _b: AX) -> AX {
+            0.0
+        }
+
+        fn lower_bound_to_box(_query: &[AX; 4], _min: &[AX; 4], _max: &[AX; 4]) -> Option<AX> {
+            Some(0.0)
+        }
+    }
+
+    #[test]
+    fn can_query_immutable_tree_without_pruning() {
+        // not a power of two, so that the tree has unused stems
+        const SIZE: usize = 700;
+
+        let content_to_add: Vec<[AX; 4]> = (0..SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        let radius = 0.2;
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+
+            let mut expected: Vec<AX> = content_to_add
+                .iter()
+                .map(|p| UnprunedChebyshev::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected_within: Vec<AX> =
+                expected.iter().copied().filter(|&d| d < radius).collect();
+
+            let dists = |results: Vec<crate::NearestNeighbour<AX, u32>>| -> Vec<AX> {
+                results.into_iter().map(|n| n.distance).collect()
+            };
+
+            assert_eq!(
+                tree.nearest_one::<UnprunedChebyshev>(&query_point).distance,
+                expected[0]
+            );
+            assert_eq!(
+                dists(tree.nearest_n::<UnprunedChebyshev>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(tree.within::<UnprunedChebyshev>(&query_point, radius)),
+                expected_within
+            );
+            let mut within_unsorted =
+                dists(tree.within_unsorted::<UnprunedChebyshev>(&query_point, radius));
+            within_unsorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(within_unsorted, expected_within);
+            assert_eq!(
+                tree.best_n_within::<UnprunedChebyshev>(&query_point, radius, N)
+                    .count(),
+                expected_within.len().min(N)
+            );
+        }
+    }
+
+    #[test]
+    fn can_query_upper_bounds_with_lower_bound_to_box_only() {
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        let (min_dist, max_dist) = (0.2, 0.3);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+
+            let mut expected: Vec<AX> = content_to_add
+                .iter()
+                .map(|p| BoxBoundedChebyshev::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected_count = expected.iter().filter(|&&d| d < max_dist).count();
+            let expected_annulus: Vec<AX> = expected
+                .iter()
+                .copied()
+                .filter(|&d| d >= min_dist && d <= max_dist)
+                .collect();
+            let expected_farthest: Vec<AX> = expected.iter().rev().take(N).copied().collect();
+
+            let dists = |results: Vec<crate::NearestNeighbour<AX, u32>>| -> Vec<AX> {
+                results.into_iter().map(|n| n.distance).collect()
+            };
+
+            assert_eq!(
+                tree.count_within::<BoxBoundedChebyshev>(&query_point, max_dist),
+                expected_count
+            );
+            assert_eq!(
+                dists(tree.farthest_n::<BoxBoundedChebyshev>(&query_point, N)),
+                expected_farthest
+            );
+            assert_eq!(
+                dists(tree.within_annulus::<BoxBoundedChebyshev>(&query_point, min_dist, max_dist)),
+                expected_annulus
+            );
+            assert_eq!(
+                immutable_tree.count_within::<BoxBoundedChebyshev>(&query_point, max_dist),
+                expected_count
+            );
+            assert_eq!(
+                dists(immutable_tree.farthest_n::<BoxBoundedChebyshev>(&query_point, N)),
+                expected_farthest
+            );
+            assert_eq!(
+                dists(immutable_tree.within_annulus::<BoxBoundedChebyshev>(
+                    &query_point,
+                    min_dist,
+                    max_dist
+                )),
+                expected_annulus
+            );
+        }
+    }
+
+    /// Squared Euclidean distance between `f32` points, accumulated in `f64`
+    struct WideSquaredEuclidean {}
+
+    impl DistanceMetric<f32, 4> for WideSquaredEuclidean {
+        type Output = f64;
+
+        fn dist(a: &[f32; 4], b: &[f32; 4]) -> f64 {
+            a.iter()
+                .zip(b.iter())
+                .map(|(&a_val, &b_val)| <Self as DistanceMetric<f32, 4>>::dist1(a_val, b_val))
+                .sum()
+        }
+
+        fn dist1(a: f32, b: f32) -> f64 {
+            (a as f64 - b as f64) * (a as f64 - b as f64)
+        }
+    }
+
+    #[test]
+    fn can_query_with_wider_output_type() {
+        let content_to_add: Vec<[f32; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[f32; 4]>()).collect();
+
+        let mut tree: KdTree<f32, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<f32, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        let radius: f64 = 0.01;
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[f32; 4]>();
+
+            let mut expected: Vec<f64> = content_to_add
+                .iter()
+                .map(|p| WideSquaredEuclidean::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected_within: Vec<f64> =
+                expected.iter().copied().filter(|&d| d < radius).collect();
+
+            let dists = |results: Vec<crate::NearestNeighbour<f64, u32>>| -> Vec<f64> {
+                results.into_iter().map(|n| n.distance).collect()
+            };
+
+            assert_eq!(
+                tree.nearest_one::<WideSquaredEuclidean>(&query_point)
+                    .distance,
+                expected[0]
+            );
+            assert_eq!(
+                dists(tree.nearest_n::<WideSquaredEuclidean>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(tree.within::<WideSquaredEuclidean>(&query_point, radius)),
+                expected_within
+            );
+
+            assert_eq!(
+                immutable_tree
+                    .nearest_one::<WideSquaredEuclidean>(&query_point)
+                    .distance,
+                expected[0]
+            );
+            assert_eq!(
+                dists(immutable_tree.nearest_n::<WideSquaredEuclidean>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(immutable_tree.within::<WideSquaredEuclidean>(&query_point, radius)),
+                expected_within
+            );
+        }
+    }
+
+    fn random_direction() -> [AX; 4] {
+        rand::random::<[AX; 4]>().map(|val| val * 2.0 - 1.0)
+    }
+
+    fn assert_queries_match_linear_search<D: DistanceMetric<AX, 4, Output = AX>>(radius: AX) {
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+
+            let mut expected: Vec<AX> = content_to_add
+                .iter()
+                .map(|p| D::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected_within: Vec<AX> =
+                expected.iter().copied().filter(|&d| d < radius).collect();
+
+            let dists = |results: Vec<crate::NearestNeighbour<AX, u32>>| -> Vec<AX> {
+                results.into_iter().map(|n| n.distance).collect()
+            };
+
+            assert_eq!(tree.nearest_one::<D>(&query_point).distance, expected[0]);
+            assert_eq!(dists(tree.nearest_n::<D>(&query_point, N)), expected[..N]);
+            assert_eq!(
+                dists(tree.within::<D>(&query_point, radius)),
+                expected_within
+            );
+
+            assert_eq!(
+                immutable_tree.nearest_one::<D>(&query_point).distance,
+                expected[0]
+            );
+            assert_eq!(
+                dists(immutable_tree.nearest_n::<D>(&query_point, N)),
+                expected[..N]
+            );
+            assert_eq!(
+                dists(immutable_tree.within::<D>(&query_point, radius)),
+                expected_within
+            );
+            assert_eq!(
+                dists(immutable_tree.nearest_n_within::<D>(&query_point, radius, N, true)),
+                expected_within[..N.min(expected_within.len())]
+            );
+        }
+    }
+}
@@ -8,6 +8,7 @@ use num_traits::float::FloatCore;
 use std::cmp::PartialEq;
 use std::fmt::Debug;
 
+use crate::distance_metric::Accumulation;
 use crate::{
     iter::{IterableTreeData, TreeIter},
     types::{Content, Index},
@@ -25,6 +26,23 @@ pub trait Axis: FloatCore + Default + Debug + Copy + Sync + Send + std::ops::Add
 
     /// used in query methods to update the rd value. Basically a saturating add for Fixed and an add for Float
     fn rd_update(rd: Self, delta: Self) -> Self;
+
+    /// used in query methods to update the rd value according to how the distance
+    /// metric combines the distances along each axis: `rd_update` for
+    /// [`Accumulation::Sum`], or the larger of the two for [`Accumulation::Max`]
+    #[inline]
+    fn rd_accumulate(rd: Self, delta: Self, accumulation: Accumulation) -> Self {
+        match accumulation {
+            Accumulation::Sum => Self::rd_update(rd, delta),
+            Accumulation::Max => {
+                if delta > rd {
+                    delta
+                } else {
+                    rd
+                }
+            }
+        }
+    }
 }
 impl<T: FloatCore + Default + Debug + Copy + Sync + Send + std::ops::AddAssign> Axis for T {
     fn saturating_dist(self, other: Self) -> Self {
@@ -8,4 +8,3 @@ pub mod distance;
 pub mod kdtree;
 #[doc(hidden)]
 pub mod query;
-pub(crate) mod result_collection;
@@ -0,0 +1,171 @@
+use az::{Az, Cast};
+use num_traits::Zero;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_any_within;
+
+macro_rules! generate_float_any_within {
+    ($doctest_build_tree:tt) => {
+        generate_any_within!((
+            "Returns true if any element lies within `dist` of `query`, using the
+specified distance metric function.
+
+Equivalent to `!within_unsorted(query, dist).is_empty()`, but does not
+allocate, and returns as soon as the first matching element is found.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+    ",
+            $doctest_build_tree,
+            "
+
+    assert!(tree.any_within::<SquaredEuclidean>(&[1.0, 2.0, 5.5], 1f64));
+    assert!(!tree.any_within::<SquaredEuclidean>(&[10.0, 20.0, 50.0], 1f64));
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_any_within!(
+        "
+let mut tree: KdTree<f64, 3> = KdTree::new();
+tree.add(&[1.0, 2.0, 5.0], 100);
+tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_any_within!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::{Manhattan, SquaredEuclidean};
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_any_within() {
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.5f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.2f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.3f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.4f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.1f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        assert!(tree.any_within::<Manhattan>(&[0.4f32, 0.5f32, 0.4f32, 0.5f32], 0.01));
+        assert!(!tree.any_within::<Manhattan>(&[0.4f32, 0.5f32, 0.4f32, 0.5f32], 0.0));
+        assert!(!tree.any_within::<Manhattan>(&[5.0f32; 4], 1.0));
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let radius: f32 = rng.gen_range(0.0..0.5);
+
+            let expected = linear_any::<Manhattan, AX, 4>(&content_to_add, &query_point, radius);
+            let result = tree.any_within::<Manhattan>(&query_point, radius);
+            assert_eq!(result, expected);
+
+            let expected =
+                linear_any::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, radius);
+            let result = tree.any_within::<SquaredEuclidean>(&query_point, radius);
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_any_within_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[f32; 4]>();
+            let radius: f32 = rng.gen_range(0.0..0.001);
+
+            let expected =
+                linear_any::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, radius);
+            let result = tree.any_within::<SquaredEuclidean>(&query_point, radius);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_any<D: DistanceMetric<A, K, Output = A>, A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        query_point: &[A; K],
+        radius: A,
+    ) -> bool {
+        content
+            .iter()
+            .any(|(p, _)| D::dist(query_point, p) < radius)
+    }
+}
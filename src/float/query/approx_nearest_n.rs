@@ -0,0 +1,220 @@
+use az::{Az, Cast};
+use num_traits::{One, Zero};
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_approx_nearest_n;
+
+macro_rules! generate_float_approx_nearest_n {
+    ($doctest_build_tree:tt) => {
+        generate_approx_nearest_n!((
+            "Finds approximately the nearest `qty` elements to `query`, using the specified
+distance metric function.
+
+A subtree is only searched if it could contain an item that is closer than the current
+`qty`th-best result by more than a factor of `1 + epsilon`. The distance of the `i`th
+result is therefore at most `1 + epsilon` times the distance of the true `i`th nearest
+item, and an `epsilon` of zero gives the same results as `nearest_n`.
+
+The bound applies to the values returned by the distance metric, so when using
+`SquaredEuclidean` the bound on the Euclidean distance is `sqrt(1 + epsilon)`.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let nearest: Vec<_> = tree.approx_nearest_n::<SquaredEuclidean>(&[1.0, 2.0, 5.1], 1, 0.5);
+
+    assert_eq!(nearest.len(), 1);
+    assert!((nearest[0].distance - 0.01f64).abs() < f64::EPSILON);
+    assert_eq!(nearest[0].item, 100);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_approx_nearest_n!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_approx_nearest_n!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_approx_nearest_n_items() {
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.51f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.22f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.33f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.44f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.15f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        let expected = vec![(0.17569996, 6), (0.19139998, 5), (0.24420004, 7)];
+
+        let result: Vec<_> = tree
+            .approx_nearest_n::<SquaredEuclidean>(&query_point, 3, 0.0)
+            .into_iter()
+            .map(|n| (n.distance, n.item))
+            .collect();
+        assert_eq!(result, expected);
+
+        assert!(tree
+            .approx_nearest_n::<SquaredEuclidean>(&query_point, 0, 0.0)
+            .is_empty());
+
+        let qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let epsilon = rng.gen_range(0f32..2f32);
+            let expected = linear_search(&content_to_add, qty, &query_point);
+
+            let result: Vec<_> = tree
+                .approx_nearest_n::<SquaredEuclidean>(&query_point, qty, epsilon)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+
+            assert_within_bound(&result, &expected, epsilon);
+        }
+    }
+
+    #[test]
+    fn can_query_approx_nearest_10_items_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let query_points: Vec<[f32; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[f32; 4]>())
+            .collect();
+
+        for query_point in query_points {
+            let expected = linear_search(&content_to_add, N, &query_point);
+
+            let exact: Vec<_> = tree
+                .approx_nearest_n::<SquaredEuclidean>(&query_point, N, 0.0)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            assert_eq!(exact, expected);
+
+            for epsilon in [0.1f32, 0.5f32, 1.0f32] {
+                let result: Vec<_> = tree
+                    .approx_nearest_n::<SquaredEuclidean>(&query_point, N, epsilon)
+                    .into_iter()
+                    .map(|n| n.distance)
+                    .collect();
+
+                assert_within_bound(&result, &expected, epsilon);
+            }
+        }
+    }
+
+    fn assert_within_bound(result: &[AX], expected: &[AX], epsilon: AX) {
+        assert_eq!(result.len(), expected.len());
+        for (result_dist, expected_dist) in result.iter().zip(expected) {
+            assert!(*result_dist <= *expected_dist * (1.0 + epsilon));
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        qty: usize,
+        query_point: &[A; K],
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .map(|(p, _)| SquaredEuclidean::dist(query_point, p))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(qty);
+
+        dists
+    }
+}
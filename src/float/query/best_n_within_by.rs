@@ -0,0 +1,210 @@
+use az::{Az, Cast};
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::best_neighbour::{BestNeighbour, ScoredNeighbour};
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_best_n_within_by;
+
+macro_rules! generate_float_best_n_within_by {
+    ($doctest_build_tree:tt) => {
+        generate_best_n_within_by!((
+            "Finds the \"best\" `max_qty` elements within `dist` of `query`, ranked by a
+caller-supplied `score` function rather than by the item values themselves.
+
+`score` is called with the item and its distance from `query` for every element
+within `dist`, and the elements with the lowest scores are kept. Wrap the score
+in [`std::cmp::Reverse`] to keep the highest-scoring elements instead.
+Results are returned best-first.
+
+# Examples
+
+```rust
+    use std::cmp::Reverse;
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+
+    let population = |item: u64| if item == 101 { 5000 } else { 20 };
+    ",
+            $doctest_build_tree,
+            "
+
+    let mut best = tree.best_n_within_by::<SquaredEuclidean, _, _>(
+        &[1.0, 2.0, 5.0],
+        10f64,
+        1,
+        |item, _| Reverse(population(item)),
+    );
+
+    assert_eq!(best.next().unwrap().item, 101);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_best_n_within_by!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_best_n_within_by!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+    use std::cmp::Reverse;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_best_n_items_within_radius_by_score() {
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.5f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.2f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.3f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.4f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.1f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        // a side table of scores, indexed by item
+        let population: Vec<u32> = (0..=16).map(|_| rand::random()).collect();
+        let max_qty = 5;
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let radius = rng.gen_range(0f32..1f32);
+
+            let score = |item: u32, _| Reverse(population[item as usize]);
+            let expected = linear_search(&content_to_add, &query, radius, max_qty, score);
+
+            let result: Vec<_> = tree
+                .best_n_within_by::<SquaredEuclidean, _, _>(&query, radius, max_qty, score)
+                .map(|best| score(best.item, best.distance))
+                .collect();
+            assert_eq!(result, expected);
+
+            let score = |item: u32, _| item;
+            let expected = linear_search(&content_to_add, &query, radius, max_qty, score);
+
+            let result: Vec<_> = tree
+                .best_n_within_by::<SquaredEuclidean, _, _>(&query, radius, max_qty, score)
+                .map(|best| best.item)
+                .collect();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_best_n_items_within_radius_by_score_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        let max_qty = 5;
+
+        let content_to_add: Vec<([AX; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([AX; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let population: Vec<u32> = (0..TREE_SIZE).map(|_| rand::random()).collect();
+        let score = |item: u32, _| Reverse(population[item as usize % TREE_SIZE]);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+            let radius = 0.05;
+            let expected = linear_search(&content_to_add, &query_point, radius, max_qty, score);
+
+            let result: Vec<_> = tree
+                .best_n_within_by::<SquaredEuclidean, _, _>(&query_point, radius, max_qty, score)
+                .map(|best| score(best.item, best.distance))
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize, F: Fn(u32, A) -> S, S: Ord + Copy>(
+        content: &[([A; K], u32)],
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+        score: F,
+    ) -> Vec<S> {
+        let mut scores = vec![];
+
+        for &(p, item) in content {
+            let distance = SquaredEuclidean::dist(query, &p);
+            if distance <= radius {
+                scores.push(score(item, distance));
+            }
+        }
+
+        scores.sort_unstable();
+        scores.truncate(max_qty);
+
+        scores
+    }
+}
@@ -0,0 +1,191 @@
+use az::Cast;
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+
+use crate::best_neighbour::ScoredNeighbour;
+use crate::distance_metric::{DistanceMetric, StatelessMetric};
+use crate::float::kdtree::{Axis, KdTree};
+use crate::point_neighbour::PointNeighbour;
+use crate::types::{Content, Index};
+
+use crate::generate_best_n_within_with_point;
+
+macro_rules! generate_float_best_n_within_with_point {
+    ($doctest_build_tree:tt) => {
+        generate_best_n_within_with_point!((
+            "Finds the \"best\" `n` elements within `dist` of `query`, returning the
+coordinates of each found point alongside its distance and item.
+
+Behaves like [`best_n_within`](Self::best_n_within): \"best\" is determined by the
+lowest item values, and results are returned in arbitrary order.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let mut best = tree.best_n_within_with_point::<SquaredEuclidean>(&[1.0, 2.0, 5.1], 10f64, 1);
+
+    let first = best.next().unwrap();
+    assert_eq!(first.item, 100);
+    assert_eq!(first.point, [1.0, 2.0, 5.0]);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_best_n_within_with_point!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_best_n_within_with_point!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_best_n_items_within_radius_with_points() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.5f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.2f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.3f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.4f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.1f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        assert_eq!(tree.size(), 16);
+
+        let max_qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let radius = rng.gen_range(0f32..1f32);
+            let expected = linear_search(&content_to_add, &query_point, radius, max_qty);
+
+            let result: Vec<_> = tree
+                .best_n_within_with_point::<SquaredEuclidean>(&query_point, radius, max_qty)
+                .collect();
+
+            let mut result_items: Vec<_> = result.iter().map(|found| found.item).collect();
+            result_items.sort_unstable();
+            assert_eq!(result_items, expected);
+            assert!(result
+                .iter()
+                .all(|found| found.point == content_to_add[found.item as usize]));
+        }
+    }
+
+    #[test]
+    fn can_query_best_n_items_within_radius_with_points_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        let max_qty = 10;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+            let radius = 0.05;
+            let expected = linear_search(&content_to_add, &query_point, radius, max_qty);
+
+            let result: Vec<_> = tree
+                .best_n_within_with_point::<SquaredEuclidean>(&query_point, radius, max_qty)
+                .collect();
+
+            let mut result_items: Vec<_> = result.iter().map(|found| found.item).collect();
+            result_items.sort_unstable();
+            assert_eq!(result_items, expected);
+            assert!(result
+                .iter()
+                .all(|found| found.point == content_to_add[found.item as usize]));
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[[A; K]],
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+    ) -> Vec<u32> {
+        let mut items: Vec<u32> = content
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| SquaredEuclidean::dist(query, p) <= radius)
+            .map(|(item, _)| item as u32)
+            .collect();
+
+        items.sort_unstable();
+        items.truncate(max_qty);
+
+        items
+    }
+}
@@ -0,0 +1,197 @@
+use az::{Az, Cast};
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_farthest_n;
+
+macro_rules! generate_float_farthest_n {
+    ($doctest_build_tree:tt) => {
+        generate_farthest_n!((
+            "Finds the `qty` elements furthest from `query`, using the specified
+distance metric function.
+
+Results are returned sorted furthest-first. Subtrees whose points cannot be
+further from `query` than the current `qty`th-furthest result are skipped.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+    ",
+            $doctest_build_tree,
+            "
+
+    let farthest = tree.farthest_n::<SquaredEuclidean>(&[1.0, 2.0, 5.0], 1);
+
+    assert_eq!(farthest.len(), 1);
+    assert_eq!(farthest[0].item, 101);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_farthest_n!(
+        "
+let mut tree: KdTree<f64, 3> = KdTree::new();
+tree.add(&[1.0, 2.0, 5.0], 100);
+tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_farthest_n!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::{Manhattan, SquaredEuclidean};
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_farthest_n_items() {
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.5f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.2f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.3f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.4f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.1f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        assert!(tree.farthest_n::<Manhattan>(&query_point, 0).is_empty());
+        assert_eq!(tree.farthest_n::<Manhattan>(&query_point, 100).len(), 16);
+        assert_eq!(tree.farthest_n::<Manhattan>(&query_point, 1)[0].item, 10);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let qty = rng.gen_range(1usize..20);
+
+            let expected = linear_search::<Manhattan, AX, 4>(&content_to_add, &query_point, qty);
+            let result: Vec<_> = tree
+                .farthest_n::<Manhattan>(&query_point, qty)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            assert_eq!(result, expected);
+
+            let expected =
+                linear_search::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, qty);
+            let result: Vec<_> = tree
+                .farthest_n::<SquaredEuclidean>(&query_point, qty)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_farthest_n_items_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[f32; 4]>();
+            let qty = rng.gen_range(1usize..50);
+
+            let expected =
+                linear_search::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, qty);
+            let result: Vec<_> = tree
+                .farthest_n::<SquaredEuclidean>(&query_point, qty)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<D: DistanceMetric<A, K, Output = A>, A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        query_point: &[A; K],
+        qty: usize,
+    ) -> Vec<A> {
+        let mut distances: Vec<A> = content
+            .iter()
+            .map(|(p, _)| D::dist(query_point, p))
+            .collect();
+
+        distances.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap());
+        distances.truncate(qty);
+
+        distances
+    }
+}
@@ -0,0 +1,170 @@
+use az::{Az, Cast};
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::knn_graph::KnnGraph;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_knn_graph;
+
+macro_rules! generate_float_knn_graph {
+    ($doctest_build_tree:tt) => {
+        generate_knn_graph!((
+            "Builds a graph connecting every item in the tree to its nearest `k` neighbours,
+using the specified distance metric function.
+
+This is much faster than calling `nearest_n` once for every item, as the contents of each
+leaf are processed together: the other items in the same leaf are checked first, giving
+a tight bound with which to prune the search through the rest of the tree.
+
+If `exclude_self` is `true`, an item is not considered to be a neighbour of itself
+(although other items stored at the same position still are).
+
+The result is a [`KnnGraph`](`crate::knn_graph::KnnGraph`) in compressed sparse row form, with one row per item.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let graph = tree.knn_graph::<SquaredEuclidean>(1, true);
+
+    assert_eq!(graph.len(), 2);
+    assert_eq!(graph.neighbours_of(100).unwrap()[0].item, 101);
+    assert_eq!(graph.neighbours_of(101).unwrap()[0].item, 100);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_knn_graph!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_knn_graph!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::{Axis, KdTree};
+
+    type AX = f32;
+
+    #[test]
+    fn can_build_knn_graph() {
+        let content_to_add: Vec<[AX; 4]> = (0..100).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        for k in [0, 1, 3, 8] {
+            for exclude_self in [false, true] {
+                let graph = tree.knn_graph::<SquaredEuclidean>(k, exclude_self);
+
+                assert_graph_matches_linear_search(&graph, &content_to_add, k, exclude_self);
+            }
+        }
+    }
+
+    #[test]
+    fn can_build_knn_graph_large_scale() {
+        const TREE_SIZE: usize = 2_000;
+        const N: usize = 10;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let graph = tree.knn_graph::<SquaredEuclidean>(N, true);
+
+        assert_graph_matches_linear_search(&graph, &content_to_add, N, true);
+    }
+
+    fn assert_graph_matches_linear_search(
+        graph: &crate::knn_graph::KnnGraph<AX, u32>,
+        content: &[[AX; 4]],
+        k: usize,
+        exclude_self: bool,
+    ) {
+        assert_eq!(graph.len(), content.len());
+        assert_eq!(graph.offsets.len(), content.len() + 1);
+
+        for (idx, (item, neighbours)) in graph.iter().enumerate() {
+            assert_eq!(item, idx as u32);
+
+            let expected = linear_search(content, k, idx, exclude_self);
+            let result: Vec<_> = neighbours.iter().map(|n| n.distance).collect();
+
+            assert_eq!(result, expected);
+            if exclude_self {
+                assert!(neighbours.iter().all(|n| n.item != item));
+            }
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[[A; K]],
+        k: usize,
+        query_idx: usize,
+        exclude_self: bool,
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !exclude_self || *idx != query_idx)
+            .map(|(_, p)| SquaredEuclidean::dist(&content[query_idx], p))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(k);
+
+        dists
+    }
+}
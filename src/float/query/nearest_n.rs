@@ -1,10 +1,12 @@
 use az::{Az, Cast};
+use num_traits::Zero;
 use std::collections::BinaryHeap;
 use std::ops::Rem;
 
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{DistanceMetric, StatefulDistanceMetric, StatelessMetric};
 use crate::float::kdtree::{Axis, KdTree};
 use crate::nearest_neighbour::NearestNeighbour;
+use crate::neighbour::{FromLeafEntry, Neighbour};
 use crate::types::{is_stem_index, Content, Index};
 
 use crate::generate_nearest_n;
@@ -0,0 +1,159 @@
+use az::Cast;
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+
+use crate::distance_metric::{DistanceMetric, StatelessMetric};
+use crate::float::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{Content, Index};
+
+use crate::generate_nearest_n_into;
+
+macro_rules! generate_float_nearest_n_into {
+    ($doctest_build_tree:tt) => {
+        generate_nearest_n_into!((
+            "Finds the nearest `qty` elements to `query`, according to the specified
+distance metric function, writing them into `results`.
+
+Behaves like [`nearest_n`](Self::nearest_n), but `results` is cleared and reused rather
+than a new `Vec` being allocated, so repeated queries into the same `Vec` do not need to
+allocate once it has grown large enough. Results are sorted nearest-first.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let mut results = Vec::new();
+    tree.nearest_n_into::<SquaredEuclidean>(&[1.0, 2.0, 5.1], 1, &mut results);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].item, 100);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_nearest_n_into!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_nearest_n_into!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::KdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_n_items_into_reused_vec() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.5f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.2f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.3f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.4f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.1f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        assert_eq!(tree.size(), 16);
+
+        // start with more capacity than needed, so that the allocation
+        // being reused can't limit the number of results
+        let mut results = Vec::with_capacity(64);
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let qty = rng.gen_range(0usize..10usize);
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let expected = tree.nearest_n::<SquaredEuclidean>(&query_point, qty);
+
+            tree.nearest_n_into::<SquaredEuclidean>(&query_point, qty, &mut results);
+
+            assert_eq!(results, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_n_items_into_reused_vec_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut results = Vec::new();
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+            let expected = tree.nearest_n::<SquaredEuclidean>(&query_point, N);
+
+            tree.nearest_n_into::<SquaredEuclidean>(&query_point, N, &mut results);
+
+            assert_eq!(results, expected);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use az::Cast;
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+
+use crate::distance_metric::StatefulDistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{Content, Index};
+
+use crate::generate_nearest_n_with_metric;
+
+macro_rules! generate_float_nearest_n_with_metric {
+    ($doctest_build_tree:tt) => {
+        generate_nearest_n_with_metric!((
+            "Finds the nearest `qty` elements to `query`, using the supplied `metric`
+instance to measure distances.
+
+Behaves like [`nearest_n`](Self::nearest_n), but allows the metric to carry
+parameters that are chosen at runtime, such as per-axis weights.
+Results are returned sorted nearest-first.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::float::distance::WeightedSquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let metric = WeightedSquaredEuclidean::new([1.0, 4.0, 0.5]);
+    let nearest = tree.nearest_n_with_metric(&metric, &[1.0, 2.0, 5.1], 1);
+
+    assert_eq!(nearest.len(), 1);
+    assert_eq!(nearest[0].item, 100);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_nearest_n_with_metric!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_nearest_n_with_metric!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::StatefulDistanceMetric;
+    use crate::float::distance::WeightedSquaredEuclidean;
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_n_items_with_metric() {
+        let metric = WeightedSquaredEuclidean::new([1.0, 4.0, 0.25, 2.0]);
+
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.5f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.2f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.3f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.4f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.1f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        assert_eq!(tree.size(), 16);
+
+        let qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let expected = linear_search(&metric, &content_to_add, &query_point, qty);
+
+            let result = tree.nearest_n_with_metric(&metric, &query_point, qty);
+
+            let result_dists: Vec<_> = result.iter().map(|found| found.distance).collect();
+            assert_eq!(result_dists, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_n_items_with_metric_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let metric = WeightedSquaredEuclidean::new([1.0, 4.0, 0.25, 2.0]);
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+            let expected = linear_search(&metric, &content_to_add, &query_point, N);
+
+            let result = tree.nearest_n_with_metric(&metric, &query_point, N);
+
+            let result_dists: Vec<_> = result.iter().map(|found| found.distance).collect();
+            assert_eq!(result_dists, expected);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize, D: StatefulDistanceMetric<A, K, Output = A>>(
+        metric: &D,
+        content: &[[A; K]],
+        query: &[A; K],
+        qty: usize,
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content.iter().map(|p| metric.dist(query, p)).collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(qty);
+
+        dists
+    }
+}
@@ -1,12 +1,13 @@
 use az::{Az, Cast};
+use num_traits::Zero;
 use sorted_vec::SortedVec;
 use std::collections::BinaryHeap;
 use std::ops::Rem;
 
 use crate::distance_metric::DistanceMetric;
 use crate::float::kdtree::{Axis, KdTree};
-use crate::float::result_collection::ResultCollection;
 use crate::nearest_neighbour::NearestNeighbour;
+use crate::result_collection::ResultCollection;
 use crate::types::{is_stem_index, Content, Index};
 
 use crate::generate_nearest_n_within_unsorted;
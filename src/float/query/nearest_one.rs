@@ -1,10 +1,13 @@
 use az::{Az, Cast};
+use num_traits::float::FloatCore;
+use num_traits::Zero;
 use std::ops::Rem;
 
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{DistanceMetric, StatefulDistanceMetric, StatelessMetric};
 use crate::float::kdtree::{Axis, KdTree, LeafNode};
 use crate::generate_nearest_one;
 use crate::nearest_neighbour::NearestNeighbour;
+use crate::neighbour::FromLeafEntry;
 use crate::types::{is_stem_index, Content, Index};
 
 macro_rules! generate_float_nearest_one {
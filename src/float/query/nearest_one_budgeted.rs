@@ -0,0 +1,214 @@
+use az::{Az, Cast};
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+use std::ops::Rem;
+
+use crate::distance_metric::{DistanceMetric, StatelessMetric};
+use crate::float::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::search_budget::{BudgetTracker, BudgetedResult, SearchBudget};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_nearest_one_budgeted;
+
+macro_rules! generate_float_nearest_one_budgeted {
+    ($doctest_build_tree:tt) => {
+        generate_nearest_one_budgeted!((
+            "Finds the nearest element to `query`, using the specified
+distance metric function, doing no more work than is allowed by `budget`.
+
+If the budget runs out before the search completes, the closest element found so far
+is returned and `exact` is `false` in the returned [`BudgetedResult`]. If the budget
+runs out before any leaves have been searched, the returned result is `None`.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+    use kiddo::search_budget::SearchBudget;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let nearest = tree.nearest_one_budgeted::<SquaredEuclidean>(&[1.0, 2.0, 5.1], SearchBudget::with_max_leaves(4));
+
+    assert!(nearest.exact);
+    assert!((nearest.result.unwrap().distance - 0.01f64).abs() < f64::EPSILON);
+    assert_eq!(nearest.result.unwrap().item, 100);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_nearest_one_budgeted!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_nearest_one_budgeted!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::Manhattan;
+    use crate::float::kdtree::{Axis, KdTree};
+    use crate::nearest_neighbour::NearestNeighbour;
+    use crate::search_budget::SearchBudget;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_one_item_budgeted() {
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.51f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.22f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.33f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.44f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.15f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        let result =
+            tree.nearest_one_budgeted::<Manhattan>(&query_point, SearchBudget::unlimited());
+        assert!(result.exact);
+        assert_eq!(result.result.unwrap().distance, 0.819_999_93);
+
+        let result =
+            tree.nearest_one_budgeted::<Manhattan>(&query_point, SearchBudget::with_max_leaves(0));
+        assert!(!result.exact);
+        assert_eq!(result.result, None);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let budget = SearchBudget::new(rng.gen_range(0..6), rng.gen_range(0..8));
+            let expected = linear_search(&content_to_add, &query_point);
+
+            let result = tree.nearest_one_budgeted::<Manhattan>(&query_point, budget);
+
+            if result.exact {
+                assert_eq!(result.result.unwrap().distance, expected.distance);
+            } else {
+                assert!(result
+                    .result
+                    .is_none_or(|nearest| nearest.distance >= expected.distance));
+            }
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_one_item_budgeted_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let query_points: Vec<[f32; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[f32; 4]>())
+            .collect();
+
+        for query_point in query_points {
+            let expected = linear_search(&content_to_add, &query_point);
+
+            let result =
+                tree.nearest_one_budgeted::<Manhattan>(&query_point, SearchBudget::unlimited());
+            assert!(result.exact);
+            assert_eq!(result.result, Some(expected));
+
+            let result = tree
+                .nearest_one_budgeted::<Manhattan>(&query_point, SearchBudget::with_max_leaves(4));
+            if result.exact {
+                assert_eq!(result.result.unwrap().distance, expected.distance);
+            } else {
+                assert!(result
+                    .result
+                    .is_none_or(|nearest| nearest.distance >= expected.distance));
+            }
+            assert!(result.result.is_some());
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        query_point: &[A; K],
+    ) -> NearestNeighbour<A, u32> {
+        let mut best_dist: A = A::infinity();
+        let mut best_item: u32 = u32::MAX;
+
+        for &(p, item) in content {
+            let dist = Manhattan::dist(query_point, &p);
+            if dist < best_dist {
+                best_item = item;
+                best_dist = dist;
+            }
+        }
+
+        NearestNeighbour {
+            distance: best_dist,
+            item: best_item,
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use az::{Az, Cast};
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_reverse_nearest_n;
+
+macro_rules! generate_float_reverse_nearest_n {
+    ($doctest_build_tree:tt) => {
+        generate_reverse_nearest_n!((
+            "Finds the stored elements that would have `query` among their own `k`
+nearest neighbours, using the specified distance metric function.
+
+An element is returned if fewer than `k` other stored elements are strictly
+closer to it than `query` is. Results are returned sorted nearest-first,
+with `distance` being the distance from `query` to each element.
+Subtrees that hold more than `k` elements and are further from `query` than
+their own diameter are skipped.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+    ",
+            $doctest_build_tree,
+            "
+
+    let reverse_nearest = tree.reverse_nearest_n::<SquaredEuclidean>(&[2.5, 3.5, 6.5], 1);
+
+    assert_eq!(reverse_nearest.len(), 1);
+    assert_eq!(reverse_nearest[0].item, 101);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_reverse_nearest_n!(
+        "
+let mut tree: KdTree<f64, 3> = KdTree::new();
+tree.add(&[1.0, 2.0, 5.0], 100);
+tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_reverse_nearest_n!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::{Manhattan, SquaredEuclidean};
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_reverse_nearest_n_items() {
+        let content_to_add: Vec<[AX; 4]> = (0..100).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        let mut rng = rand::thread_rng();
+
+        assert!(tree
+            .reverse_nearest_n::<SquaredEuclidean>(&[0.5f32; 4], 0)
+            .is_empty());
+
+        for _i in 0..100 {
+            let query_point = rng.gen::<[AX; 4]>();
+
+            for k in [1, 3, 8] {
+                let expected = linear_search::<Manhattan, AX, 4>(&content_to_add, &query_point, k);
+                let result: Vec<_> = tree
+                    .reverse_nearest_n::<Manhattan>(&query_point, k)
+                    .into_iter()
+                    .map(|n| (n.distance, n.item))
+                    .collect();
+                assert_eq!(result, expected);
+
+                let expected =
+                    linear_search::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, k);
+                let result: Vec<_> = tree
+                    .reverse_nearest_n::<SquaredEuclidean>(&query_point, k)
+                    .into_iter()
+                    .map(|n| (n.distance, n.item))
+                    .collect();
+                assert_eq!(result, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn can_query_reverse_nearest_n_items_large_scale() {
+        const TREE_SIZE: usize = 2_000;
+        const NUM_QUERIES: usize = 10;
+        const N: usize = 5;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rng.gen::<[AX; 4]>();
+
+            let expected =
+                linear_search::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, N);
+            let result: Vec<_> = tree
+                .reverse_nearest_n::<SquaredEuclidean>(&query_point, N)
+                .into_iter()
+                .map(|n| (n.distance, n.item))
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_reverse_nearest_n_items_in_clusters() {
+        const N: usize = 3;
+
+        // tight clusters fill whole leaves, so most candidates are rejected by
+        // the distances to the other points in their leaf
+        let mut rng = rand::thread_rng();
+        let centres: Vec<[AX; 4]> = (0..8).map(|_| rng.gen::<[AX; 4]>()).collect();
+        let content_to_add: Vec<[AX; 4]> = (0..1_000)
+            .map(|idx| {
+                let centre = centres[idx % centres.len()];
+                std::array::from_fn(|dim| centre[dim] + rng.gen_range(-0.01f32..0.01f32))
+            })
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::new();
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+
+        for centre in &centres {
+            let query_point = centre.map(|x| x + 0.005);
+
+            let expected =
+                linear_search::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, N);
+            let result: Vec<_> = tree
+                .reverse_nearest_n::<SquaredEuclidean>(&query_point, N)
+                .into_iter()
+                .map(|n| (n.distance, n.item))
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<D: DistanceMetric<A, K, Output = A>, A: Axis, const K: usize>(
+        content: &[[A; K]],
+        query_point: &[A; K],
+        k: usize,
+    ) -> Vec<(A, u32)> {
+        let mut matching_items: Vec<(A, u32)> = content
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, p)| {
+                let distance = D::dist(query_point, p);
+                let closer_count = content
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other_idx, other)| other_idx != idx && D::dist(p, other) < distance)
+                    .count();
+
+                (closer_count < k).then_some((distance, idx as u32))
+            })
+            .collect();
+
+        matching_items.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap().then(a.1.cmp(&b.1)));
+
+        matching_items
+    }
+}
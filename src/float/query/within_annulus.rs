@@ -0,0 +1,234 @@
+use az::{Az, Cast};
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_within_annulus;
+
+macro_rules! generate_float_within_annulus {
+    ($doctest_build_tree:tt) => {
+        generate_within_annulus!((
+            "Finds all elements whose distance from `query` is at least `min_dist`
+and at most `max_dist`, using the specified distance metric function.
+
+Results are returned sorted nearest-first. Subtrees that lie entirely
+closer than `min_dist` or entirely further than `max_dist` are skipped.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+    ",
+            $doctest_build_tree,
+            "
+
+    let within = tree.within_annulus::<SquaredEuclidean>(&[1.0, 2.0, 5.0], 1f64, 10f64);
+
+    assert_eq!(within.len(), 1);
+    assert_eq!(within[0].item, 101);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_annulus!(
+        "
+let mut tree: KdTree<f64, 3> = KdTree::new();
+tree.add(&[1.0, 2.0, 5.0], 100);
+tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_annulus!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::{Manhattan, SquaredEuclidean};
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+    use std::cmp::Ordering;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_items_within_annulus() {
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.5f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.2f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.3f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.4f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.1f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        assert_eq!(
+            tree.within_annulus::<Manhattan>(&query_point, 0.0, 100.0)
+                .len(),
+            16
+        );
+        assert!(tree
+            .within_annulus::<Manhattan>(&query_point, 100.0, 200.0)
+            .is_empty());
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let min_dist: f32 = rng.gen_range(0.0..1.0);
+            let max_dist: f32 = min_dist + rng.gen_range(0.0..1.0);
+
+            let expected = linear_search::<Manhattan, AX, 4>(
+                &content_to_add,
+                &query_point,
+                min_dist,
+                max_dist,
+            );
+            let mut result: Vec<_> = tree
+                .within_annulus::<Manhattan>(&query_point, min_dist, max_dist)
+                .into_iter()
+                .map(|n| (n.distance, n.item))
+                .collect();
+            stabilize_sort(&mut result);
+            assert_eq!(result, expected);
+
+            let expected = linear_search::<SquaredEuclidean, AX, 4>(
+                &content_to_add,
+                &query_point,
+                min_dist,
+                max_dist,
+            );
+            let mut result: Vec<_> = tree
+                .within_annulus::<SquaredEuclidean>(&query_point, min_dist, max_dist)
+                .into_iter()
+                .map(|n| (n.distance, n.item))
+                .collect();
+            stabilize_sort(&mut result);
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_annulus_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[f32; 4]>();
+            let min_dist: f32 = rng.gen_range(0.0..0.5);
+            let max_dist: f32 = min_dist + rng.gen_range(0.0..0.05);
+
+            let expected = linear_search::<SquaredEuclidean, AX, 4>(
+                &content_to_add,
+                &query_point,
+                min_dist,
+                max_dist,
+            );
+            let mut result: Vec<_> = tree
+                .within_annulus::<SquaredEuclidean>(&query_point, min_dist, max_dist)
+                .into_iter()
+                .map(|n| (n.distance, n.item))
+                .collect();
+            stabilize_sort(&mut result);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<D: DistanceMetric<A, K, Output = A>, A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        query_point: &[A; K],
+        min_dist: A,
+        max_dist: A,
+    ) -> Vec<(A, u32)> {
+        let mut matching_items = vec![];
+
+        for &(p, item) in content {
+            let dist = D::dist(query_point, &p);
+            if dist >= min_dist && dist <= max_dist {
+                matching_items.push((dist, item));
+            }
+        }
+
+        stabilize_sort(&mut matching_items);
+
+        matching_items
+    }
+
+    fn stabilize_sort<A: Axis>(matching_items: &mut [(A, u32)]) {
+        matching_items.sort_unstable_by(|a, b| {
+            let dist_cmp = a.0.partial_cmp(&b.0).unwrap();
+            if dist_cmp == Ordering::Equal {
+                a.1.cmp(&b.1)
+            } else {
+                dist_cmp
+            }
+        });
+    }
+}
@@ -0,0 +1,163 @@
+use az::Cast;
+
+use crate::batch_results::BatchResults;
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::{Axis, KdTree};
+use crate::types::{Content, Index};
+
+use crate::generate_within_batch;
+
+macro_rules! generate_float_within_batch {
+    ($doctest_build_tree:tt) => {
+        generate_within_batch!((
+            "Finds all elements within `dist` of each of `queries`, using the specified
+distance metric function, running the queries in parallel using Rayon.
+
+The results of all of the queries are returned together in a single [`BatchResults`](`crate::batch_results::BatchResults`),
+in the same order as `queries`, with each query's results sorted nearest-first.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let within = tree.within_batch::<SquaredEuclidean>(&[[1.0, 2.0, 5.0], [20.0, 30.0, 60.0]], 10f64);
+
+    assert_eq!(within.len(), 2);
+    assert_eq!(within[0].len(), 2);
+    assert!(within[1].is_empty());
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_batch!(
+        "let mut tree: KdTree<f64, 3> = KdTree::new();
+    tree.add(&[1.0, 2.0, 5.0], 100);
+    tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_batch!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::distance::Manhattan;
+    use crate::float::kdtree::KdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_within_batch() {
+        let mut tree: KdTree<AX, u32, 4, 4, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.51f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.22f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.33f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.44f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.15f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+        let radius = 0.2;
+
+        let mut rng = rand::thread_rng();
+        let query_points: Vec<[AX; 4]> = (0..1000)
+            .map(|_| {
+                [
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                ]
+            })
+            .collect();
+
+        let result = tree.within_batch::<Manhattan>(&query_points, radius);
+
+        assert_eq!(result.len(), query_points.len());
+        assert_eq!(result.offsets.len(), query_points.len() + 1);
+        for (query_point, result) in query_points.iter().zip(result.iter()) {
+            assert_eq!(result, tree.within::<Manhattan>(query_point, radius));
+        }
+
+        let result = tree.within_batch::<Manhattan>(&[], radius);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn can_query_within_batch_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 1_000;
+        const RADIUS: f32 = 0.2;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let query_points: Vec<[f32; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[f32; 4]>())
+            .collect();
+
+        let result = tree.within_batch::<Manhattan>(&query_points, RADIUS);
+
+        assert_eq!(result.len(), query_points.len());
+        assert_eq!(result.offsets.len(), query_points.len() + 1);
+        for (query_point, result) in query_points.iter().zip(result.iter()) {
+            assert_eq!(result, tree.within::<Manhattan>(query_point, RADIUS));
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use az::{Az, Cast};
+use std::ops::Rem;
+
+use crate::float::kdtree::{Axis, KdTree};
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_within_box_unsorted;
+
+macro_rules! generate_float_within_box_unsorted {
+    ($doctest_build_tree:tt) => {
+        generate_within_box_unsorted!((
+            "Finds all elements whose points lie within the axis-aligned box
+spanning from `min` to `max` (inclusive on all sides).
+
+Results are returned in arbitrary order. Faster than `within_box`.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    ",
+            $doctest_build_tree,
+            "
+
+    let within = tree.within_box_unsorted(&[0.0, 0.0, 0.0], &[1.5, 2.5, 5.5]);
+
+    assert_eq!(within, vec![100]);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_box_unsorted!(
+        "
+let mut tree: KdTree<f64, 3> = KdTree::new();
+tree.add(&[1.0, 2.0, 5.0], 100);
+tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_box_unsorted!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::kdtree::{Axis, KdTree};
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_items_within_box_unsorted() {
+        let mut tree: KdTree<AX, u32, 4, 5, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.5f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.2f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.3f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.4f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.1f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let min = [0.4f32, 0.2f32, 0.4f32, 0.2f32];
+        let max = [0.7f32, 0.5f32, 0.7f32, 0.5f32];
+
+        let mut result = tree.within_box_unsorted(&min, &max);
+        result.sort();
+        assert_eq!(result, vec![4, 5, 6, 7]);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let (min, max) = random_box(&mut rng);
+            let expected = linear_search(&content_to_add, &min, &max);
+
+            let mut result = tree.within_box_unsorted(&min, &max);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_box_unsorted_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let (min, max) = random_box(&mut rng);
+            let expected = linear_search(&content_to_add, &min, &max);
+
+            let mut result = tree.within_box_unsorted(&min, &max);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn random_box<R: Rng>(rng: &mut R) -> ([f32; 4], [f32; 4]) {
+        let mut min = [0f32; 4];
+        let mut max = [0f32; 4];
+        for dim in 0..4 {
+            let a = rng.gen_range(0f32..1f32);
+            let b = rng.gen_range(0f32..1f32);
+            min[dim] = a.min(b);
+            max[dim] = a.max(b);
+        }
+        (min, max)
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        min: &[A; K],
+        max: &[A; K],
+    ) -> Vec<u32> {
+        let mut matching_items: Vec<u32> = content
+            .iter()
+            .filter(|(p, _)| (0..K).all(|dim| p[dim] >= min[dim] && p[dim] <= max[dim]))
+            .map(|&(_, item)| item)
+            .collect();
+
+        matching_items.sort();
+
+        matching_items
+    }
+}
@@ -0,0 +1,225 @@
+use az::{Az, Cast};
+use std::ops::Rem;
+
+use crate::float::kdtree::{Axis, KdTree};
+use crate::query_region::QueryRegion;
+use crate::types::{is_stem_index, Content, Index};
+
+use crate::generate_within_region;
+
+macro_rules! generate_float_within_region {
+    ($doctest_build_tree:tt) => {
+        generate_within_region!((
+            "Finds all elements whose points lie within `region`, which can be any
+type that implements [`QueryRegion`](`crate::query_region::QueryRegion`).
+
+Subtrees that do not intersect `region` are skipped, and the items of subtrees
+that lie entirely within `region` are returned without testing each point.
+Results are returned in arbitrary order.
+
+# Examples
+
+```rust
+    use kiddo::KdTree;
+    use kiddo::query_region::QueryRegion;
+
+    struct Slab { min_z: f64, max_z: f64 }
+
+    impl QueryRegion<f64, 3> for Slab {
+        fn intersects_box(&self, min: &[f64; 3], max: &[f64; 3]) -> bool {
+            max[2] >= self.min_z && min[2] <= self.max_z
+        }
+
+        fn contains_box(&self, min: &[f64; 3], max: &[f64; 3]) -> bool {
+            min[2] >= self.min_z && max[2] <= self.max_z
+        }
+
+        fn contains_point(&self, point: &[f64; 3]) -> bool {
+            point[2] >= self.min_z && point[2] <= self.max_z
+        }
+    }
+    ",
+            $doctest_build_tree,
+            "
+
+    let within = tree.within_region(&Slab { min_z: 4.0, max_z: 5.5 });
+
+    assert_eq!(within, vec![100]);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize, IDX: Index<T = IDX>>
+    KdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_region!(
+        "
+let mut tree: KdTree<f64, 3> = KdTree::new();
+tree.add(&[1.0, 2.0, 5.0], 100);
+tree.add(&[2.0, 3.0, 6.0], 101);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::float::kdtree::ArchivedKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+        IDX: Index<T = IDX> + rkyv::Archive<Archived = IDX>,
+    > ArchivedKdTree<A, T, K, B, IDX>
+where
+    usize: Cast<IDX>,
+{
+    generate_float_within_region!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/float-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<KdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::kdtree::KdTree;
+    use crate::query_region::QueryRegion;
+    use rand::Rng;
+
+    type AX = f32;
+
+    struct Ball {
+        centre: [AX; 4],
+        radius: AX,
+    }
+
+    impl Ball {
+        fn dist_sq(&self, point: &[AX; 4]) -> AX {
+            (0..4)
+                .map(|dim| (point[dim] - self.centre[dim]).powi(2))
+                .sum()
+        }
+    }
+
+    impl QueryRegion<AX, 4> for Ball {
+        fn intersects_box(&self, min: &[AX; 4], max: &[AX; 4]) -> bool {
+            let closest: [AX; 4] =
+                std::array::from_fn(|dim| self.centre[dim].clamp(min[dim], max[dim]));
+            self.dist_sq(&closest) <= self.radius * self.radius
+        }
+
+        fn contains_box(&self, min: &[AX; 4], max: &[AX; 4]) -> bool {
+            let furthest: [AX; 4] = std::array::from_fn(|dim| {
+                if (self.centre[dim] - min[dim]).abs() > (self.centre[dim] - max[dim]).abs() {
+                    min[dim]
+                } else {
+                    max[dim]
+                }
+            });
+            self.dist_sq(&furthest) <= self.radius * self.radius
+        }
+
+        fn contains_point(&self, point: &[AX; 4]) -> bool {
+            self.dist_sq(point) <= self.radius * self.radius
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_region() {
+        let mut tree: KdTree<AX, u32, 4, 5, u32> = KdTree::new();
+
+        let content_to_add: [([AX; 4], u32); 16] = [
+            ([0.9f32, 0.0f32, 0.9f32, 0.0f32], 9),
+            ([0.4f32, 0.5f32, 0.4f32, 0.5f32], 4),
+            ([0.12f32, 0.3f32, 0.12f32, 0.3f32], 12),
+            ([0.7f32, 0.2f32, 0.7f32, 0.2f32], 7),
+            ([0.13f32, 0.4f32, 0.13f32, 0.4f32], 13),
+            ([0.6f32, 0.3f32, 0.6f32, 0.3f32], 6),
+            ([0.2f32, 0.7f32, 0.2f32, 0.7f32], 2),
+            ([0.14f32, 0.5f32, 0.14f32, 0.5f32], 14),
+            ([0.3f32, 0.6f32, 0.3f32, 0.6f32], 3),
+            ([0.10f32, 0.1f32, 0.10f32, 0.1f32], 10),
+            ([0.16f32, 0.7f32, 0.16f32, 0.7f32], 16),
+            ([0.1f32, 0.8f32, 0.1f32, 0.8f32], 1),
+            ([0.15f32, 0.6f32, 0.15f32, 0.6f32], 15),
+            ([0.5f32, 0.4f32, 0.5f32, 0.4f32], 5),
+            ([0.8f32, 0.1f32, 0.8f32, 0.1f32], 8),
+            ([0.11f32, 0.2f32, 0.11f32, 0.2f32], 11),
+        ];
+
+        for (point, item) in content_to_add {
+            tree.add(&point, item);
+        }
+
+        assert_eq!(tree.size(), 16);
+
+        let everything = Ball {
+            centre: [0.5f32; 4],
+            radius: 10.0,
+        };
+        let mut result = tree.within_region(&everything);
+        result.sort();
+        assert_eq!(result, (1..=16).collect::<Vec<_>>());
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let region = Ball {
+                centre: rng.gen::<[AX; 4]>(),
+                radius: rng.gen_range(0f32..0.6f32),
+            };
+            let expected = linear_search(&content_to_add, &region);
+
+            let mut result = tree.within_region(&region);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_region_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<([f32; 4], u32)> = (0..TREE_SIZE)
+            .map(|_| rand::random::<([f32; 4], u32)>())
+            .collect();
+
+        let mut tree: KdTree<AX, u32, 4, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .for_each(|(point, content)| tree.add(point, *content));
+        assert_eq!(tree.size(), TREE_SIZE as u32);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let region = Ball {
+                centre: rng.gen::<[AX; 4]>(),
+                radius: rng.gen_range(0f32..0.6f32),
+            };
+            let expected = linear_search(&content_to_add, &region);
+
+            let mut result = tree.within_region(&region);
+            result.sort();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<R: QueryRegion<AX, 4>>(content: &[([AX; 4], u32)], region: &R) -> Vec<u32> {
+        let mut matching_items: Vec<u32> = content
+            .iter()
+            .filter(|(p, _)| region.contains_point(p))
+            .map(|&(_, item)| item)
+            .collect();
+
+        matching_items.sort();
+
+        matching_items
+    }
+}
@@ -1,5 +1,6 @@
 use az::{Az, Cast};
 use generator::{done, Gn, Scope};
+use num_traits::Zero;
 use std::ops::Rem;
 
 use crate::distance_metric::DistanceMetric;
@@ -1,4 +1,5 @@
 use az::Cast;
+use num_traits::float::FloatCore;
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 
@@ -19,7 +20,7 @@ use super::f64_avx2::get_best_from_dists_f64_avx2;
 
 use super::fallback::get_best_from_dists_autovec;
 
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{DistanceMetric, StatefulDistanceMetric, StatelessMetric};
 use crate::{float::kdtree::Axis, types::Content};
 
 #[doc(hidden)]
@@ -72,21 +73,34 @@ where
         }
     }
 
-    pub fn nearest_one<D>(&self, query: &[A; K], best_dist: &mut A, best_item: &mut T)
+    pub fn nearest_one<D>(&self, query: &[A; K], best_dist: &mut D::Output, best_item: &mut T)
     where
         D: DistanceMetric<A, K>,
+        D::Output: Axis + BestFromDists<T, B>,
     {
-        // AVX512: 4 loops of 32 iterations, each 4x unrolled, 5 instructions per pre-unrolled iteration
-        let mut acc = [A::zero(); B];
-        (0..K).step_by(1).for_each(|dim| {
-            let qd = [query[dim]; B];
-
-            (0..B).step_by(1).for_each(|idx| {
-                acc[idx] += D::dist1(self.content_points[dim][idx], qd[idx]);
-            });
+        self.nearest_one_with_metric(&StatelessMetric::<D>::new(), query, best_dist, best_item)
+    }
+
+    pub fn nearest_one_with_metric<D>(
+        &self,
+        metric: &D,
+        query: &[A; K],
+        best_dist: &mut D::Output,
+        best_item: &mut T,
+    ) where
+        D: StatefulDistanceMetric<A, K>,
+        D::Output: Axis + BestFromDists<T, B>,
+    {
+        // slots past the end of the leaf stay at infinity, so that they never win
+        let mut acc = [D::Output::infinity(); B];
+        (0..self.size).for_each(|idx| {
+            acc[idx] = metric.dist(
+                query,
+                &std::array::from_fn(|dim| self.content_points[dim][idx]),
+            );
         });
 
-        A::get_best_from_dists(acc, &self.content_items, best_dist, best_item);
+        D::Output::get_best_from_dists(acc, &self.content_items, best_dist, best_item);
     }
 }
 
@@ -108,21 +122,34 @@ where
     T: Content + rkyv::Archive<Archived = T>,
     usize: Cast<T>,
 {
-    pub fn nearest_one<D>(&self, query: &[A; K], best_dist: &mut A, best_item: &mut T)
+    pub fn nearest_one<D>(&self, query: &[A; K], best_dist: &mut D::Output, best_item: &mut T)
     where
         D: DistanceMetric<A, K>,
+        D::Output: Axis + BestFromDists<T, B>,
+    {
+        self.nearest_one_with_metric(&StatelessMetric::<D>::new(), query, best_dist, best_item)
+    }
+
+    pub fn nearest_one_with_metric<D>(
+        &self,
+        metric: &D,
+        query: &[A; K],
+        best_dist: &mut D::Output,
+        best_item: &mut T,
+    ) where
+        D: StatefulDistanceMetric<A, K>,
+        D::Output: Axis + BestFromDists<T, B>,
     {
-        // AVX512: 4 loops of 32 iterations, each 4x unrolled, 5 instructions per pre-unrolled iteration
-        let mut acc = [A::zero(); B];
-        (0..K).step_by(1).for_each(|dim| {
-            let qd = [query[dim]; B];
-
-            (0..B).step_by(1).for_each(|idx| {
-                acc[idx] += D::dist1(self.content_points[dim][idx], qd[idx]);
-            });
+        // slots past the end of the leaf stay at infinity, so that they never win
+        let mut acc = [D::Output::infinity(); B];
+        (0..self.size as usize).for_each(|idx| {
+            acc[idx] = metric.dist(
+                query,
+                &std::array::from_fn(|dim| self.content_points[dim][idx]),
+            );
         });
 
-        A::get_best_from_dists(acc, &self.content_items, best_dist, best_item);
+        D::Output::get_best_from_dists(acc, &self.content_items, best_dist, best_item);
     }
 }
 
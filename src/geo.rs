@@ -0,0 +1,576 @@
+//! Helpers for storing and querying points on the surface of a sphere, such as
+//! latitude / longitude positions on the Earth.
+//!
+//! There are two ways of indexing this kind of data with Kiddo:
+//!
+//! * convert each latitude / longitude into 3D cartesian (ECEF) co-ordinates with
+//!   [`lat_lon_to_ecef`], store them in a 3-d tree and query it with
+//!   [`SquaredEuclidean`](crate::SquaredEuclidean). The squared chord distances that
+//!   this returns can be converted to and from great-circle distances with
+//!   [`squared_chord_to_great_circle`] and [`great_circle_to_squared_chord`].
+//!   This is the fastest option, and has no problems at the poles or the antimeridian.
+//! * store `[latitude, longitude]` in degrees directly in a 2-d tree and query it
+//!   with the [`Haversine`] metric, which returns great-circle distances as an angle
+//!   in radians.
+//!
+//! All latitudes and longitudes are in degrees. Great-circle and chord distances
+//! are in the same units as `radius`.
+
+use num_traits::Float;
+
+use crate::distance_metric::DistanceMetric;
+
+/// The mean radius of the Earth, in kilometres
+pub const EARTH_RADIUS_IN_KM: f64 = 6371.0;
+
+/// Converts a `[latitude, longitude]` in degrees into 3D cartesian (Earth-centred,
+/// Earth-fixed) co-ordinates on a sphere of the given `radius`.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::geo::lat_lon_to_ecef;
+///
+/// let [x, y, z] = lat_lon_to_ecef([90f64, 0f64], 1f64);
+///
+/// assert!(x.abs() < 1e-12);
+/// assert!(y.abs() < 1e-12);
+/// assert!((z - 1f64).abs() < 1e-12);
+/// ```
+pub fn lat_lon_to_ecef<A: Float>(lat_lon: [A; 2], radius: A) -> [A; 3] {
+    let lat = lat_lon[0].to_radians();
+    let lon = lat_lon[1].to_radians();
+
+    [
+        radius * lat.cos() * lon.cos(),
+        radius * lat.cos() * lon.sin(),
+        radius * lat.sin(),
+    ]
+}
+
+/// Converts 3D cartesian (Earth-centred, Earth-fixed) co-ordinates back into a
+/// `[latitude, longitude]` in degrees. The distance of the point from the origin
+/// does not affect the result.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::geo::{ecef_to_lat_lon, lat_lon_to_ecef};
+///
+/// let [lat, lon] = ecef_to_lat_lon(lat_lon_to_ecef([51.5f64, -0.1f64], 6371f64));
+///
+/// assert!((lat - 51.5f64).abs() < 1e-9);
+/// assert!((lon + 0.1f64).abs() < 1e-9);
+/// ```
+pub fn ecef_to_lat_lon<A: Float>(xyz: [A; 3]) -> [A; 2] {
+    let [x, y, z] = xyz;
+
+    [z.atan2(x.hypot(y)).to_degrees(), y.atan2(x).to_degrees()]
+}
+
+/// Converts the straight-line (chord) distance between two points on a sphere of
+/// the given `radius` into the great-circle distance between them.
+pub fn chord_to_great_circle<A: Float>(chord: A, radius: A) -> A {
+    let two = A::one() + A::one();
+    let half_angle_sin = (chord / (two * radius)).min(A::one());
+
+    two * radius * half_angle_sin.asin()
+}
+
+/// Converts the great-circle distance between two points on a sphere of the given
+/// `radius` into the straight-line (chord) distance between them.
+pub fn great_circle_to_chord<A: Float>(great_circle: A, radius: A) -> A {
+    let two = A::one() + A::one();
+    let half_angle =
+        (great_circle / (two * radius)).min(A::from(std::f64::consts::FRAC_PI_2).unwrap());
+
+    two * radius * half_angle.sin()
+}
+
+/// Converts a squared chord distance, as returned by
+/// [`SquaredEuclidean`](crate::SquaredEuclidean) on points created with
+/// [`lat_lon_to_ecef`], into the great-circle distance between the points.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::distance_metric::DistanceMetric;
+/// use kiddo::geo::{lat_lon_to_ecef, squared_chord_to_great_circle, EARTH_RADIUS_IN_KM};
+/// use kiddo::SquaredEuclidean;
+///
+/// let a = lat_lon_to_ecef([0f64, 0f64], EARTH_RADIUS_IN_KM);
+/// let b = lat_lon_to_ecef([0f64, 90f64], EARTH_RADIUS_IN_KM);
+///
+/// let km = squared_chord_to_great_circle(SquaredEuclidean::dist(&a, &b), EARTH_RADIUS_IN_KM);
+///
+/// assert!((km - 10007.543f64).abs() < 1e-3);
+/// ```
+pub fn squared_chord_to_great_circle<A: Float>(squared_chord: A, radius: A) -> A {
+    chord_to_great_circle(squared_chord.sqrt(), radius)
+}
+
+/// Converts a great-circle distance into the squared chord distance that
+/// [`SquaredEuclidean`](crate::SquaredEuclidean) would return for two points that
+/// far apart, for use as the `dist` argument of queries on trees of points created
+/// with [`lat_lon_to_ecef`].
+pub fn great_circle_to_squared_chord<A: Float>(great_circle: A, radius: A) -> A {
+    great_circle_to_chord(great_circle, radius).powi(2)
+}
+
+/// Returns the great-circle distance between two `[latitude, longitude]` points in
+/// degrees, as an angle in radians. Multiply by the radius of the sphere (e.g.
+/// [`EARTH_RADIUS_IN_KM`]) to get a distance.
+///
+/// Longitudes can be in either `-180..=180` or `0..360`. Near the poles, points
+/// that are far apart in longitude can still be close together, so the search is
+/// pruned using [`lower_bound_to_box`](DistanceMetric::lower_bound_to_box), which
+/// allows for this and wraps around the antimeridian, rather than a bound on each
+/// axis. This is still slower than querying ECEF co-ordinates with
+/// [`SquaredEuclidean`](crate::SquaredEuclidean).
+///
+/// Queries that need an upper bound on the distance to a node, such as
+/// `count_within`, `farthest_n` and `within_annulus`, use
+/// [`upper_bound_to_box`](DistanceMetric::upper_bound_to_box), and work with both
+/// [`KdTree`](crate::float::kdtree::KdTree) and
+/// [`ImmutableKdTree`](crate::immutable::float::kdtree::ImmutableKdTree).
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::KdTree;
+/// use kiddo::geo::{Haversine, EARTH_RADIUS_IN_KM};
+///
+/// let mut tree: KdTree<f64, 2> = KdTree::new();
+/// tree.add(&[51.5, -0.13], 100); // London
+/// tree.add(&[48.86, 2.35], 101); // Paris
+/// tree.add(&[-16.5, 179.9], 102); // Fiji, just west of the antimeridian
+///
+/// let nearest = tree.nearest_one::<Haversine>(&[-16.5, -179.9]);
+///
+/// assert_eq!(nearest.item, 102);
+/// assert!(nearest.distance * EARTH_RADIUS_IN_KM < 25.0);
+/// ```
+pub struct Haversine {}
+
+impl<A: Float> DistanceMetric<A, 2> for Haversine {
+    type Output = A;
+
+    #[inline]
+    fn dist(a: &[A; 2], b: &[A; 2]) -> A {
+        let two = A::one() + A::one();
+
+        let lat_a = a[0].to_radians();
+        let lat_b = b[0].to_radians();
+        let half_d_lat = (lat_b - lat_a) / two;
+        let half_d_lon = (b[1] - a[1]).to_radians() / two;
+
+        let h = half_d_lat.sin().powi(2) + lat_a.cos() * lat_b.cos() * half_d_lon.sin().powi(2);
+
+        two * h.min(A::one()).sqrt().asin()
+    }
+
+    /// Always zero, since the distance along either axis alone can't bound the
+    /// great-circle distance. See [`dist1_on_axis`](Self::dist1_on_axis).
+    #[inline]
+    fn dist1(_a: A, _b: A) -> A {
+        A::zero()
+    }
+
+    /// Points that are `d` degrees of latitude apart are always at least `d` degrees
+    /// of arc apart. Longitude gives no such bound, since lines of longitude converge
+    /// at the poles.
+    #[inline]
+    fn dist1_on_axis(a: A, b: A, dim: usize) -> A {
+        if dim == 0 {
+            (a - b).abs().to_radians()
+        } else {
+            A::zero()
+        }
+    }
+
+    /// Bounds the distance to a box of latitudes and longitudes by the larger of
+    /// its separation from `query` in latitude, and the distance from `query` to
+    /// the nearest meridian that bounds the box, wrapping around the antimeridian.
+    fn lower_bound_to_box(query: &[A; 2], min: &[A; 2], max: &[A; 2]) -> Option<A> {
+        let full_circle = A::from(360.0).unwrap();
+        let right_angle = A::from(90.0).unwrap();
+
+        let [lat, lon] = *query;
+
+        let d_lat = if lat < min[0] {
+            min[0] - lat
+        } else if lat > max[0] {
+            lat - max[0]
+        } else {
+            A::zero()
+        };
+
+        // how far east of the western edge of the box the query is, and how
+        // far that leaves it from whichever edge is closest
+        let width = max[1] - min[1];
+        let mut east_of_min = (lon - min[1]) % full_circle;
+        if east_of_min < A::zero() {
+            east_of_min = east_of_min + full_circle;
+        }
+        let d_lon = if width >= full_circle || east_of_min <= width {
+            A::zero()
+        } else {
+            (east_of_min - width).min(full_circle - east_of_min)
+        };
+
+        // every point at least `d_lon` away in longitude is at least this far
+        // away, whatever its latitude
+        let lon_bound = if d_lon < right_angle {
+            (lat.to_radians().cos() * d_lon.to_radians().sin()).asin()
+        } else {
+            (right_angle - lat.abs()).to_radians()
+        };
+
+        Some(d_lat.to_radians().max(lon_bound))
+    }
+
+    /// Bounds the distance to a box of latitudes and longitudes by taking the
+    /// largest separation in latitude, the largest separation in longitude
+    /// (wrapping around the antimeridian) and the latitude closest to the equator
+    /// separately, since the haversine grows with each of them.
+    fn upper_bound_to_box(query: &[A; 2], min: &[A; 2], max: &[A; 2]) -> Option<A> {
+        let two = A::one() + A::one();
+        let full_circle = A::from(360.0).unwrap();
+        let half_circle = A::from(180.0).unwrap();
+        let right_angle = A::from(90.0).unwrap();
+
+        let [lat, lon] = *query;
+
+        let min_lat = min[0].max(-right_angle);
+        let max_lat = max[0].min(right_angle);
+        let d_lat = (lat - min_lat).abs().max((lat - max_lat).abs());
+
+        // the box's points are furthest away in longitude at the antipodal meridian
+        // if it is inside the box, or otherwise at whichever edge is closest to it
+        let width = max[1] - min[1];
+        let wrapped = |d: A| {
+            let mut d = d % full_circle;
+            if d < A::zero() {
+                d = d + full_circle;
+            }
+            d.min(full_circle - d)
+        };
+        let mut antipode_east_of_min = (lon + half_circle - min[1]) % full_circle;
+        if antipode_east_of_min < A::zero() {
+            antipode_east_of_min = antipode_east_of_min + full_circle;
+        }
+        let d_lon = if width >= full_circle || antipode_east_of_min <= width {
+            half_circle
+        } else {
+            wrapped(lon - min[1]).max(wrapped(lon - max[1]))
+        };
+
+        let max_cos_lat = if min_lat <= A::zero() && max_lat >= A::zero() {
+            A::one()
+        } else {
+            min_lat.abs().min(max_lat.abs()).to_radians().cos()
+        };
+
+        let h = (d_lat.to_radians() / two).sin().powi(2)
+            + lat.to_radians().cos() * max_cos_lat * (d_lon.to_radians() / two).sin().powi(2);
+
+        Some(two * h.min(A::one()).sqrt().asin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::float::kdtree::KdTree;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    fn random_lat_lon(rng: &mut impl Rng) -> [f64; 2] {
+        [
+            rng.gen_range(-90f64..=90f64),
+            rng.gen_range(-180f64..180f64),
+        ]
+    }
+
+    #[test]
+    fn ecef_conversion_round_trips() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let [lat, lon] = random_lat_lon(&mut rng);
+            let [lat_rt, lon_rt] = ecef_to_lat_lon(lat_lon_to_ecef([lat, lon], EARTH_RADIUS_IN_KM));
+
+            assert!((lat - lat_rt).abs() < 1e-9);
+            // longitude is meaningless at the poles
+            if lat.abs() < 89.9 {
+                assert!((lon - lon_rt).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn chord_conversion_matches_haversine() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let a = random_lat_lon(&mut rng);
+            let b = random_lat_lon(&mut rng);
+
+            let expected = Haversine::dist(&a, &b) * EARTH_RADIUS_IN_KM;
+
+            let a_xyz = lat_lon_to_ecef(a, EARTH_RADIUS_IN_KM);
+            let b_xyz = lat_lon_to_ecef(b, EARTH_RADIUS_IN_KM);
+            let squared_chord: f64 = a_xyz
+                .iter()
+                .zip(b_xyz.iter())
+                .map(|(a, b)| (a - b).powi(2))
+                .sum();
+
+            assert!(
+                (squared_chord_to_great_circle(squared_chord, EARTH_RADIUS_IN_KM) - expected).abs()
+                    < 1e-6
+            );
+            assert!(
+                (great_circle_to_squared_chord(expected, EARTH_RADIUS_IN_KM) - squared_chord).abs()
+                    < 1e-3
+            );
+        }
+    }
+
+    #[test]
+    fn haversine_dist_matches_known_distance() {
+        let london = [51.5074f64, -0.1278f64];
+        let paris = [48.8566f64, 2.3522f64];
+
+        let km = Haversine::dist(&london, &paris) * EARTH_RADIUS_IN_KM;
+
+        assert!((km - 343.5).abs() < 1.0);
+    }
+
+    #[test]
+    fn haversine_lower_bound_to_box_never_exceeds_dist() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..10_000 {
+            let query = random_lat_lon(&mut rng);
+            let corners = [random_lat_lon(&mut rng), random_lat_lon(&mut rng)];
+            let min = [
+                corners[0][0].min(corners[1][0]),
+                corners[0][1].min(corners[1][1]),
+            ];
+            let max = [
+                corners[0][0].max(corners[1][0]),
+                corners[0][1].max(corners[1][1]),
+            ];
+
+            let bound = Haversine::lower_bound_to_box(&query, &min, &max).unwrap();
+
+            for _ in 0..10 {
+                let point = [
+                    rng.gen_range(min[0]..=max[0]),
+                    rng.gen_range(min[1]..=max[1]),
+                ];
+                assert!(bound <= Haversine::dist(&query, &point) + 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn haversine_upper_bound_to_box_never_below_dist() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..10_000 {
+            let query = random_lat_lon(&mut rng);
+            let corners = [random_lat_lon(&mut rng), random_lat_lon(&mut rng)];
+            let min = [
+                corners[0][0].min(corners[1][0]),
+                corners[0][1].min(corners[1][1]),
+            ];
+            let max = [
+                corners[0][0].max(corners[1][0]),
+                corners[0][1].max(corners[1][1]),
+            ];
+
+            let bound = Haversine::upper_bound_to_box(&query, &min, &max).unwrap();
+
+            for _ in 0..10 {
+                let point = [
+                    rng.gen_range(min[0]..=max[0]),
+                    rng.gen_range(min[1]..=max[1]),
+                ];
+                assert!(bound >= Haversine::dist(&query, &point) - 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_n_and_within_with_haversine() {
+        const TREE_SIZE: usize = 10_000;
+        const NUM_QUERIES: usize = 200;
+        const N: usize = 10;
+
+        let mut rng = rand::thread_rng();
+        let content_to_add: Vec<[f64; 2]> =
+            (0..TREE_SIZE).map(|_| random_lat_lon(&mut rng)).collect();
+
+        let mut tree: KdTree<f64, u32, 2, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<f64, u32, 2, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        for i in 0..NUM_QUERIES {
+            // make sure queries near the antimeridian and poles are well covered
+            let query_point = match i % 4 {
+                0 => [rng.gen_range(-90f64..=90f64), rng.gen_range(179f64..180f64)],
+                1 => [rng.gen_range(85f64..=90f64), rng.gen_range(-180f64..180f64)],
+                _ => random_lat_lon(&mut rng),
+            };
+
+            let mut expected: Vec<f64> = content_to_add
+                .iter()
+                .map(|p| Haversine::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let nearest: Vec<_> = tree
+                .nearest_n::<Haversine>(&query_point, N)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            assert_eq!(nearest, expected[..N]);
+
+            let nearest_one = tree.nearest_one::<Haversine>(&query_point);
+            assert_eq!(nearest_one.distance, expected[0]);
+
+            let radius = 0.05;
+            let within: Vec<_> = tree
+                .within::<Haversine>(&query_point, radius)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            let expected_within: Vec<_> =
+                expected.iter().copied().filter(|&d| d < radius).collect();
+            assert_eq!(within, expected_within);
+
+            let nearest: Vec<_> = immutable_tree
+                .nearest_n::<Haversine>(&query_point, N)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            assert_eq!(nearest, expected[..N]);
+
+            let nearest_one = immutable_tree.nearest_one::<Haversine>(&query_point);
+            assert_eq!(nearest_one.distance, expected[0]);
+
+            let within: Vec<_> = immutable_tree
+                .within::<Haversine>(&query_point, radius)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+            assert_eq!(within, expected_within);
+        }
+    }
+
+    #[test]
+    fn can_query_count_within_farthest_n_and_within_annulus_with_haversine() {
+        const TREE_SIZE: usize = 10_000;
+        const NUM_QUERIES: usize = 200;
+        const N: usize = 10;
+
+        let mut rng = rand::thread_rng();
+        let content_to_add: Vec<[f64; 2]> =
+            (0..TREE_SIZE).map(|_| random_lat_lon(&mut rng)).collect();
+
+        let mut tree: KdTree<f64, u32, 2, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<f64, u32, 2, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        let (min_dist, max_dist) = (0.5, 0.6);
+
+        for i in 0..NUM_QUERIES {
+            let query_point = match i % 4 {
+                0 => [rng.gen_range(-90f64..=90f64), rng.gen_range(179f64..180f64)],
+                1 => [rng.gen_range(85f64..=90f64), rng.gen_range(-180f64..180f64)],
+                _ => random_lat_lon(&mut rng),
+            };
+
+            let mut expected: Vec<f64> = content_to_add
+                .iter()
+                .map(|p| Haversine::dist(&query_point, p))
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let expected_count = expected.iter().filter(|&&d| d < max_dist).count();
+            let expected_annulus: Vec<_> = expected
+                .iter()
+                .copied()
+                .filter(|&d| d >= min_dist && d <= max_dist)
+                .collect();
+            let expected_farthest: Vec<_> = expected.iter().rev().take(N).copied().collect();
+
+            let dists = |results: Vec<crate::NearestNeighbour<f64, u32>>| -> Vec<f64> {
+                results.into_iter().map(|n| n.distance).collect()
+            };
+
+            assert_eq!(
+                tree.count_within::<Haversine>(&query_point, max_dist),
+                expected_count
+            );
+            assert_eq!(
+                dists(tree.farthest_n::<Haversine>(&query_point, N)),
+                expected_farthest
+            );
+            assert_eq!(
+                dists(tree.within_annulus::<Haversine>(&query_point, min_dist, max_dist)),
+                expected_annulus
+            );
+            assert_eq!(
+                immutable_tree.count_within::<Haversine>(&query_point, max_dist),
+                expected_count
+            );
+            assert_eq!(
+                dists(immutable_tree.farthest_n::<Haversine>(&query_point, N)),
+                expected_farthest
+            );
+            assert_eq!(
+                dists(immutable_tree.within_annulus::<Haversine>(&query_point, min_dist, max_dist)),
+                expected_annulus
+            );
+        }
+    }
+
+    #[test]
+    fn can_query_any_within_with_haversine() {
+        const TREE_SIZE: usize = 10_000;
+        const NUM_QUERIES: usize = 300;
+
+        let mut rng = rand::thread_rng();
+        let content_to_add: Vec<[f64; 2]> =
+            (0..TREE_SIZE).map(|_| random_lat_lon(&mut rng)).collect();
+
+        let mut tree: KdTree<f64, u32, 2, 32, u32> = KdTree::with_capacity(TREE_SIZE);
+        content_to_add
+            .iter()
+            .enumerate()
+            .for_each(|(idx, point)| tree.add(point, idx as u32));
+        let immutable_tree: ImmutableKdTree<f64, u32, 2, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = random_lat_lon(&mut rng);
+            let radius = rng.gen_range(0f64..0.05f64);
+
+            let expected = content_to_add
+                .iter()
+                .any(|p| Haversine::dist(&query_point, p) < radius);
+
+            assert_eq!(tree.any_within::<Haversine>(&query_point, radius), expected);
+            assert_eq!(
+                immutable_tree.any_within::<Haversine>(&query_point, radius),
+                expected
+            );
+        }
+    }
+}
@@ -8,18 +8,22 @@ macro_rules! generate_immutable_best_n_within {
             pub fn best_n_within<D>(
                 &self,
                 query: &[A; K],
-                dist: A,
+                dist: D::Output,
                 max_qty: usize,
-            ) -> impl Iterator<Item = BestNeighbour<A, T>>
+            ) -> impl Iterator<Item = BestNeighbour<D::Output, T>>
             where
                 A: BestFromDists<T, B>,
                 usize: Cast<T>,
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 let mut off = [A::zero(); K];
-                let mut best_items: BinaryHeap<BestNeighbour<A, T>> = BinaryHeap::new();
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut best_items: BinaryHeap<BestNeighbour<D::Output, T>> = BinaryHeap::new();
 
-                self.best_n_within_recurse::<D>(
+                self.best_n_within_recurse(
+                    &StatelessMetric::<D>::new(),
                     query,
                     dist,
                     max_qty,
@@ -27,57 +31,57 @@ macro_rules! generate_immutable_best_n_within {
                     0,
                     &mut best_items,
                     &mut off,
-                    A::zero(),
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
                 );
 
                 best_items.into_iter()
             }
 
             #[allow(clippy::too_many_arguments)]
-            fn best_n_within_recurse<D>(
+            pub(crate) fn best_n_within_recurse<D, N>(
                 &self,
+                metric: &D,
                 query: &[A; K],
-                radius: A,
+                radius: D::Output,
                 max_qty: usize,
                 stem_idx: usize,
                 split_dim: usize,
-                best_items: &mut BinaryHeap<BestNeighbour<A, T>>,
+                best_items: &mut BinaryHeap<N>,
                 off: &mut [A; K],
-                rd: A,
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
             ) where
                 A: BestFromDists<T, B>,
                 usize: Cast<T>,
-                D: DistanceMetric<A, K>,
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+                N: FromLeafEntry<A, T, K, D::Output>,
             {
                 if stem_idx >= self.stems.len() {
-                    let leaf_node = &self.leaves[stem_idx - self.stems.len()];
-
-                    let mut acc = [A::zero(); B];
-                    (0..K).step_by(1).for_each(|dim| {
-                        let qd = [query[dim]; B];
-
-                        (0..leaf_node.size as usize).step_by(1).for_each(|idx| {
-                            acc[idx] += D::dist1(leaf_node.content_points[dim][idx], qd[idx]);
-                        });
-                    });
-
-                    acc
-                        .iter()
-                        .enumerate()
-                        .take(leaf_node.size as usize)
-                        .filter(|(_, &distance)| distance <= radius)
-                        .for_each(|(idx, &distance)| {
-                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
-                            if best_items.len() < max_qty {
-                                best_items.push(BestNeighbour { distance, item });
-                            } else {
-                                let mut top = best_items.peek_mut().unwrap();
-                                if item < top.item {
-                                    top.item = item;
-                                    top.distance = distance;
-                                }
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point: [A; K] = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        let distance = metric.dist(query, &point);
+                        if distance > radius {
+                            return;
+                        }
+
+                        let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                        if best_items.len() < max_qty {
+                            best_items.push(N::from_leaf_entry(distance, item, &point));
+                        } else {
+                            let mut top = best_items.peek_mut().unwrap();
+                            if item < top.item() {
+                                *top = N::from_leaf_entry(distance, item, &point);
                             }
-                        });
+                        }
+                    });
 
                     return;
                 }
@@ -102,7 +106,8 @@ macro_rules! generate_immutable_best_n_within {
 
                 let next_split_dim = (split_dim + 1).rem(K);
 
-                self.best_n_within_recurse::<D>(
+                self.best_n_within_recurse::<D, N>(
+                    metric,
                     query,
                     radius,
                     max_qty,
@@ -111,13 +116,26 @@ macro_rules! generate_immutable_best_n_within {
                     best_items,
                     off,
                     rd,
+                    min,
+                    max,
                 );
 
-                rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = metric.lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, metric.dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
 
                 if rd <= radius {
                     off[split_dim] = new_off;
-                    self.best_n_within_recurse::<D>(
+                    self.best_n_within_recurse::<D, N>(
+                        metric,
                         query,
                         radius,
                         max_qty,
@@ -126,9 +144,13 @@ macro_rules! generate_immutable_best_n_within {
                         best_items,
                         off,
                         rd,
+                        min,
+                        max,
                     );
                     off[split_dim] = old_off;
                 }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
             }
         }
     };
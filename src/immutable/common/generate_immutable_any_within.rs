@@ -0,0 +1,107 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_any_within {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn any_within<D>(&self, query: &[A; K], dist: D::Output) -> bool
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                self.any_within_recurse::<D>(query, dist, 1, 0, &mut off, D::Output::zero(), &mut min, &mut max)
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn any_within_recurse<D>(
+                &self,
+                query: &[A; K],
+                radius: D::Output,
+                stem_idx: usize,
+                split_dim: usize,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) -> bool
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return false;
+                    };
+
+                    return (0..leaf_node.size as usize).any(|idx| {
+                        let point = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+
+                        D::dist(query, &point) < radius
+                    });
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let mut rd = rd;
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+
+                let is_left_child = usize::from(*unsafe { query.get_unchecked(split_dim) } < val);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                if self.any_within_recurse::<D>(query, radius, closer_node_idx, next_split_dim, off, rd, min, max) {
+                    return true;
+                }
+
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
+
+                if rd <= radius {
+                    off[split_dim] = new_off;
+                    let found = self.any_within_recurse::<D>(
+                        query,
+                        radius,
+                        further_node_idx,
+                        next_split_dim,
+                        off,
+                        rd,
+                        min,
+                        max,
+                    );
+                    off[split_dim] = old_off;
+                    min[split_dim] = old_min;
+                    max[split_dim] = old_max;
+
+                    return found;
+                }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+
+                false
+            }
+        }
+    };
+}
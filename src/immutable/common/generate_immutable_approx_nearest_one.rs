@@ -5,16 +5,17 @@ macro_rules! generate_immutable_approx_nearest_one {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn approx_nearest_one<D>(&self, query: &[A; K]) -> NearestNeighbour<A, T>
+            pub fn approx_nearest_one<D>(&self, query: &[A; K]) -> NearestNeighbour<D::Output, T>
             where
                 A: BestFromDists<T, B>,
                 D: DistanceMetric<A, K>,
+                D::Output: Axis + BestFromDists<T, B>,
                 usize: Cast<T>,
             {
                 let mut split_dim = 0;
                 let mut stem_idx = 1;
                 let mut best_item = T::zero();
-                let mut best_dist = A::max_value();
+                let mut best_dist = D::Output::max_value();
 
                 let stem_len = self.stems.len();
 
@@ -0,0 +1,170 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_best_n_within_by {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn best_n_within_by<D, F, S>(
+                &self,
+                query: &[A; K],
+                dist: D::Output,
+                max_qty: usize,
+                score: F,
+            ) -> impl Iterator<Item = BestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                F: Fn(T, D::Output) -> S,
+                S: Ord,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut best_items: BinaryHeap<ScoredNeighbour<S, BestNeighbour<D::Output, T>>> = BinaryHeap::new();
+
+                if max_qty > 0 {
+                    self.best_n_within_by_recurse::<D, F, S>(
+                        query,
+                        dist,
+                        max_qty,
+                        1,
+                        0,
+                        &mut best_items,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &score,
+                    );
+                }
+
+                best_items
+                    .into_sorted_vec()
+                    .into_iter()
+                    .map(|scored| scored.neighbour)
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn best_n_within_by_recurse<D, F, S>(
+                &self,
+                query: &[A; K],
+                radius: D::Output,
+                max_qty: usize,
+                stem_idx: usize,
+                split_dim: usize,
+                best_items: &mut BinaryHeap<ScoredNeighbour<S, BestNeighbour<D::Output, T>>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                score: &F,
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                F: Fn(T, D::Output) -> S,
+                S: Ord,
+            {
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    let mut acc = [D::Output::zero(); B];
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point: [A; K] = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        acc[idx] = D::dist(query, &point);
+                    });
+
+                    acc
+                        .iter()
+                        .enumerate()
+                        .take(leaf_node.size as usize)
+                        .filter(|(_, &distance)| distance <= radius)
+                        .for_each(|(idx, &distance)| {
+                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                            let element = ScoredNeighbour {
+                                score: score(item, distance),
+                                neighbour: BestNeighbour { distance, item },
+                            };
+
+                            if best_items.len() < max_qty {
+                                best_items.push(element);
+                            } else {
+                                let mut top = best_items.peek_mut().unwrap();
+                                if element.score < top.score {
+                                    *top = element;
+                                }
+                            }
+                        });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let mut rd = rd;
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+
+                let is_left_child = usize::from(*unsafe { query.get_unchecked(split_dim) } < val);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                self.best_n_within_by_recurse::<D, F, S>(
+                    query,
+                    radius,
+                    max_qty,
+                    closer_node_idx,
+                    next_split_dim,
+                    best_items,
+                    off,
+                    rd,
+                    min,
+                    max,
+                    score,
+                );
+
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
+
+                if rd <= radius {
+                    off[split_dim] = new_off;
+                    self.best_n_within_by_recurse::<D, F, S>(
+                        query,
+                        radius,
+                        max_qty,
+                        further_node_idx,
+                        next_split_dim,
+                        best_items,
+                        off,
+                        rd,
+                        min,
+                        max,
+                        score,
+                    );
+                    off[split_dim] = old_off;
+                }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+            }
+        }
+    };
+}
@@ -0,0 +1,148 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_farthest_n {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn farthest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut result: BinaryHeap<Reverse<NearestNeighbour<D::Output, T>>> =
+                    BinaryHeap::with_capacity(qty);
+
+                if qty > 0 {
+                    self.farthest_n_recurse::<D>(query, qty, 1, 0, &mut result, &mut min, &mut max);
+                }
+
+                result.into_sorted_vec().into_iter().map(|Reverse(nn)| nn).collect()
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn farthest_n_recurse<D>(
+                &self,
+                query: &[A; K],
+                qty: usize,
+                stem_idx: usize,
+                split_dim: usize,
+                results: &mut BinaryHeap<Reverse<NearestNeighbour<D::Output, T>>>,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if results.len() >= qty {
+                    let upper_bound = Self::farthest_n_max_dist_to_box::<D>(query, min, max);
+                    if upper_bound <= results.peek().unwrap().0.distance {
+                        return;
+                    }
+                }
+
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        let distance = D::dist(query, &point);
+                        let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                        let element = NearestNeighbour { distance, item };
+
+                        if results.len() < qty {
+                            results.push(Reverse(element));
+                        } else {
+                            let mut top = results.peek_mut().unwrap();
+                            if element.distance > top.0.distance {
+                                *top = Reverse(element);
+                            }
+                        }
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+                let next_split_dim = (split_dim + 1).rem(K);
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+
+                // unused stems have an infinite split value, which must not
+                // push the lower bound of the box past its upper bound
+                let right_min = if val < old_max { val } else { old_max };
+
+                // the child on the opposite side of the split from the query
+                // is more likely to hold the farthest points, so visit it first
+                if *unsafe { query.get_unchecked(split_dim) } < val {
+                    if val > old_min {
+                        min[split_dim] = right_min;
+                    }
+                    self.farthest_n_recurse::<D>(query, qty, left_child_idx + 1, next_split_dim, results, min, max);
+                    min[split_dim] = old_min;
+
+                    if val < old_max {
+                        max[split_dim] = val;
+                    }
+                    self.farthest_n_recurse::<D>(query, qty, left_child_idx, next_split_dim, results, min, max);
+                    max[split_dim] = old_max;
+                } else {
+                    if val < old_max {
+                        max[split_dim] = val;
+                    }
+                    self.farthest_n_recurse::<D>(query, qty, left_child_idx, next_split_dim, results, min, max);
+                    max[split_dim] = old_max;
+
+                    if val > old_min {
+                        min[split_dim] = right_min;
+                    }
+                    self.farthest_n_recurse::<D>(query, qty, left_child_idx + 1, next_split_dim, results, min, max);
+                    min[split_dim] = old_min;
+                }
+            }
+
+            /// Returns an upper bound on the distance from `query` to any point inside
+            /// the box bounded by `min` and `max`, or the maximum value of `A` if any
+            /// side of the box has not yet been bounded by a split.
+            fn farthest_n_max_dist_to_box<D>(query: &[A; K], min: &[A; K], max: &[A; K]) -> D::Output
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if let Some(max_dist) = D::upper_bound_to_box(query, min, max) {
+                    return max_dist;
+                }
+                // a metric that bounds boxes itself isn't necessarily built from its per-axis
+                // distances, so they can't be relied on to bound it from above either
+                if D::lower_bound_to_box(query, min, max).is_some() {
+                    return D::Output::max_value();
+                }
+
+                let mut max_dist = D::Output::zero();
+
+                for dim in 0..K {
+                    if min[dim] == A::min_value() || max[dim] == A::max_value() {
+                        return D::Output::max_value();
+                    }
+
+                    let to_min = query[dim].saturating_dist(min[dim]);
+                    let to_max = query[dim].saturating_dist(max[dim]);
+                    let furthest = if to_min > to_max { to_min } else { to_max };
+
+                    max_dist = Axis::rd_accumulate(max_dist, D::dist1(furthest, A::zero()), D::ACCUMULATION);
+                }
+
+                max_dist
+            }
+        }
+    };
+}
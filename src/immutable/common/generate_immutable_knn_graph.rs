@@ -0,0 +1,181 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_knn_graph {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            pub fn knn_graph<D>(&self, k: usize, exclude_self: bool) -> KnnGraph<D::Output, T>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut rows = Vec::with_capacity(self.size as usize);
+
+                for (leaf_idx, leaf_node) in self.leaves.iter().enumerate() {
+                    let leaf_size = leaf_node.size as usize;
+
+                    for point_idx in 0..leaf_size {
+                        let query: [A; K] =
+                            std::array::from_fn(|dim| leaf_node.content_points[dim][point_idx]);
+                        let mut results: BinaryHeap<NearestNeighbour<D::Output, T>> =
+                            BinaryHeap::with_capacity(k);
+
+                        if k > 0 {
+                            // every other point in the same leaf is a likely neighbour, so checking
+                            // them first gives a tight bound with which to prune the rest of the tree
+                            for other_idx in 0..leaf_size {
+                                if exclude_self && other_idx == point_idx {
+                                    continue;
+                                }
+                                let distance = D::dist(
+                                    &query,
+                                    &std::array::from_fn(|dim| leaf_node.content_points[dim][other_idx]),
+                                );
+                                let item = leaf_node.content_items[other_idx];
+                                Self::knn_graph_add_to_heap(k, NearestNeighbour { distance, item }, &mut results);
+                            }
+
+                            let mut off = [A::zero(); K];
+                            let mut min = [A::min_value(); K];
+                            let mut max = [A::max_value(); K];
+                            self.knn_graph_recurse::<D>(
+                                &query,
+                                k,
+                                leaf_idx,
+                                1,
+                                0,
+                                &mut results,
+                                &mut off,
+                                D::Output::zero(),
+                                &mut min,
+                                &mut max,
+                            );
+                        }
+
+                        rows.push((leaf_node.content_items[point_idx], results.into_sorted_vec()));
+                    }
+                }
+
+                KnnGraph::from_rows(rows)
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn knn_graph_recurse<D>(
+                &self,
+                query: &[A; K],
+                k: usize,
+                home_leaf_idx: usize,
+                stem_idx: usize,
+                split_dim: usize,
+                results: &mut BinaryHeap<NearestNeighbour<D::Output, T>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if stem_idx >= self.stems.len() {
+                    // the contents of the home leaf have already been checked
+                    if stem_idx - self.stems.len() == home_leaf_idx {
+                        return;
+                    }
+
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let distance = D::dist(
+                            query,
+                            &std::array::from_fn(|dim| leaf_node.content_points[dim][idx]),
+                        );
+                        let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                        Self::knn_graph_add_to_heap(k, NearestNeighbour { distance, item }, results);
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let mut rd = rd;
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+
+                let is_left_child = usize::from(*unsafe { query.get_unchecked(split_dim) } < val);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                self.knn_graph_recurse::<D>(
+                    query,
+                    k,
+                    home_leaf_idx,
+                    closer_node_idx,
+                    next_split_dim,
+                    results,
+                    off,
+                    rd,
+                    min,
+                    max,
+                );
+
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
+
+                if results.len() < k || rd < results.peek().unwrap().distance {
+                    off[split_dim] = new_off;
+                    self.knn_graph_recurse::<D>(
+                        query,
+                        k,
+                        home_leaf_idx,
+                        further_node_idx,
+                        next_split_dim,
+                        results,
+                        off,
+                        rd,
+                        min,
+                        max,
+                    );
+                    off[split_dim] = old_off;
+                }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+            }
+
+            #[inline]
+            fn knn_graph_add_to_heap<O: PartialOrd>(
+                k: usize,
+                element: NearestNeighbour<O, T>,
+                results: &mut BinaryHeap<NearestNeighbour<O, T>>,
+            ) {
+                if results.len() < k {
+                    results.push(element)
+                } else {
+                    let mut top = results.peek_mut().unwrap();
+                    if element.distance < top.distance {
+                        *top = element;
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,150 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_nearest_iter {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_iter<D>(&'a self, query: &'a [A; K]) -> NearestIter<'a, D::Output, T>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let gen = Gn::new_scoped(move |mut gen_scope| {
+                    let mut candidates: BinaryHeap<NearestIterCandidate<A, T, usize, K, D::Output>> =
+                        BinaryHeap::new();
+
+                    candidates.push(NearestIterCandidate {
+                        distance: D::Output::zero(),
+                        kind: NearestIterCandidateKind::Node {
+                            node_idx: 1,
+                            split_dim: 0,
+                            off: [A::zero(); K],
+                            min: [A::min_value(); K],
+                            max: [A::max_value(); K],
+                        },
+                    });
+
+                    while let Some(candidate) = candidates.pop() {
+                        match candidate.kind {
+                            NearestIterCandidateKind::Item(item) => {
+                                gen_scope.yield_(NearestNeighbour {
+                                    distance: candidate.distance,
+                                    item,
+                                });
+                            }
+                            NearestIterCandidateKind::Node {
+                                node_idx,
+                                split_dim,
+                                off,
+                                min,
+                                max,
+                            } => {
+                                self.nearest_iter_expand::<D>(
+                                    query,
+                                    node_idx,
+                                    split_dim,
+                                    off,
+                                    candidate.distance,
+                                    min,
+                                    max,
+                                    &mut candidates,
+                                );
+                            }
+                        }
+                    }
+
+                    done!();
+                });
+
+                NearestIter::new(gen)
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn nearest_iter_expand<D>(
+                &self,
+                query: &[A; K],
+                stem_idx: usize,
+                split_dim: usize,
+                off: [A; K],
+                rd: D::Output,
+                min: [A; K],
+                max: [A; K],
+                candidates: &mut BinaryHeap<NearestIterCandidate<A, T, usize, K, D::Output>>,
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+
+                        candidates.push(NearestIterCandidate {
+                            distance: D::dist(query, &point),
+                            kind: NearestIterCandidateKind::Item(
+                                *unsafe { leaf_node.content_items.get_unchecked(idx) },
+                            ),
+                        });
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+
+                let is_left_child = usize::from(*unsafe { query.get_unchecked(split_dim) } < val);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                candidates.push(NearestIterCandidate {
+                    distance: rd,
+                    kind: NearestIterCandidateKind::Node {
+                        node_idx: closer_node_idx,
+                        split_dim: next_split_dim,
+                        off,
+                        min,
+                        max,
+                    },
+                });
+
+                let mut further_off = off;
+                further_off[split_dim] = new_off;
+                let mut further_min = min;
+                let mut further_max = max;
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    further_min[split_dim] = if val < max[split_dim] { val } else { max[split_dim] };
+                } else {
+                    further_max[split_dim] = val;
+                }
+                candidates.push(NearestIterCandidate {
+                    distance: D::lower_bound_to_box(query, &further_min, &further_max).unwrap_or_else(|| {
+                        Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                    }),
+                    kind: NearestIterCandidateKind::Node {
+                        node_idx: further_node_idx,
+                        split_dim: next_split_dim,
+                        off: further_off,
+                        min: further_min,
+                        max: further_max,
+                    },
+                });
+            }
+        }
+    };
+}
@@ -5,13 +5,14 @@ macro_rules! generate_immutable_nearest_n {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn nearest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<A, T>>
+            pub fn nearest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 A: BestFromDists<T, B>,
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
                 usize: Cast<T>,
             {
-                self.nearest_n_within::<D>(query, A::infinity(), qty, true)
+                self.nearest_n_within::<D>(query, D::Output::infinity(), qty, true)
             }
         }
     };
@@ -24,12 +25,13 @@ macro_rules! generate_immutable_nearest_n {
     doc_comment! {
     concat!$comments,
     #[inline]
-    pub fn nearest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<A, T>>
+    pub fn nearest_n<D>(&self, query: &[A; K], qty: usize) -> Vec<NearestNeighbour<D::Output, T>>
     where
         D: DistanceMetric<A, K>,
+        D::Output: Axis,
     {
         let mut off = [A::zero(); K];
-        let mut result: BinaryHeap<NearestNeighbour<A, T>> = BinaryHeap::with_capacity(qty);
+        let mut result: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(qty);
 
         self.nearest_n_recurse::<D>(
             query,
@@ -37,7 +39,7 @@ macro_rules! generate_immutable_nearest_n {
             0,
             &mut result,
             &mut off,
-            A::zero(),
+            D::Output::zero(),
         );
 
         result.into_sorted_vec()
@@ -49,11 +51,12 @@ macro_rules! generate_immutable_nearest_n {
         query: &[A; K],
         stem_idx: usize,
         split_dim: usize,
-        results: &mut BinaryHeap<NearestNeighbour<A, T>>,
+        results: &mut BinaryHeap<NearestNeighbour<D::Output, T>>,
         off: &mut [A; K],
-        rd: A,
+        rd: D::Output,
     ) where
         D: DistanceMetric<A, K>,
+        D::Output: Axis,
     {
         if stem_idx >= self.stems.len() {
             let leaf_node = &self.leaves[stem_idx - self.stems.len()];
@@ -64,7 +67,7 @@ macro_rules! generate_immutable_nearest_n {
                 .take(leaf_node.size as usize)
                 .enumerate()
                 .for_each(|(idx, entry)| {
-                    let distance: A = D::dist(query, entry);
+                    let distance: D::Output = D::dist(query, entry);
                     if Self::dist_belongs_in_heap(distance, results) {
                         let item = unsafe { *leaf_node.content_items.get_unchecked(idx) };
                         let element = NearestNeighbour { distance, item };
@@ -104,7 +107,7 @@ macro_rules! generate_immutable_nearest_n {
 
         self.nearest_n_recurse::<D>(query, closer_node_idx, next_split_dim, results, off, rd);
 
-        rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+        rd = Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION);
 
         if Self::dist_belongs_in_heap(rd, results) {
             off[split_dim] = new_off;
@@ -113,7 +116,7 @@ macro_rules! generate_immutable_nearest_n {
         }
     }
 
-    fn dist_belongs_in_heap(dist: A, heap: &BinaryHeap<NearestNeighbour<A, T>>) -> bool {
+    fn dist_belongs_in_heap<O: PartialOrd>(dist: O, heap: &BinaryHeap<NearestNeighbour<O, T>>) -> bool {
         heap.is_empty() || dist < heap.peek().unwrap().distance || heap.len() < heap.capacity()
     }
 }}}
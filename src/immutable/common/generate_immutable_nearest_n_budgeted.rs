@@ -0,0 +1,165 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_nearest_n_budgeted {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_budgeted<D>(
+                &self,
+                query: &[A; K],
+                qty: usize,
+                budget: SearchBudget,
+            ) -> BudgetedResult<Vec<NearestNeighbour<D::Output, T>>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut budget = BudgetTracker::new(budget);
+                if qty == 0 {
+                    return budget.into_result(Vec::new());
+                }
+
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut result: BinaryHeap<NearestNeighbour<D::Output, T>> = BinaryHeap::with_capacity(qty);
+
+                self.nearest_n_budgeted_recurse::<D>(
+                    query,
+                    qty,
+                    1,
+                    0,
+                    &mut result,
+                    &mut off,
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                    &mut budget,
+                );
+
+                budget.into_result(result.into_sorted_vec())
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn nearest_n_budgeted_recurse<D>(
+                &self,
+                query: &[A; K],
+                qty: usize,
+                stem_idx: usize,
+                split_dim: usize,
+                results: &mut BinaryHeap<NearestNeighbour<D::Output, T>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                budget: &mut BudgetTracker,
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    if !budget.visit_leaf() {
+                        return;
+                    }
+
+                    let mut acc = [D::Output::zero(); B];
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point: [A; K] = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        acc[idx] = D::dist(query, &point);
+                    });
+
+                    acc
+                        .iter()
+                        .enumerate()
+                        .take(leaf_node.size as usize)
+                        .for_each(|(idx, &distance)| {
+                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                            let element = NearestNeighbour { distance, item };
+
+                            if results.len() < qty {
+                                results.push(element)
+                            } else {
+                                let mut top = results.peek_mut().unwrap();
+                                if element.distance < top.distance {
+                                    *top = element;
+                                }
+                            }
+                        });
+
+                    return;
+                }
+
+                if !budget.visit_stem() {
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let mut rd = rd;
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+
+                let is_left_child = usize::from(*unsafe { query.get_unchecked(split_dim) } < val);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                self.nearest_n_budgeted_recurse::<D>(
+                    query,
+                    qty,
+                    closer_node_idx,
+                    next_split_dim,
+                    results,
+                    off,
+                    rd,
+                    min,
+                    max,
+                    budget,
+                );
+
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
+
+                if results.len() < qty || rd < results.peek().unwrap().distance {
+                    off[split_dim] = new_off;
+                    self.nearest_n_budgeted_recurse::<D>(
+                        query,
+                        qty,
+                        further_node_idx,
+                        next_split_dim,
+                        results,
+                        off,
+                        rd,
+                        min,
+                        max,
+                        budget,
+                    );
+                    off[split_dim] = old_off;
+                }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+            }
+        }
+    };
+}
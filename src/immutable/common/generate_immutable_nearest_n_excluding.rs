@@ -0,0 +1,19 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_nearest_n_excluding {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_excluding<D>(&self, query: &[A; K], qty: usize, exclude: &[T]) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                A: BestFromDists<T, B>,
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                usize: Cast<T>,
+            {
+                self.nearest_n_filtered::<D, _>(query, qty, |item| !exclude.contains(&item))
+            }
+        }
+    };
+}
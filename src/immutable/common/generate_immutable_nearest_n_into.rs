@@ -0,0 +1,43 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_nearest_n_into {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_n_into<D>(&self, query: &[A; K], qty: usize, results: &mut Vec<NearestNeighbour<D::Output, T>>)
+            where
+                A: BestFromDists<T, B>,
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                usize: Cast<T>,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+
+                // reuse the caller's allocation as the backing storage of the heap
+                results.clear();
+                let mut matching_items = BoundedHeap::from_vec(std::mem::take(results), qty);
+
+                if qty > 0 {
+                    self.nearest_n_within_recurse(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        D::Output::infinity(),
+                        1,
+                        0,
+                        &mut matching_items,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                        &|_| true,
+                    );
+                }
+
+                *results = matching_items.into_sorted_vec();
+            }
+        }
+    };
+}
@@ -5,35 +5,62 @@ macro_rules! generate_immutable_nearest_n_within {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn nearest_n_within<D>(&self, query: &[A; K], dist: A, max_items: usize, sorted: bool) -> Vec<NearestNeighbour<A, T>>
+            pub fn nearest_n_within<D>(&self, query: &[A; K], dist: D::Output, max_items: usize, sorted: bool) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                self.nearest_n_within_with_metric_stub(&StatelessMetric::<D>::new(), query, dist, max_items, sorted)
+            }
+
+            /// Shared by [`nearest_n_within`](Self::nearest_n_within) and the
+            /// `_with_metric` and `_with_point` queries that delegate to it.
+            pub(crate) fn nearest_n_within_with_metric_stub<D, N>(
+                &self, metric: &D, query: &[A; K], dist: D::Output, max_items: usize, sorted: bool
+            ) -> Vec<N>
+            where
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+                N: FromLeafEntry<A, T, K, D::Output>,
             {
                 if sorted && max_items < usize::MAX {
                     if max_items <= MAX_VEC_RESULT_SIZE {
-                        self.nearest_n_within_stub::<D, SortedVec<NearestNeighbour<A, T>>>(query, dist, max_items, sorted)
+                        self.nearest_n_within_stub::<D, N, SortedVec<N>, _>(metric, query, dist, max_items, sorted, &|_| true)
                     } else {
-                        self.nearest_n_within_stub::<D, BinaryHeap<NearestNeighbour<A, T>>>(query, dist, max_items, sorted)
+                        self.nearest_n_within_stub::<D, N, BinaryHeap<N>, _>(metric, query, dist, max_items, sorted, &|_| true)
                     }
                 } else {
-                    self.nearest_n_within_stub::<D, Vec<NearestNeighbour<A,T>>>(query, dist, 0, sorted)
+                    self.nearest_n_within_stub::<D, N, Vec<N>, _>(metric, query, dist, 0, sorted, &|_| true)
                 }
             }
 
-            fn nearest_n_within_stub<D: DistanceMetric<A, K>, H: ResultCollection<A, T>>(
-                &self, query: &[A; K], dist: A, res_capacity: usize, sorted: bool
-            ) -> Vec<NearestNeighbour<A, T>> {
+            pub(crate) fn nearest_n_within_stub<D, N, H, F>(
+                &self, metric: &D, query: &[A; K], dist: D::Output, res_capacity: usize, sorted: bool, filter: &F
+            ) -> Vec<N>
+            where
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+                N: FromLeafEntry<A, T, K, D::Output>,
+                H: ResultCollection<D::Output, T, N>,
+                F: Fn(T) -> bool,
+            {
                 let mut matching_items = H::new_with_capacity(res_capacity);
                 let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
 
-                self.nearest_n_within_recurse::<D, H>(
+                self.nearest_n_within_recurse::<D, N, H, F>(
+                    metric,
                     query,
                     dist,
                     1,
                     0,
                     &mut matching_items,
                     &mut off,
-                    A::zero(),
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                    filter,
                 );
 
                 if sorted {
@@ -44,43 +71,42 @@ macro_rules! generate_immutable_nearest_n_within {
             }
 
             #[allow(clippy::too_many_arguments)]
-            fn nearest_n_within_recurse<D, R: ResultCollection<A, T>>(
+            pub(crate) fn nearest_n_within_recurse<D, N, R, F>(
                 &self,
+                metric: &D,
                 query: &[A; K],
-                radius: A,
+                radius: D::Output,
                 stem_idx: usize,
                 split_dim: usize,
                 matching_items: &mut R,
                 off: &mut [A; K],
-                rd: A,
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                filter: &F,
             ) where
-                D: DistanceMetric<A, K>,
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+                N: FromLeafEntry<A, T, K, D::Output>,
+                R: ResultCollection<D::Output, T, N>,
+                F: Fn(T) -> bool,
             {
                 if stem_idx >= self.stems.len() {
-                    let leaf_node = &self.leaves[stem_idx - self.stems.len()];
-
-                    let mut acc = [A::zero(); B];
-                    (0..K).step_by(1).for_each(|dim| {
-                        let qd = [query[dim]; B];
-
-                        (0..leaf_node.size as usize).step_by(1).for_each(|idx| {
-                            acc[idx] += D::dist1(leaf_node.content_points[dim][idx], qd[idx]);
-                        });
-                    });
-
-                    acc
-                        .iter()
-                        .enumerate()
-                        .take(leaf_node.size as usize)
-                        .for_each(|(idx, &distance)| {
-
-                            if distance < radius {
-                                matching_items.add(NearestNeighbour {
-                                    distance,
-                                    item: *unsafe { leaf_node.content_items.get_unchecked(idx) },
-                                });
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point: [A; K] = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        let distance = metric.dist(query, &point);
+
+                        if distance < radius {
+                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                            if filter(item) {
+                                matching_items.add(N::from_leaf_entry(distance, item, &point));
                             }
-                        });
+                        }
+                    });
 
                     return;
                 }
@@ -105,7 +131,8 @@ macro_rules! generate_immutable_nearest_n_within {
 
                 let next_split_dim = (split_dim + 1).rem(K);
 
-                self.nearest_n_within_recurse::<D, R>(
+                self.nearest_n_within_recurse::<D, N, R, F>(
+                    metric,
                     query,
                     radius,
                     closer_node_idx,
@@ -113,13 +140,27 @@ macro_rules! generate_immutable_nearest_n_within {
                     matching_items,
                     off,
                     rd,
+                    min,
+                    max,
+                    filter,
                 );
 
-                rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = metric.lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, metric.dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
 
-                if rd <= radius && rd < matching_items.max_dist() {
+                if rd <= radius && rd < matching_items.max_dist().unwrap_or(D::Output::infinity()) {
                     off[split_dim] = new_off;
-                    self.nearest_n_within_recurse::<D, R>(
+                    self.nearest_n_within_recurse::<D, N, R, F>(
+                        metric,
                         query,
                         radius,
                         further_node_idx,
@@ -127,9 +168,14 @@ macro_rules! generate_immutable_nearest_n_within {
                         matching_items,
                         off,
                         rd,
+                        min,
+                        max,
+                        filter,
                     );
                     off[split_dim] = old_off;
                 }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
             }
         }
     };
@@ -5,39 +5,53 @@ macro_rules! generate_immutable_nearest_one {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn nearest_one<D>(&self, query: &[A; K]) -> NearestNeighbour<A, T>
+            pub fn nearest_one<D>(&self, query: &[A; K]) -> NearestNeighbour<D::Output, T>
                 where
                     D: DistanceMetric<A, K>,
+                    D::Output: Axis + BestFromDists<T, B>,
             {
                 let mut off = [A::zero(); K];
-                self.nearest_one_recurse::<D>(
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                self.nearest_one_recurse::<_, _, fn(T) -> bool>(
+                    &StatelessMetric::<D>::new(),
                     query,
                     1,
                     0,
                     NearestNeighbour {
-                        distance: A::max_value(),
+                        distance: D::Output::max_value(),
                         item: T::zero(),
                     },
                     &mut off,
-                    A::zero(),
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                    None,
                 )
             }
 
             #[allow(clippy::too_many_arguments)]
-            fn nearest_one_recurse<D>(
+            pub(crate) fn nearest_one_recurse<D, N, F>(
                 &self,
+                metric: &D,
                 query: &[A; K],
                 stem_idx: usize,
                 split_dim: usize,
-                mut nearest: NearestNeighbour<A, T>,
+                mut nearest: N,
                 off: &mut [A; K],
-                rd: A,
-            ) -> NearestNeighbour<A, T>
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                filter: Option<&F>,
+            ) -> N
                 where
-                    D: DistanceMetric<A, K>,
+                    D: StatefulDistanceMetric<A, K>,
+                    D::Output: Axis + BestFromDists<T, B>,
+                    N: FromLeafEntry<A, T, K, D::Output>,
+                    F: Fn(T) -> bool,
             {
                 if stem_idx >= self.stems.len() {
-                    self.search_leaf_for_nearest::<D>(query, &mut nearest, stem_idx - self.stems.len());
+                    self.search_leaf_for_nearest::<D, N, F>(metric, query, &mut nearest, stem_idx, filter);
 
                     return nearest;
                 }
@@ -65,23 +79,37 @@ macro_rules! generate_immutable_nearest_one {
                 let next_split_dim = (split_dim + 1).rem(K);
 
                 let nearest_neighbour =
-                    self.nearest_one_recurse::<D>(query, closer_node_idx, next_split_dim, nearest, off, rd);
+                    self.nearest_one_recurse::<D, N, F>(metric, query, closer_node_idx, next_split_dim, nearest, off, rd, min, max, filter);
 
                 if nearest_neighbour < nearest {
                     nearest = nearest_neighbour;
                 }
 
-                rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = metric.lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, metric.dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
 
-                if rd <= nearest.distance {
+                if rd <= nearest.distance() {
                     off[split_dim] = new_off;
-                    let result = self.nearest_one_recurse::<D>(
+                    let result = self.nearest_one_recurse::<D, N, F>(
+                        metric,
                         query,
                         further_node_idx,
                         next_split_dim,
                         nearest,
                         off,
                         rd,
+                        min,
+                        max,
+                        filter,
                     );
                     off[split_dim] = old_off;
 
@@ -89,33 +117,60 @@ macro_rules! generate_immutable_nearest_one {
                         nearest = result;
                     }
                 }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
 
                 nearest
             }
 
             #[inline]
-             fn search_leaf_for_nearest<D>(
+            pub(crate) fn search_leaf_for_nearest<D, N, F>(
                 &self,
+                metric: &D,
                 query: &[A; K],
-                nearest: &mut NearestNeighbour<A, T>,
-                leaf_idx: usize,
+                nearest: &mut N,
+                stem_idx: usize,
+                filter: Option<&F>,
             ) where
-                D: DistanceMetric<A, K>,
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis + BestFromDists<T, B>,
+                N: FromLeafEntry<A, T, K, D::Output>,
+                F: Fn(T) -> bool,
             {
-                let leaf_node = unsafe { self.leaves.get_unchecked(leaf_idx) };
-                // let leaf_node = &self.leaves[leaf_idx];
+                let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                    return;
+                };
+
+                // the SIMD leaf search can't skip rejected items or report where the
+                // nearest item is stored, so filtered queries and those that return
+                // points fall back to checking each point in turn
+                if filter.is_some() || N::KEEPS_POINT {
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        let dist = metric.dist(query, &point);
+                        if dist < nearest.distance() {
+                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                            if filter.is_none_or(|filter| filter(item)) {
+                                *nearest = N::from_leaf_entry(dist, item, &point);
+                            }
+                        }
+                    });
+
+                    return;
+                }
 
-                let mut best_item = nearest.item;
-                let mut best_dist = nearest.distance;
+                let mut best_item = nearest.item();
+                let mut best_dist = nearest.distance();
 
-                leaf_node.nearest_one::<D>(
+                leaf_node.nearest_one_with_metric(
+                    metric,
                     query,
                     &mut best_dist,
                     &mut best_item
                 );
 
-                nearest.distance = best_dist;
-                nearest.item = best_item;
+                // the point isn't kept, so the query stands in for it
+                *nearest = N::from_leaf_entry(best_dist, best_item, query);
 
                 // leaf_node
                 //     .content_points
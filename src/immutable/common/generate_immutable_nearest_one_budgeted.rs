@@ -0,0 +1,142 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_nearest_one_budgeted {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_one_budgeted<D>(
+                &self,
+                query: &[A; K],
+                budget: SearchBudget,
+            ) -> BudgetedResult<Option<NearestNeighbour<D::Output, T>>>
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis + BestFromDists<T, B>,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut nearest = NearestNeighbour {
+                    distance: D::Output::max_value(),
+                    item: T::zero(),
+                };
+                let mut budget = BudgetTracker::new(budget);
+
+                self.nearest_one_budgeted_recurse::<D>(
+                    query,
+                    1,
+                    0,
+                    &mut nearest,
+                    &mut off,
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                    &mut budget,
+                );
+
+                // the search only keeps items strictly nearer than the initial nearest,
+                // so it is unchanged if the budget ran out before any leaf was searched
+                budget.into_result((nearest.distance < D::Output::max_value()).then_some(nearest))
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn nearest_one_budgeted_recurse<D>(
+                &self,
+                query: &[A; K],
+                stem_idx: usize,
+                split_dim: usize,
+                nearest: &mut NearestNeighbour<D::Output, T>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                budget: &mut BudgetTracker,
+            )
+                where
+                    D: DistanceMetric<A, K>,
+                    D::Output: Axis + BestFromDists<T, B>,
+            {
+                if stem_idx >= self.stems.len() {
+                    if self.leaf_for_node_idx(stem_idx).is_none() || !budget.visit_leaf() {
+                        return;
+                    }
+
+                    self.search_leaf_for_nearest::<_, _, fn(T) -> bool>(
+                        &StatelessMetric::<D>::new(),
+                        query,
+                        nearest,
+                        stem_idx,
+                        None,
+                    );
+
+                    return;
+                }
+
+                if !budget.visit_stem() {
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let mut rd = rd;
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+
+                let is_left_child = usize::from(*unsafe { query.get_unchecked(split_dim) } < val);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                self.nearest_one_budgeted_recurse::<D>(
+                    query,
+                    closer_node_idx,
+                    next_split_dim,
+                    nearest,
+                    off,
+                    rd,
+                    min,
+                    max,
+                    budget,
+                );
+
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                if query[split_dim] < val {
+                    // unused stems have an infinite split value
+                    min[split_dim] = if val < old_max { val } else { old_max };
+                } else {
+                    max[split_dim] = val;
+                }
+                rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
+
+                if rd <= nearest.distance {
+                    off[split_dim] = new_off;
+                    self.nearest_one_budgeted_recurse::<D>(
+                        query,
+                        further_node_idx,
+                        next_split_dim,
+                        nearest,
+                        off,
+                        rd,
+                        min,
+                        max,
+                        budget,
+                    );
+                    off[split_dim] = old_off;
+                }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+            }
+        }
+    };
+}
@@ -0,0 +1,31 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_nearest_one_with_metric {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn nearest_one_with_metric<D>(&self, metric: &D, query: &[A; K]) -> NearestNeighbour<D::Output, T>
+                where
+                    D: StatefulDistanceMetric<A, K>,
+                    D::Output: Axis + BestFromDists<T, B>,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                self.nearest_one_recurse::<D, _, fn(T) -> bool>(
+                    metric,
+                    query,
+                    1,
+                    0,
+                    NearestNeighbour { distance: D::Output::max_value(), item: T::zero() },
+                    &mut off,
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                    None,
+                )
+            }
+        }
+    };
+}
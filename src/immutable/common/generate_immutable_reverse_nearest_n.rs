@@ -0,0 +1,239 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_reverse_nearest_n {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn reverse_nearest_n<D>(&self, query: &[A; K], k: usize) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                A: BestFromDists<T, B>,
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                usize: Cast<T>,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                if k > 0 {
+                    let subtree_sizes = self.reverse_nearest_n_subtree_sizes();
+
+                    self.reverse_nearest_n_recurse::<D>(
+                        query,
+                        k,
+                        1,
+                        0,
+                        &subtree_sizes,
+                        &mut Vec::new(),
+                        &mut matching_items,
+                        &mut off,
+                        D::Output::zero(),
+                        &mut min,
+                        &mut max,
+                    );
+                }
+
+                matching_items.sort();
+                matching_items
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn reverse_nearest_n_recurse<D>(
+                &self,
+                query: &[A; K],
+                k: usize,
+                stem_idx: usize,
+                split_dim: usize,
+                subtree_sizes: &[usize],
+                leaf_dists: &mut Vec<D::Output>,
+                matching_items: &mut Vec<NearestNeighbour<D::Output, T>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) where
+                A: BestFromDists<T, B>,
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                usize: Cast<T>,
+            {
+                // if the subtree holds more than k points, each of them has k
+                // neighbours no further away than the diameter of its bounding box,
+                // so none of them can have a query point beyond that among their k nearest
+                if rd > Self::reverse_nearest_n_box_diameter::<D>(query, min, max)
+                    && self.reverse_nearest_n_subtree_size(stem_idx, subtree_sizes) > k
+                {
+                    return;
+                }
+
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    let points: Vec<[A; K]> = (0..leaf_node.size as usize)
+                        .map(|idx| std::array::from_fn(|dim| leaf_node.content_points[dim][idx]))
+                        .collect();
+
+                    points.iter().enumerate().for_each(|(idx, point)| {
+                        let distance = D::dist(query, point);
+
+                        // if the leaf holds more than k points, the k nearest to each of them
+                        // are no further away than the k nearest within the leaf, which are
+                        // much cheaper to find than running a query from the point
+                        if points.len() > k {
+                            leaf_dists.clear();
+                            leaf_dists.extend(points.iter().map(|other| D::dist(point, other)));
+                            let (_, kth_dist, _) = leaf_dists.select_nth_unstable_by(k, |a, b| {
+                                a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+                            });
+                            if distance > *kth_dist {
+                                return;
+                            }
+                        }
+
+                        // the nearest k + 1 items to a stored point include the point itself
+                        let nearest = self.nearest_n::<D>(point, k + 1);
+                        if nearest.len() <= k || distance <= nearest[k].distance {
+                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                            matching_items.push(NearestNeighbour { distance, item });
+                        }
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+
+                let is_left = *unsafe { query.get_unchecked(split_dim) } < val;
+                let is_left_child = usize::from(is_left);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                // unused stems have an infinite split value, which must not
+                // push the lower bound of the box past its upper bound
+                let right_min = if val < old_max { val } else { old_max };
+
+                if is_left {
+                    if val < old_max {
+                        max[split_dim] = val;
+                    }
+                } else if val > old_min {
+                    min[split_dim] = right_min;
+                }
+                self.reverse_nearest_n_recurse::<D>(
+                    query,
+                    k,
+                    closer_node_idx,
+                    next_split_dim,
+                    subtree_sizes,
+                    leaf_dists,
+                    matching_items,
+                    off,
+                    rd,
+                    min,
+                    max,
+                );
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+
+                if is_left {
+                    if val > old_min {
+                        min[split_dim] = right_min;
+                    }
+                } else if val < old_max {
+                    max[split_dim] = val;
+                }
+                let rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
+
+                off[split_dim] = new_off;
+                self.reverse_nearest_n_recurse::<D>(
+                    query,
+                    k,
+                    further_node_idx,
+                    next_split_dim,
+                    subtree_sizes,
+                    leaf_dists,
+                    matching_items,
+                    off,
+                    rd,
+                    min,
+                    max,
+                );
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+                off[split_dim] = old_off;
+            }
+
+            /// Returns the distance between opposite corners of the box bounded by `min`
+            /// and `max`, or the maximum value of `A` if any side of the box has not
+            /// yet been bounded by a split or the metric implements `lower_bound_to_box`.
+            fn reverse_nearest_n_box_diameter<D>(query: &[A; K], min: &[A; K], max: &[A; K]) -> D::Output
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                // the diameter is built from per-axis distances, which a metric that bounds
+                // boxes itself isn't necessarily made of
+                if D::lower_bound_to_box(query, min, max).is_some() {
+                    return D::Output::max_value();
+                }
+
+                let mut diameter = D::Output::zero();
+
+                for dim in 0..K {
+                    if min[dim] == A::min_value() || max[dim] == A::max_value() {
+                        return D::Output::max_value();
+                    }
+
+                    diameter = Axis::rd_accumulate(diameter, D::dist1(max[dim], min[dim]), D::ACCUMULATION);
+                }
+
+                diameter
+            }
+
+            /// Returns the number of points under every stem, indexed like the stems, so
+            /// that the pruning checks don't have to walk the subtree each time.
+            fn reverse_nearest_n_subtree_sizes(&self) -> Vec<usize> {
+                let mut subtree_sizes = vec![0; self.stems.len()];
+
+                // the children of a stem always come after it, so filling in the sizes
+                // from the back sees every child before its parent
+                for stem_idx in (1..self.stems.len()).rev() {
+                    let left_child_idx = stem_idx << 1;
+                    subtree_sizes[stem_idx] = self.reverse_nearest_n_subtree_size(left_child_idx, &subtree_sizes)
+                        + self.reverse_nearest_n_subtree_size(left_child_idx + 1, &subtree_sizes);
+                }
+
+                subtree_sizes
+            }
+
+            fn reverse_nearest_n_subtree_size(&self, stem_idx: usize, subtree_sizes: &[usize]) -> usize {
+                if stem_idx >= self.stems.len() {
+                    return self
+                        .leaf_for_node_idx(stem_idx)
+                        .map_or(0, |leaf_node| leaf_node.size as usize);
+                }
+
+                subtree_sizes[stem_idx]
+            }
+        }
+    };
+}
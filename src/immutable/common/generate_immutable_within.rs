@@ -5,10 +5,11 @@ macro_rules! generate_immutable_within {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn within<D>(&self, query: &[A; K], dist: A) -> Vec<NearestNeighbour<A, T>>
+            pub fn within<D>(&self, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 A: BestFromDists<T, B>,
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
                 usize: Cast<T>,            {
                 self.nearest_n_within::<D>(query, dist, usize::MAX, true)
             }
@@ -23,9 +24,10 @@ macro_rules! generate_immutable_within {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn within<D>(&self, query: &[A; K], dist: A) -> Vec<NearestNeighbour<A, T>>
+            pub fn within<D>(&self, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 let mut matching_items = self.within_unsorted::<D>(query, dist);
                 matching_items.sort();
@@ -0,0 +1,187 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_annulus {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_annulus<D>(&self, query: &[A; K], min_dist: D::Output, max_dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                self.within_annulus_recurse::<D>(
+                    query,
+                    min_dist,
+                    max_dist,
+                    1,
+                    0,
+                    &mut matching_items,
+                    &mut off,
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                );
+
+                matching_items.sort();
+                matching_items
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn within_annulus_recurse<D>(
+                &self,
+                query: &[A; K],
+                min_dist: D::Output,
+                max_dist: D::Output,
+                stem_idx: usize,
+                split_dim: usize,
+                matching_items: &mut Vec<NearestNeighbour<D::Output, T>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if Self::within_annulus_max_dist_to_box::<D>(query, min, max) < min_dist {
+                    return;
+                }
+
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        let distance = D::dist(query, &point);
+
+                        if distance >= min_dist && distance <= max_dist {
+                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                            matching_items.push(NearestNeighbour { distance, item });
+                        }
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let mut rd = rd;
+                let old_off = off[split_dim];
+                let new_off = query[split_dim].saturating_dist(val);
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+
+                let is_left = *unsafe { query.get_unchecked(split_dim) } < val;
+                let is_left_child = usize::from(is_left);
+
+                let closer_node_idx = left_child_idx + (1 - is_left_child);
+                let further_node_idx = left_child_idx + is_left_child;
+
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                // unused stems have an infinite split value, which must not
+                // push the lower bound of the box past its upper bound
+                let right_min = if val < old_max { val } else { old_max };
+
+                if is_left {
+                    if val < old_max {
+                        max[split_dim] = val;
+                    }
+                } else if val > old_min {
+                    min[split_dim] = right_min;
+                }
+                self.within_annulus_recurse::<D>(
+                    query,
+                    min_dist,
+                    max_dist,
+                    closer_node_idx,
+                    next_split_dim,
+                    matching_items,
+                    off,
+                    rd,
+                    min,
+                    max,
+                );
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+
+                if is_left {
+                    if val > old_min {
+                        min[split_dim] = right_min;
+                    }
+                } else if val < old_max {
+                    max[split_dim] = val;
+                }
+                rd = D::lower_bound_to_box(query, min, max).unwrap_or_else(|| {
+                    Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION)
+                });
+
+                if rd <= max_dist {
+                    off[split_dim] = new_off;
+                    self.within_annulus_recurse::<D>(
+                        query,
+                        min_dist,
+                        max_dist,
+                        further_node_idx,
+                        next_split_dim,
+                        matching_items,
+                        off,
+                        rd,
+                        min,
+                        max,
+                    );
+                    off[split_dim] = old_off;
+                }
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+            }
+
+            /// Returns an upper bound on the distance from `query` to any point inside
+            /// the box bounded by `min` and `max`, or the maximum value of `A` if any
+            /// side of the box has not yet been bounded by a split.
+            fn within_annulus_max_dist_to_box<D>(query: &[A; K], min: &[A; K], max: &[A; K]) -> D::Output
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if let Some(max_dist) = D::upper_bound_to_box(query, min, max) {
+                    return max_dist;
+                }
+                // a metric that bounds boxes itself isn't necessarily built from its per-axis
+                // distances, so they can't be relied on to bound it from above either
+                if D::lower_bound_to_box(query, min, max).is_some() {
+                    return D::Output::max_value();
+                }
+
+                let mut max_dist = D::Output::zero();
+
+                for dim in 0..K {
+                    if min[dim] == A::min_value() || max[dim] == A::max_value() {
+                        return D::Output::max_value();
+                    }
+
+                    let to_min = query[dim].saturating_dist(min[dim]);
+                    let to_max = query[dim].saturating_dist(max[dim]);
+                    let furthest = if to_min > to_max { to_min } else { to_max };
+
+                    max_dist = Axis::rd_accumulate(max_dist, D::dist1(furthest, A::zero()), D::ACCUMULATION);
+                }
+
+                max_dist
+            }
+        }
+    };
+}
@@ -0,0 +1,73 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_box_unsorted {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_box_unsorted(&self, min: &[A; K], max: &[A; K]) -> Vec<T> {
+                let mut matching_items = Vec::new();
+
+                self.within_box_unsorted_recurse(min, max, 1, 0, &mut matching_items);
+
+                matching_items
+            }
+
+            fn within_box_unsorted_recurse(
+                &self,
+                min: &[A; K],
+                max: &[A; K],
+                stem_idx: usize,
+                split_dim: usize,
+                matching_items: &mut Vec<T>,
+            ) {
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let is_inside = (0..K).all(|dim| {
+                            let val = leaf_node.content_points[dim][idx];
+                            val >= min[dim] && val <= max[dim]
+                        });
+
+                        if is_inside {
+                            matching_items.push(*unsafe { leaf_node.content_items.get_unchecked(idx) });
+                        }
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                if *unsafe { min.get_unchecked(split_dim) } < val {
+                    self.within_box_unsorted_recurse(
+                        min,
+                        max,
+                        left_child_idx,
+                        next_split_dim,
+                        matching_items,
+                    );
+                }
+
+                if *unsafe { max.get_unchecked(split_dim) } >= val {
+                    self.within_box_unsorted_recurse(
+                        min,
+                        max,
+                        left_child_idx + 1,
+                        next_split_dim,
+                        matching_items,
+                    );
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,81 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_box_unsorted_iter {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_box_unsorted_iter(
+                &'a self,
+                min: &'a [A; K],
+                max: &'a [A; K],
+            ) -> WithinBoxUnsortedIter<'a, T> {
+                let gen = Gn::new_scoped(move |gen_scope| {
+                    self.within_box_unsorted_iter_recurse(min, max, 1, 0, gen_scope);
+
+                    done!();
+                });
+
+                WithinBoxUnsortedIter::new(gen)
+            }
+
+            fn within_box_unsorted_iter_recurse(
+                &'a self,
+                min: &[A; K],
+                max: &[A; K],
+                stem_idx: usize,
+                split_dim: usize,
+                mut gen_scope: Scope<'a, (), T>,
+            ) -> Scope<'a, (), T> {
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return gen_scope;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let is_inside = (0..K).all(|dim| {
+                            let val = leaf_node.content_points[dim][idx];
+                            val >= min[dim] && val <= max[dim]
+                        });
+
+                        if is_inside {
+                            gen_scope.yield_(*unsafe { leaf_node.content_items.get_unchecked(idx) });
+                        }
+                    });
+
+                    return gen_scope;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                if *unsafe { min.get_unchecked(split_dim) } < val {
+                    gen_scope = self.within_box_unsorted_iter_recurse(
+                        min,
+                        max,
+                        left_child_idx,
+                        next_split_dim,
+                        gen_scope,
+                    );
+                }
+
+                if *unsafe { max.get_unchecked(split_dim) } >= val {
+                    gen_scope = self.within_box_unsorted_iter_recurse(
+                        min,
+                        max,
+                        left_child_idx + 1,
+                        next_split_dim,
+                        gen_scope,
+                    );
+                }
+
+                gen_scope
+            }
+        }
+    };
+}
@@ -0,0 +1,27 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_filtered {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_filtered<D, F>(&self, query: &[A; K], dist: D::Output, filter: F) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                A: BestFromDists<T, B>,
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                F: Fn(T) -> bool,
+                usize: Cast<T>,
+            {
+                self.nearest_n_within_stub::<_, _, Vec<NearestNeighbour<D::Output, T>>, F>(
+                    &StatelessMetric::<D>::new(),
+                    query,
+                    dist,
+                    0,
+                    true,
+                    &filter,
+                )
+            }
+        }
+    };
+}
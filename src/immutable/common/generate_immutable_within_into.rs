@@ -0,0 +1,20 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_into {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_into<D>(&self, query: &[A; K], dist: D::Output, results: &mut Vec<NearestNeighbour<D::Output, T>>)
+            where
+                A: BestFromDists<T, B>,
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                usize: Cast<T>,
+            {
+                self.within_unsorted_into::<D>(query, dist, results);
+                results.sort();
+            }
+        }
+    };
+}
@@ -0,0 +1,164 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_periodic {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_periodic<D>(&self, query: &[A; K], dist: D::Output, box_size: &[A; K]) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                self.within_periodic_recurse::<D>(
+                    query,
+                    dist,
+                    box_size,
+                    1,
+                    0,
+                    &mut matching_items,
+                    &mut off,
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                );
+
+                matching_items.sort();
+                matching_items
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            fn within_periodic_recurse<D>(
+                &self,
+                query: &[A; K],
+                radius: D::Output,
+                box_size: &[A; K],
+                stem_idx: usize,
+                split_dim: usize,
+                matching_items: &mut Vec<NearestNeighbour<D::Output, T>>,
+                off: &mut [A; K],
+                rd: D::Output,
+                min: &mut [A; K],
+                max: &mut [A; K],
+            ) where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point: [A; K] = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+                        let image = Self::within_periodic_image(query, &point, box_size);
+                        let distance = D::dist(query, &image);
+
+                        if distance < radius {
+                            let item = *unsafe { leaf_node.content_items.get_unchecked(idx) };
+                            matching_items.push(NearestNeighbour { distance, item });
+                        }
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+
+                let old_off = off[split_dim];
+                let old_min = min[split_dim];
+                let old_max = max[split_dim];
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                let left_max = if val < old_max { val } else { old_max };
+                let right_min = if val > old_min { val } else { old_min };
+                let left_off = Self::within_periodic_interval_dist(
+                    query[split_dim], old_min, left_max, box_size[split_dim],
+                );
+                let right_off = Self::within_periodic_interval_dist(
+                    query[split_dim], right_min, old_max, box_size[split_dim],
+                );
+
+                for (child_idx, child_off, child_min, child_max) in [
+                    (left_child_idx, left_off, old_min, left_max),
+                    (left_child_idx + 1, right_off, right_min, old_max),
+                ] {
+                    let child_rd = Axis::rd_accumulate(rd, D::dist1_on_axis(child_off, old_off, split_dim), D::ACCUMULATION);
+
+                    if child_rd <= radius {
+                        off[split_dim] = child_off;
+                        min[split_dim] = child_min;
+                        max[split_dim] = child_max;
+                        self.within_periodic_recurse::<D>(
+                            query,
+                            radius,
+                            box_size,
+                            child_idx,
+                            next_split_dim,
+                            matching_items,
+                            off,
+                            child_rd,
+                            min,
+                            max,
+                        );
+                    }
+                }
+
+                off[split_dim] = old_off;
+                min[split_dim] = old_min;
+                max[split_dim] = old_max;
+            }
+
+            /// Returns the smallest distance along one axis between the interval `lo..=hi`
+            /// and either `q` or one of its periodic images, or zero if any of them lie within it.
+            #[inline]
+            fn within_periodic_interval_dist(q: A, lo: A, hi: A, size: A) -> A {
+                let interval_dist = |x: A| {
+                    if x < lo {
+                        lo - x
+                    } else if x > hi {
+                        x - hi
+                    } else {
+                        A::zero()
+                    }
+                };
+
+                let direct = interval_dist(q);
+                let below = interval_dist(q - size);
+                let above = interval_dist(q + size);
+
+                let nearest = if below < direct { below } else { direct };
+                if above < nearest { above } else { nearest }
+            }
+
+            /// Returns the image of `point` that is nearest to `query` along every axis.
+            #[inline]
+            fn within_periodic_image(query: &[A; K], point: &[A; K], box_size: &[A; K]) -> [A; K] {
+                let mut image = *point;
+
+                for dim in 0..K {
+                    let half_size = box_size[dim] / (A::one() + A::one());
+                    let delta = point[dim] - query[dim];
+
+                    if delta > half_size {
+                        image[dim] = point[dim] - box_size[dim];
+                    } else if delta < -half_size {
+                        image[dim] = point[dim] + box_size[dim];
+                    }
+                }
+
+                image
+            }
+        }
+    };
+}
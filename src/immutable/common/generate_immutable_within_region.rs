@@ -0,0 +1,114 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_region {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_region<R>(&self, region: &R) -> Vec<T>
+            where
+                R: QueryRegion<A, K>,
+            {
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                let mut matching_items = Vec::new();
+
+                self.within_region_recurse(region, 1, 0, &mut min, &mut max, &mut matching_items);
+
+                matching_items
+            }
+
+            fn within_region_recurse<R>(
+                &self,
+                region: &R,
+                stem_idx: usize,
+                split_dim: usize,
+                min: &mut [A; K],
+                max: &mut [A; K],
+                matching_items: &mut Vec<T>,
+            ) where
+                R: QueryRegion<A, K>,
+            {
+                if !region.intersects_box(min, max) {
+                    return;
+                }
+
+                if region.contains_box(min, max) {
+                    self.within_region_add_all(stem_idx, matching_items);
+                    return;
+                }
+
+                if stem_idx >= self.stems.len() {
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
+
+                    (0..leaf_node.size as usize).for_each(|idx| {
+                        let point = std::array::from_fn(|dim| leaf_node.content_points[dim][idx]);
+
+                        if region.contains_point(&point) {
+                            matching_items.push(*unsafe { leaf_node.content_items.get_unchecked(idx) });
+                        }
+                    });
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+                self.prefetch_stems(left_child_idx);
+
+                let val = *unsafe { self.stems.get_unchecked(stem_idx) };
+                let next_split_dim = (split_dim + 1).rem(K);
+
+                let old_max = max[split_dim];
+                if val < old_max {
+                    max[split_dim] = val;
+                }
+                self.within_region_recurse(
+                    region,
+                    left_child_idx,
+                    next_split_dim,
+                    min,
+                    max,
+                    matching_items,
+                );
+                max[split_dim] = old_max;
+
+                // unused stems have an infinite split value, which must not
+                // push the lower bound of the box past its upper bound
+                let old_min = min[split_dim];
+                if val > old_min {
+                    min[split_dim] = if val < max[split_dim] { val } else { max[split_dim] };
+                }
+                self.within_region_recurse(
+                    region,
+                    left_child_idx + 1,
+                    next_split_dim,
+                    min,
+                    max,
+                    matching_items,
+                );
+                min[split_dim] = old_min;
+            }
+
+            fn within_region_add_all(&self, stem_idx: usize, matching_items: &mut Vec<T>) {
+                if stem_idx >= self.stems.len() {
+                    if let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) {
+                        matching_items.extend_from_slice(
+                            &leaf_node.content_items[..leaf_node.size as usize],
+                        );
+                    }
+
+                    return;
+                }
+
+                let left_child_idx = stem_idx << 1;
+
+                self.within_region_add_all(left_child_idx, matching_items);
+                self.within_region_add_all(left_child_idx + 1, matching_items);
+            }
+        }
+    };
+}
@@ -5,10 +5,11 @@ macro_rules! generate_immutable_within_unsorted {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn within_unsorted<D>(&self, query: &[A; K], dist: A) -> Vec<NearestNeighbour<A, T>>
+            pub fn within_unsorted<D>(&self, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 A: BestFromDists<T, B>,
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
                 usize: Cast<T>,            {
                 self.nearest_n_within::<D>(query, dist, usize::MAX, false)
             }
@@ -23,9 +24,10 @@ macro_rules! generate_immutable_within_unsorted {
         doc_comment! {
             concat!$comments,
             #[inline]
-            pub fn within_unsorted<D>(&self, query: &[A; K], dist: A) -> Vec<NearestNeighbour<A, T>>
+            pub fn within_unsorted<D>(&self, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
             where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 let mut off = [A::zero(); K];
                 let mut matching_items = Vec::new();
@@ -37,7 +39,7 @@ macro_rules! generate_immutable_within_unsorted {
                     0,
                     &mut matching_items,
                     &mut off,
-                    A::zero(),
+                    D::Output::zero(),
                 );
 
                 matching_items
@@ -47,17 +49,20 @@ macro_rules! generate_immutable_within_unsorted {
             fn within_unsorted_recurse<D>(
                 &self,
                 query: &[A; K],
-                radius: A,
+                radius: D::Output,
                 stem_idx: usize,
                 split_dim: usize,
-                matching_items: &mut Vec<NearestNeighbour<A, T>>,
+                matching_items: &mut Vec<NearestNeighbour<D::Output, T>>,
                 off: &mut [A; K],
-                rd: A,
+                rd: D::Output,
             ) where
                 D: DistanceMetric<A, K>,
+                D::Output: Axis,
             {
                 if stem_idx >= self.stems.len() {
-                    let leaf_node = &self.leaves[stem_idx - self.stems.len()];
+                    let Some(leaf_node) = self.leaf_for_node_idx(stem_idx) else {
+                        return;
+                    };
 
                     leaf_node
                         .content_points
@@ -108,7 +113,7 @@ macro_rules! generate_immutable_within_unsorted {
                     rd,
                 );
 
-                rd = Axis::rd_update(rd, D::dist1(new_off, old_off));
+                rd = Axis::rd_accumulate(rd, D::dist1_on_axis(new_off, old_off, split_dim), D::ACCUMULATION);
 
                 if rd <= radius {
                     off[split_dim] = new_off;
@@ -0,0 +1,36 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_unsorted_into {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_unsorted_into<D>(&self, query: &[A; K], dist: D::Output, results: &mut Vec<NearestNeighbour<D::Output, T>>)
+            where
+                A: BestFromDists<T, B>,
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+                usize: Cast<T>,
+            {
+                let mut off = [A::zero(); K];
+                let mut min = [A::min_value(); K];
+                let mut max = [A::max_value(); K];
+                results.clear();
+
+                self.nearest_n_within_recurse(
+                    &StatelessMetric::<D>::new(),
+                    query,
+                    dist,
+                    1,
+                    0,
+                    results,
+                    &mut off,
+                    D::Output::zero(),
+                    &mut min,
+                    &mut max,
+                    &|_| true,
+                );
+            }
+        }
+    };
+}
@@ -0,0 +1,17 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_unsorted_with_metric {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_unsorted_with_metric<D>(&self, metric: &D, query: &[A; K], dist: D::Output) -> Vec<NearestNeighbour<D::Output, T>>
+            where
+                D: StatefulDistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                self.nearest_n_within_with_metric_stub(metric, query, dist, usize::MAX, false)
+            }
+        }
+    };
+}
@@ -0,0 +1,17 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! generate_immutable_within_unsorted_with_point {
+    ($comments:tt) => {
+        doc_comment! {
+            concat!$comments,
+            #[inline]
+            pub fn within_unsorted_with_point<D>(&self, query: &[A; K], dist: D::Output) -> Vec<PointNeighbour<A, T, K, D::Output>>
+            where
+                D: DistanceMetric<A, K>,
+                D::Output: Axis,
+            {
+                self.nearest_n_within_with_metric_stub(&StatelessMetric::<D>::new(), query, dist, usize::MAX, false)
+            }
+        }
+    };
+}
@@ -1,8 +1,42 @@
 pub(crate) mod generate_best_n_within;
+pub(crate) mod generate_immutable_any_within;
+pub(crate) mod generate_immutable_approx_nearest_n;
 pub(crate) mod generate_immutable_approx_nearest_one;
+pub(crate) mod generate_immutable_best_n_within_by;
+pub(crate) mod generate_immutable_best_n_within_with_metric;
+pub(crate) mod generate_immutable_best_n_within_with_point;
+pub(crate) mod generate_immutable_count_within;
+pub(crate) mod generate_immutable_farthest_n;
+pub(crate) mod generate_immutable_knn_graph;
+pub(crate) mod generate_immutable_nearest_iter;
 pub(crate) mod generate_immutable_nearest_n;
+pub(crate) mod generate_immutable_nearest_n_budgeted;
+pub(crate) mod generate_immutable_nearest_n_excluding;
+pub(crate) mod generate_immutable_nearest_n_filtered;
+pub(crate) mod generate_immutable_nearest_n_into;
+pub(crate) mod generate_immutable_nearest_n_periodic;
+pub(crate) mod generate_immutable_nearest_n_with_metric;
+pub(crate) mod generate_immutable_nearest_n_with_point;
 pub(crate) mod generate_immutable_nearest_n_within;
+pub(crate) mod generate_immutable_nearest_n_within_into_collection;
 pub(crate) mod generate_immutable_nearest_one;
+pub(crate) mod generate_immutable_nearest_one_budgeted;
+pub(crate) mod generate_immutable_nearest_one_filtered;
+pub(crate) mod generate_immutable_nearest_one_with_metric;
+pub(crate) mod generate_immutable_nearest_one_with_point;
+pub(crate) mod generate_immutable_reverse_nearest_n;
 pub(crate) mod generate_immutable_within;
+pub(crate) mod generate_immutable_within_annulus;
+pub(crate) mod generate_immutable_within_box_unsorted;
+pub(crate) mod generate_immutable_within_box_unsorted_iter;
+pub(crate) mod generate_immutable_within_filtered;
+pub(crate) mod generate_immutable_within_into;
+pub(crate) mod generate_immutable_within_periodic;
+pub(crate) mod generate_immutable_within_region;
 pub(crate) mod generate_immutable_within_unsorted;
+pub(crate) mod generate_immutable_within_unsorted_into;
+pub(crate) mod generate_immutable_within_unsorted_with_metric;
+pub(crate) mod generate_immutable_within_unsorted_with_point;
+pub(crate) mod generate_immutable_within_with_metric;
+pub(crate) mod generate_immutable_within_with_point;
 pub(crate) mod generate_within_unsorted_iter;
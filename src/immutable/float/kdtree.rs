@@ -6,6 +6,7 @@
 //! values, or [`f16`](https://docs.rs/half/latest/half/struct.f16.html) if the `f16` feature is enabled
 
 use az::{Az, Cast};
+use num_traits::Float;
 use ordered_float::OrderedFloat;
 use std::cmp::PartialEq;
 use std::fmt::Debug;
@@ -13,7 +14,10 @@ use std::ops::Rem;
 #[cfg(feature = "tracing")]
 use tracing::{event, span, Level};
 
+use crate::float::distance::normalise;
 pub use crate::float::kdtree::Axis;
+#[cfg(feature = "rkyv")]
+use crate::float_leaf_simd::leaf_node::ArchivedLeafNode;
 use crate::float_leaf_simd::leaf_node::{BestFromDists, LeafNode};
 use crate::iter::{IterableTreeData, TreeIter};
 use crate::types::Content;
@@ -86,6 +90,20 @@ where
     }
 }
 
+impl<A: Copy + Default, T: Copy + Default, const K: usize, const B: usize>
+    ImmutableKdTree<A, T, K, B>
+{
+    /// Returns the leaf at node index `idx`, which must be past the end of the stems,
+    /// or `None` if the leaf was never allocated.
+    ///
+    /// Unused stems have an infinite split value, so the right-hand side of them
+    /// can point past the end of the allocated leaves.
+    #[inline]
+    pub(crate) fn leaf_for_node_idx(&self, idx: usize) -> Option<&LeafNode<A, T, K, B>> {
+        self.leaves.get(idx - self.stems.len())
+    }
+}
+
 impl<A: Axis, T: Content, const K: usize, const B: usize> IterableTreeData<A, T, K>
     for ImmutableKdTree<A, T, K, B>
 {
@@ -215,6 +233,37 @@ where
         tree
     }
 
+    /// Creates an `ImmutableKdTree` populated with items from `source`, after
+    /// scaling each point to unit length.
+    ///
+    /// This is how trees should be built when querying with
+    /// [`CosineOnUnitSphere`](crate::float::distance::CosineOnUnitSphere). Query
+    /// points need normalising too, with [`normalise`](crate::float::distance::normalise).
+    /// As with [`new_from_slice`](Self::new_from_slice), each item is the index of its
+    /// point within `source`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kiddo::immutable::float::kdtree::ImmutableKdTree;
+    /// use kiddo::float::distance::{normalise, CosineOnUnitSphere};
+    ///
+    /// let points: Vec<[f64; 2]> = vec!([3.0, 0.0], [0.0, 5.0]);
+    /// let tree: ImmutableKdTree<f64, u32, 2, 32> = ImmutableKdTree::new_from_slice_normalised(&points);
+    ///
+    /// let nearest = tree.nearest_one::<CosineOnUnitSphere>(&normalise(&[1.0, 2.0]));
+    /// assert_eq!(nearest.item, 1);
+    /// ```
+    #[inline]
+    pub fn new_from_slice_normalised(source: &[[A; K]]) -> Self
+    where
+        A: Float,
+    {
+        let normalised: Vec<[A; K]> = source.iter().map(normalise).collect();
+
+        Self::new_from_slice(&normalised)
+    }
+
     fn extend_shifts(
         stem_node_count: usize,
         shifts: &[usize],
@@ -610,6 +659,14 @@ impl<
     pub fn size(&self) -> usize {
         self.size as usize
     }
+
+    /// Returns the leaf at node index `idx`, which must be past the end of the stems,
+    /// or `None` if the leaf was never allocated. See [`ImmutableKdTree::leaf_for_node_idx`].
+    #[inline]
+    pub(crate) fn leaf_for_node_idx(&self, idx: usize) -> Option<&ArchivedLeafNode<A, T, K, B>> {
+        self.leaves.get(idx - self.stems.len())
+    }
+
     #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
     #[inline]
     pub(crate) fn prefetch_stems(&self, idx: usize) {
@@ -0,0 +1,200 @@
+use num_traits::{One, Zero};
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+
+use crate::generate_immutable_approx_nearest_n;
+
+macro_rules! generate_immutable_float_approx_nearest_n {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_approx_nearest_n!((
+            "Finds approximately the nearest `qty` elements to `query`, using the specified
+distance metric function.
+
+A subtree is only searched if it could contain an item that is closer than the current
+`qty`th-best result by more than a factor of `1 + epsilon`. The distance of the `i`th
+result is therefore at most `1 + epsilon` times the distance of the true `i`th nearest
+item, and an `epsilon` of zero gives the same results as `nearest_n`.
+
+The bound applies to the values returned by the distance metric, so when using
+`SquaredEuclidean` the bound on the Euclidean distance is `sqrt(1 + epsilon)`.
+
+# Examples
+
+```rust
+    use kiddo::ImmutableKdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let nearest: Vec<_> = tree.approx_nearest_n::<SquaredEuclidean>(&[1.0, 2.0, 5.1], 1, 0.5);
+
+    assert_eq!(nearest.len(), 1);
+    assert!((nearest[0].distance - 0.01f64).abs() < f64::EPSILON);
+    assert_eq!(nearest[0].item, 0);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_approx_nearest_n!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_approx_nearest_n!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_approx_nearest_n_items() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.51f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.22f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.33f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.44f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.15f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        let expected = linear_search(&content_to_add, 3, &query_point);
+
+        let result: Vec<_> = tree
+            .approx_nearest_n::<SquaredEuclidean>(&query_point, 3, 0.0)
+            .into_iter()
+            .map(|n| n.distance)
+            .collect();
+        assert_eq!(result, expected);
+
+        let qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let epsilon = rng.gen_range(0f32..2f32);
+            let expected = linear_search(&content_to_add, qty, &query_point);
+
+            let result: Vec<_> = tree
+                .approx_nearest_n::<SquaredEuclidean>(&query_point, qty, epsilon)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+
+            assert_within_bound(&result, &expected, epsilon);
+        }
+    }
+
+    #[test]
+    fn can_query_approx_nearest_10_items_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        let query_points: Vec<[AX; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[AX; 4]>())
+            .collect();
+
+        for query_point in query_points {
+            let expected = linear_search(&content_to_add, N, &query_point);
+
+            for epsilon in [0.1f32, 0.5f32, 1.0f32] {
+                let result: Vec<_> = tree
+                    .approx_nearest_n::<SquaredEuclidean>(&query_point, N, epsilon)
+                    .into_iter()
+                    .map(|n| n.distance)
+                    .collect();
+
+                assert_within_bound(&result, &expected, epsilon);
+            }
+        }
+    }
+
+    fn assert_within_bound(result: &[AX], expected: &[AX], epsilon: AX) {
+        assert_eq!(result.len(), expected.len());
+        for (result_dist, expected_dist) in result.iter().zip(expected) {
+            // allow for rounding differences in the accumulated leaf distances
+            assert!(*result_dist <= *expected_dist * (1.0 + epsilon) + AX::EPSILON);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[[A; K]],
+        qty: usize,
+        query_point: &[A; K],
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .map(|p| SquaredEuclidean::dist(query_point, p))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(qty);
+
+        dists
+    }
+}
@@ -1,3 +1,5 @@
+use num_traits::float::FloatCore;
+
 use crate::distance_metric::DistanceMetric;
 use crate::float::kdtree::Axis;
 use crate::float_leaf_simd::leaf_node::BestFromDists;
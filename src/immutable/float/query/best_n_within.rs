@@ -1,12 +1,14 @@
 use az::Cast;
+use num_traits::Zero;
 use std::collections::BinaryHeap;
 use std::ops::Rem;
 
 use crate::best_neighbour::BestNeighbour;
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{DistanceMetric, StatefulDistanceMetric, StatelessMetric};
 use crate::float::kdtree::Axis;
 use crate::float_leaf_simd::leaf_node::BestFromDists;
 use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::neighbour::FromLeafEntry;
 use crate::types::Content;
 
 use crate::generate_immutable_best_n_within;
@@ -0,0 +1,180 @@
+use az::Cast;
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+
+use crate::best_neighbour::BestNeighbour;
+use crate::distance_metric::StatefulDistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::float_leaf_simd::leaf_node::BestFromDists;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::types::Content;
+
+use crate::generate_immutable_best_n_within_with_metric;
+
+macro_rules! generate_immutable_float_best_n_within_with_metric {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_best_n_within_with_metric!((
+            "Finds the \"best\" `n` elements within `dist` of `query`, using the supplied
+`metric` instance to measure distances.
+
+Behaves like [`best_n_within`](Self::best_n_within), but allows the metric to carry
+parameters that are chosen at runtime, such as per-axis weights. \"Best\" is
+determined by the lowest item values.
+
+# Examples
+
+```rust
+use kiddo::ImmutableKdTree;
+use kiddo::float::distance::WeightedSquaredEuclidean;
+",
+            $doctest_build_tree,
+            "
+
+let metric = WeightedSquaredEuclidean::new([1.0, 4.0, 0.5]);
+let mut best = tree.best_n_within_with_metric(&metric, &[1.0, 2.0, 5.1], 10f64, 1);
+
+assert_eq!(best.next().unwrap().item, 0);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_best_n_within_with_metric!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_best_n_within_with_metric!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::StatefulDistanceMetric;
+    use crate::float::distance::WeightedSquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_best_n_items_within_radius_with_metric() {
+        let metric = WeightedSquaredEuclidean::new([1.0, 4.0, 0.25, 2.0]);
+
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.5f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.2f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.3f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.4f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.1f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let max_qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let radius = rng.gen_range(0f32..1f32);
+            let expected = linear_search(&metric, &content_to_add, &query_point, radius, max_qty);
+
+            let result: Vec<_> = tree
+                .best_n_within_with_metric(&metric, &query_point, radius, max_qty)
+                .collect();
+
+            let mut result_items: Vec<_> = result.iter().map(|found| found.item).collect();
+            result_items.sort_unstable();
+            assert_eq!(result_items, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_best_n_items_within_radius_with_metric_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        let max_qty = 10;
+
+        let metric = WeightedSquaredEuclidean::new([1.0, 4.0, 0.25, 2.0]);
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rand::random::<[AX; 4]>();
+            let radius = 0.05;
+            let expected = linear_search(&metric, &content_to_add, &query_point, radius, max_qty);
+
+            let result: Vec<_> = tree
+                .best_n_within_with_metric(&metric, &query_point, radius, max_qty)
+                .collect();
+
+            let mut result_items: Vec<_> = result.iter().map(|found| found.item).collect();
+            result_items.sort_unstable();
+            assert_eq!(result_items, expected);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize, D: StatefulDistanceMetric<A, K, Output = A>>(
+        metric: &D,
+        content: &[[A; K]],
+        query: &[A; K],
+        radius: A,
+        max_qty: usize,
+    ) -> Vec<u32> {
+        let mut items: Vec<u32> = content
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| metric.dist(query, p) <= radius)
+            .map(|(item, _)| item as u32)
+            .collect();
+
+        items.sort_unstable();
+        items.truncate(max_qty);
+
+        items
+    }
+}
@@ -0,0 +1,166 @@
+use num_traits::Zero;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::types::Content;
+
+use crate::generate_immutable_count_within;
+
+macro_rules! generate_immutable_float_count_within {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_count_within!((
+            "Counts the elements within `dist` of `query`, using the specified
+distance metric function.
+
+Equivalent to `within_unsorted(query, dist).len()`, but does not allocate.
+Subtrees that lie entirely within `dist` of `query` are counted without
+calculating the distance to each of their points.
+
+# Examples
+
+```rust
+use kiddo::ImmutableKdTree;
+use kiddo::SquaredEuclidean;
+
+",
+            $doctest_build_tree,
+            "
+
+let count = tree.count_within::<SquaredEuclidean>(&[1.0, 2.0, 5.0], 10f64);
+
+assert_eq!(count, 2);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_count_within!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_count_within!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::{Manhattan, SquaredEuclidean};
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::{Rng, SeedableRng};
+
+    type AX = f32;
+
+    #[test]
+    fn can_count_items_within() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.5f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.2f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.3f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.4f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.1f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        assert_eq!(tree.count_within::<Manhattan>(&query_point, 100.0), 16);
+        assert_eq!(tree.count_within::<Manhattan>(&query_point, 0.0), 0);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let radius: f32 = rng.gen_range(0.0..2.0);
+
+            let expected = linear_count::<Manhattan, AX, 4>(&content_to_add, &query_point, radius);
+            let result = tree.count_within::<Manhattan>(&query_point, radius);
+            assert_eq!(result, expected);
+
+            let expected =
+                linear_count::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, radius);
+            let result = tree.count_within::<SquaredEuclidean>(&query_point, radius);
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_count_items_within_large_scale() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(5);
+
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<[AX; 4]> = (0..TREE_SIZE).map(|_| rng.gen::<[AX; 4]>()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = rng.gen::<[AX; 4]>();
+            let radius: f32 = rng.gen_range(0.0..1.0);
+
+            let expected =
+                linear_count::<SquaredEuclidean, AX, 4>(&content_to_add, &query_point, radius);
+            let result = tree.count_within::<SquaredEuclidean>(&query_point, radius);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_count<D: DistanceMetric<A, K, Output = A>, A: Axis, const K: usize>(
+        content: &[[A; K]],
+        query_point: &[A; K],
+        radius: A,
+    ) -> usize {
+        content
+            .iter()
+            .filter(|p| D::dist(query_point, p) < radius)
+            .count()
+    }
+}
@@ -1,7 +1,52 @@
+pub mod any_within;
+pub mod approx_nearest_n;
 pub mod approx_nearest_one;
 pub mod best_n_within;
+pub mod best_n_within_by;
+pub mod best_n_within_with_metric;
+pub mod best_n_within_with_point;
+pub mod count_within;
+pub mod farthest_n;
+pub mod knn_graph;
 pub mod nearest_n;
+pub mod nearest_n_budgeted;
+pub mod nearest_n_excluding;
+pub mod nearest_n_filtered;
+pub mod nearest_n_into;
+pub mod nearest_n_periodic;
+pub mod nearest_n_with_metric;
+pub mod nearest_n_with_point;
 pub mod nearest_n_within;
 pub mod nearest_one;
+pub mod nearest_one_budgeted;
+pub mod nearest_one_filtered;
+pub mod nearest_one_with_metric;
+pub mod nearest_one_with_point;
+pub mod reverse_nearest_n;
 pub mod within;
+pub mod within_annulus;
+pub mod within_box;
+pub mod within_box_unsorted;
+pub mod within_filtered;
+pub mod within_into;
+pub mod within_periodic;
+pub mod within_region;
 pub mod within_unsorted;
+pub mod within_unsorted_into;
+pub mod within_unsorted_with_metric;
+pub mod within_unsorted_with_point;
+pub mod within_with_metric;
+pub mod within_with_point;
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub mod nearest_iter;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub mod within_box_unsorted_iter;
+
+#[cfg(feature = "rayon")]
+pub mod nearest_n_batch;
+pub mod nearest_n_within_into_collection;
+#[cfg(feature = "rayon")]
+pub mod nearest_one_batch;
+#[cfg(feature = "rayon")]
+pub mod within_batch;
@@ -0,0 +1,184 @@
+use generator::{done, Gn};
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_iter::{NearestIter, NearestIterCandidate, NearestIterCandidateKind};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+
+use crate::generate_immutable_nearest_iter;
+
+macro_rules! generate_immutable_float_nearest_iter {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_nearest_iter!((
+            "Iterates over the elements of the tree in order of increasing distance
+from `query`, using the specified distance metric function.
+
+Only available on x86_64 and aarch64 target architectures (this is due to a dependency
+on the generator crate).
+Results are found lazily as the iterator is advanced, so there's no need to
+decide up front how many are required.
+
+# Examples
+
+```rust
+use kiddo::ImmutableKdTree;
+use kiddo::SquaredEuclidean;
+",
+            $doctest_build_tree,
+            "
+
+let mut nearest = tree.nearest_iter::<SquaredEuclidean>(&[1.0, 2.0, 5.1]);
+
+assert_eq!(nearest.next().unwrap().item, 0);
+assert_eq!(nearest.next().unwrap().item, 1);
+assert!(nearest.next().is_none());
+```"
+        ));
+    };
+}
+
+impl<'a, A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_nearest_iter!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        'a,
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_nearest_iter!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_iterate_nearest_items() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.51f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.22f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.33f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.44f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.15f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        let expected = vec![(0.17569996, 5), (0.19139998, 13), (0.24420004, 3)];
+
+        let result: Vec<_> = tree
+            .nearest_iter::<SquaredEuclidean>(&query_point)
+            .take(3)
+            .map(|n| (n.distance, n.item))
+            .collect();
+        assert_eq!(result, expected);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let expected = linear_search(&content_to_add, &query_point);
+
+            let result: Vec<_> = tree
+                .nearest_iter::<SquaredEuclidean>(&query_point)
+                .map(|n| n.distance)
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_iterate_nearest_items_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 100;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        let query_points: Vec<[AX; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[AX; 4]>())
+            .collect();
+
+        for query_point in query_points {
+            let expected: Vec<_> = linear_search(&content_to_add, &query_point)
+                .into_iter()
+                .take(N)
+                .collect();
+
+            let result: Vec<_> = tree
+                .nearest_iter::<SquaredEuclidean>(&query_point)
+                .take(N)
+                .map(|n| n.distance)
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(content: &[[A; K]], query_point: &[A; K]) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .map(|p| SquaredEuclidean::dist(query_point, p))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        dists
+    }
+}
@@ -0,0 +1,167 @@
+use az::Cast;
+
+use crate::batch_results::BatchResults;
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::float_leaf_simd::leaf_node::BestFromDists;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::types::Content;
+
+use crate::generate_nearest_n_batch;
+
+macro_rules! generate_immutable_float_nearest_n_batch {
+    ($doctest_build_tree:tt) => {
+        generate_nearest_n_batch!((
+            "Finds the nearest `qty` elements to each of `queries`, using the specified
+distance metric function, running the queries in parallel using Rayon.
+
+The results of all of the queries are returned together in a single [`BatchResults`](`crate::batch_results::BatchResults`),
+in the same order as `queries`, with each query's results sorted nearest-first.
+
+# Examples
+
+```rust
+    use kiddo::ImmutableKdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let nearest = tree.nearest_n_batch::<SquaredEuclidean>(&[[1.0, 2.0, 5.1], [2.0, 3.0, 6.1]], 2);
+
+    assert_eq!(nearest.len(), 2);
+    assert_eq!(nearest[0].len(), 2);
+    assert_eq!(nearest[0][0].item, 0);
+    assert_eq!(nearest[1][0].item, 1);
+```"
+        ));
+    };
+}
+
+impl<A, T, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B>,
+    T: Content,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_n_batch!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<A, T, const K: usize, const B: usize> ArchivedImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B> + rkyv::Archive<Archived = A>,
+    T: Content + rkyv::Archive<Archived = T>,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_n_batch!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::distance::Manhattan;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_n_batch() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.51f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.22f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.33f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.44f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.15f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let mut rng = rand::thread_rng();
+        let query_points: Vec<[AX; 4]> = (0..1000)
+            .map(|_| {
+                [
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                    rng.gen_range(0f32..1f32),
+                ]
+            })
+            .collect();
+
+        let result = tree.nearest_n_batch::<Manhattan>(&query_points, 5);
+
+        assert_eq!(result.len(), query_points.len());
+        assert_eq!(result.offsets.len(), query_points.len() + 1);
+        for (query_point, result) in query_points.iter().zip(result.iter()) {
+            // nearest_n and nearest_n_into can order equidistant items differently
+            let expected = tree.nearest_n::<Manhattan>(query_point, 5);
+            assert_eq!(
+                result.iter().map(|n| n.distance).collect::<Vec<_>>(),
+                expected.iter().map(|n| n.distance).collect::<Vec<_>>()
+            );
+        }
+
+        let result = tree.nearest_n_batch::<Manhattan>(&[], 5);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn can_query_nearest_n_batch_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 1_000;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        let query_points: Vec<[AX; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[AX; 4]>())
+            .collect();
+
+        let result = tree.nearest_n_batch::<Manhattan>(&query_points, 5);
+
+        assert_eq!(result.len(), query_points.len());
+        assert_eq!(result.offsets.len(), query_points.len() + 1);
+        for (query_point, result) in query_points.iter().zip(result.iter()) {
+            // nearest_n and nearest_n_into can order equidistant items differently
+            let expected = tree.nearest_n::<Manhattan>(query_point, 5);
+            assert_eq!(
+                result.iter().map(|n| n.distance).collect::<Vec<_>>(),
+                expected.iter().map(|n| n.distance).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::search_budget::{BudgetTracker, BudgetedResult, SearchBudget};
+use crate::types::Content;
+
+use crate::generate_immutable_nearest_n_budgeted;
+
+macro_rules! generate_immutable_float_nearest_n_budgeted {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_nearest_n_budgeted!((
+            "Finds the nearest `qty` elements to `query`, using the specified
+distance metric function, doing no more work than is allowed by `budget`.
+
+If the budget runs out before the search completes, the closest elements found so far
+are returned and `exact` is `false` in the returned [`BudgetedResult`]. Fewer than `qty`
+elements may be returned if the budget runs out before enough leaves have been searched.
+
+# Examples
+
+```rust
+    use kiddo::ImmutableKdTree;
+    use kiddo::SquaredEuclidean;
+    use kiddo::search_budget::SearchBudget;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let nearest = tree.nearest_n_budgeted::<SquaredEuclidean>(&[1.0, 2.0, 5.1], 1, SearchBudget::with_max_leaves(4));
+
+    assert!(nearest.exact);
+    assert_eq!(nearest.result.len(), 1);
+    assert!((nearest.result[0].distance - 0.01f64).abs() < f64::EPSILON);
+    assert_eq!(nearest.result[0].item, 0);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_nearest_n_budgeted!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_nearest_n_budgeted!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use crate::search_budget::SearchBudget;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_n_items_budgeted() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.51f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.22f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.33f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.44f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.15f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        let result =
+            tree.nearest_n_budgeted::<SquaredEuclidean>(&query_point, 3, SearchBudget::unlimited());
+        assert!(result.exact);
+        let result: Vec<_> = result.result.iter().map(|n| n.distance).collect();
+        assert_eq!(result, linear_search(&content_to_add, 3, &query_point));
+
+        let result = tree.nearest_n_budgeted::<SquaredEuclidean>(
+            &query_point,
+            3,
+            SearchBudget::with_max_leaves(0),
+        );
+        assert!(!result.exact);
+        assert!(result.result.is_empty());
+
+        let qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let budget = SearchBudget::new(rng.gen_range(0..6), rng.gen_range(0..8));
+            let expected = linear_search(&content_to_add, qty, &query_point);
+
+            let result = tree.nearest_n_budgeted::<SquaredEuclidean>(&query_point, qty, budget);
+            let result_dists: Vec<_> = result.result.iter().map(|n| n.distance).collect();
+
+            assert_budgeted_result(&result_dists, result.exact, &expected);
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_10_items_budgeted_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        let query_points: Vec<[AX; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[AX; 4]>())
+            .collect();
+
+        for query_point in query_points {
+            let expected = linear_search(&content_to_add, N, &query_point);
+
+            for budget in [
+                SearchBudget::unlimited(),
+                SearchBudget::with_max_leaves(4),
+                SearchBudget::new(usize::MAX, 20),
+            ] {
+                let result = tree.nearest_n_budgeted::<SquaredEuclidean>(&query_point, N, budget);
+                let result_dists: Vec<_> = result.result.iter().map(|n| n.distance).collect();
+
+                if budget == SearchBudget::unlimited() {
+                    assert!(result.exact);
+                }
+                assert_budgeted_result(&result_dists, result.exact, &expected);
+            }
+        }
+    }
+
+    fn assert_budgeted_result(result: &[AX], exact: bool, expected: &[AX]) {
+        if exact {
+            assert_eq!(result, expected);
+        } else {
+            assert!(result.len() <= expected.len());
+            for (result_dist, expected_dist) in result.iter().zip(expected) {
+                assert!(result_dist >= expected_dist);
+            }
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[[A; K]],
+        qty: usize,
+        query_point: &[A; K],
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .map(|p| SquaredEuclidean::dist(query_point, p))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(qty);
+
+        dists
+    }
+}
@@ -0,0 +1,179 @@
+use az::Cast;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::float_leaf_simd::leaf_node::BestFromDists;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+
+use crate::generate_immutable_nearest_n_excluding;
+
+macro_rules! generate_immutable_float_nearest_n_excluding {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_nearest_n_excluding!((
+            "Finds the nearest `qty` elements to `query` whose items are not in `exclude`,
+using the specified distance metric function.
+
+Excluded items are skipped during the search rather than removed afterwards,
+so up to `qty` other items are always returned. This is useful when querying
+the neighbours of an element that is itself stored in the tree.
+
+# Examples
+
+```rust
+use kiddo::ImmutableKdTree;
+use kiddo::SquaredEuclidean;
+
+",
+            $doctest_build_tree,
+            "
+
+let nearest: Vec<_> = tree.nearest_n_excluding::<SquaredEuclidean>(&[1.0, 2.0, 5.0], 1, &[0]);
+
+assert_eq!(nearest.len(), 1);
+assert_eq!(nearest[0].item, 1);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_nearest_n_excluding!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_nearest_n_excluding!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_n_excluding_items() {
+        let points: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.51f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.22f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.33f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.44f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.15f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        // every point is stored twice, under item idx and item idx + 16
+        let content_to_add: Vec<_> = points
+            .iter()
+            .chain(points.iter())
+            .enumerate()
+            .map(|(idx, point)| (*point, idx as u32))
+            .collect();
+
+        let all_points: Vec<_> = content_to_add.iter().map(|(point, _)| *point).collect();
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&all_points);
+
+        let qty = 5;
+        for (point, item) in &content_to_add {
+            let exclude = [*item];
+            let expected = linear_search(&content_to_add, qty, point, &exclude);
+
+            let result = tree.nearest_n_excluding::<SquaredEuclidean>(point, qty, &exclude);
+
+            let result_dists: Vec<_> = result.iter().map(|n| n.distance).collect();
+            assert_eq!(result_dists, expected);
+            assert!(result.iter().all(|n| n.item != *item));
+            assert_eq!(result[0].item, (item + 16) % 32);
+        }
+
+        let point = &content_to_add[0].0;
+        let exclude = [0, 16];
+        let expected = linear_search(&content_to_add, qty, point, &exclude);
+        let result = tree.nearest_n_excluding::<SquaredEuclidean>(point, qty, &exclude);
+        let result_dists: Vec<_> = result.iter().map(|n| n.distance).collect();
+        assert_eq!(result_dists, expected);
+        assert!(result.iter().all(|n| !exclude.contains(&n.item)));
+    }
+
+    #[test]
+    fn can_query_nearest_n_excluding_items_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let points: Vec<[AX; 4]> = (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+        let content_to_add: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(idx, point)| (*point, idx as u32))
+            .collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> = ImmutableKdTree::new_from_slice(&points);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        for (point, item) in content_to_add.iter().take(NUM_QUERIES) {
+            let exclude = [*item];
+            let expected = linear_search(&content_to_add, N, point, &exclude);
+
+            let result = tree.nearest_n_excluding::<SquaredEuclidean>(point, N, &exclude);
+
+            let result_dists: Vec<_> = result.iter().map(|n| n.distance).collect();
+            assert_eq!(result_dists, expected);
+            assert!(result.iter().all(|n| n.item != *item));
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[([A; K], u32)],
+        qty: usize,
+        query_point: &[A; K],
+        exclude: &[u32],
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .filter(|(_, item)| !exclude.contains(item))
+            .map(|(p, _)| SquaredEuclidean::dist(query_point, p))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(qty);
+
+        dists
+    }
+}
@@ -0,0 +1,203 @@
+use num_traits::Zero;
+use std::collections::BinaryHeap;
+use std::ops::Rem;
+
+use crate::distance_metric::DistanceMetric;
+use crate::float::kdtree::Axis;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+
+use crate::generate_immutable_nearest_n_periodic;
+
+macro_rules! generate_immutable_float_nearest_n_periodic {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_nearest_n_periodic!((
+            "Finds the nearest `qty` elements to `query` in a periodic space, using the
+specified distance metric function.
+
+The space wraps around along every axis, with `box_size` giving the length of the
+box along each one. All points in the tree, as well as `query`, are expected to lie
+within `0..box_size`. Distances are measured to the nearest periodic image of each
+point, so neighbours on the far side of the box edge are found without needing to
+store duplicates of the points near the edges. Results are sorted nearest-first.
+
+# Examples
+
+```rust
+use kiddo::ImmutableKdTree;
+use kiddo::SquaredEuclidean;
+",
+            $doctest_build_tree,
+            "
+
+let nearest = tree.nearest_n_periodic::<SquaredEuclidean>(&[9.5, 2.0, 5.0], 1, &[10.0, 10.0, 10.0]);
+
+assert_eq!(nearest.len(), 1);
+assert!((nearest[0].distance - 2.25f64).abs() < f64::EPSILON);
+assert_eq!(nearest[0].item, 0);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_nearest_n_periodic!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_nearest_n_periodic!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_n_items_periodic() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.5f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.2f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.3f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.4f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.1f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let box_size = [1.0f32; 4];
+        let qty = 5;
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let expected = linear_search(&content_to_add, &query_point, qty, &box_size);
+
+            let result: Vec<_> = tree
+                .nearest_n_periodic::<SquaredEuclidean>(&query_point, qty, &box_size)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_n_items_periodic_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+        const N: usize = 10;
+
+        let box_size = [1.0f32, 2.0f32, 0.5f32, 1.5f32];
+        let random_point = || -> [AX; 4] {
+            let point = rand::random::<[AX; 4]>();
+            std::array::from_fn(|dim| point[dim] * box_size[dim])
+        };
+
+        let content_to_add: Vec<[AX; 4]> = (0..TREE_SIZE).map(|_| random_point()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        for _ in 0..NUM_QUERIES {
+            let query_point = random_point();
+            let expected = linear_search(&content_to_add, &query_point, N, &box_size);
+
+            let result: Vec<_> = tree
+                .nearest_n_periodic::<SquaredEuclidean>(&query_point, N, &box_size)
+                .into_iter()
+                .map(|n| n.distance)
+                .collect();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[[A; K]],
+        query: &[A; K],
+        qty: usize,
+        box_size: &[A; K],
+    ) -> Vec<A> {
+        let mut dists: Vec<A> = content
+            .iter()
+            .map(|p| SquaredEuclidean::dist(query, &nearest_image(query, p, box_size)))
+            .collect();
+
+        dists.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        dists.truncate(qty);
+
+        dists
+    }
+
+    /// Returns the image of `point` nearest to `query`, picking whichever of the
+    /// three candidate positions along each axis is closest.
+    fn nearest_image<A: Axis, const K: usize>(
+        query: &[A; K],
+        point: &[A; K],
+        box_size: &[A; K],
+    ) -> [A; K] {
+        std::array::from_fn(|dim| {
+            [
+                point[dim] - box_size[dim],
+                point[dim],
+                point[dim] + box_size[dim],
+            ]
+            .into_iter()
+            .min_by(|a, b| {
+                (*a - query[dim])
+                    .abs()
+                    .partial_cmp(&(*b - query[dim]).abs())
+                    .unwrap()
+            })
+            .unwrap()
+        })
+    }
+}
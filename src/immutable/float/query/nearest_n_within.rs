@@ -1,14 +1,17 @@
 use az::Cast;
+use num_traits::float::FloatCore;
+use num_traits::Zero;
 use sorted_vec::SortedVec;
 use std::collections::BinaryHeap;
 use std::ops::Rem;
 
-use crate::distance_metric::DistanceMetric;
+use crate::distance_metric::{DistanceMetric, StatefulDistanceMetric, StatelessMetric};
 use crate::float::kdtree::Axis;
-use crate::float::result_collection::ResultCollection;
 use crate::float_leaf_simd::leaf_node::BestFromDists;
 use crate::immutable::float::kdtree::ImmutableKdTree;
 use crate::nearest_neighbour::NearestNeighbour;
+use crate::neighbour::FromLeafEntry;
+use crate::result_collection::ResultCollection;
 use crate::types::Content;
 
 use crate::generate_immutable_nearest_n_within;
@@ -0,0 +1,144 @@
+use az::Cast;
+use num_traits::Zero;
+
+use crate::distance_metric::{DistanceMetric, StatelessMetric};
+use crate::float::kdtree::Axis;
+use crate::float_leaf_simd::leaf_node::BestFromDists;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::result_collection::ResultCollection;
+use crate::types::Content;
+
+use crate::generate_immutable_nearest_n_within_into_collection;
+
+macro_rules! generate_immutable_float_nearest_n_within_into_collection {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_nearest_n_within_into_collection!((
+            "Finds the elements within `dist` of `query`, using the specified
+distance metric function, and adds them to `results`.
+
+Behaves like [`nearest_n_within`](Self::nearest_n_within), but the results are kept by
+any [`ResultCollection`](crate::result_collection::ResultCollection), which decides which
+of them to keep and how to store them. `results` is not cleared first.
+
+# Examples
+
+```rust
+use kiddo::ImmutableKdTree;
+use kiddo::SquaredEuclidean;
+use kiddo::nearest_neighbour::NearestNeighbour;
+",
+            $doctest_build_tree,
+            "
+
+let mut results: Vec<NearestNeighbour<f64, u64>> = Vec::new();
+tree.nearest_n_within_into_collection::<SquaredEuclidean, _>(&[1.0, 2.0, 5.1], 10f64, &mut results);
+
+assert_eq!(results.len(), 2);
+```"
+        ));
+    };
+}
+
+impl<A, T, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B>,
+    T: Content,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_n_within_into_collection!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<A, T, const K: usize, const B: usize> ArchivedImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B> + rkyv::Archive<Archived = A>,
+    T: Content + rkyv::Archive<Archived = T>,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_n_within_into_collection!(
+        "use std::fs::File;
+use memmap::MmapOptions;
+
+let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::distance::SquaredEuclidean;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use crate::nearest_neighbour::NearestNeighbour;
+    use rand::Rng;
+    use sorted_vec::SortedVec;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_items_within_radius_into_collection() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.5f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.2f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.3f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.4f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.1f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let radius = rng.gen_range(0f32..1f32);
+            let max_qty = rng.gen_range(1usize..10usize);
+
+            let mut expected = tree.within_unsorted::<SquaredEuclidean>(&query_point, radius);
+            expected.sort();
+            let mut results: Vec<NearestNeighbour<AX, u32>> = Vec::new();
+            tree.nearest_n_within_into_collection::<SquaredEuclidean, _>(
+                &query_point,
+                radius,
+                &mut results,
+            );
+            results.sort();
+            assert_eq!(results, expected);
+
+            let expected =
+                tree.nearest_n_within::<SquaredEuclidean>(&query_point, radius, max_qty, true);
+            let mut results = SortedVec::with_capacity(max_qty);
+            tree.nearest_n_within_into_collection::<SquaredEuclidean, _>(
+                &query_point,
+                radius,
+                &mut results,
+            );
+            assert_eq!(results.into_vec(), expected);
+        }
+    }
+}
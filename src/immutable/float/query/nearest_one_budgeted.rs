@@ -0,0 +1,207 @@
+use az::Cast;
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+use std::ops::Rem;
+
+use crate::distance_metric::{DistanceMetric, StatelessMetric};
+use crate::float::kdtree::Axis;
+use crate::float_leaf_simd::leaf_node::BestFromDists;
+use crate::generate_immutable_nearest_one_budgeted;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::search_budget::{BudgetTracker, BudgetedResult, SearchBudget};
+use crate::types::Content;
+
+macro_rules! generate_immutable_float_nearest_one_budgeted {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_nearest_one_budgeted!((
+            "Finds the nearest element to `query`, using the specified
+distance metric function, doing no more work than is allowed by `budget`.
+
+If the budget runs out before the search completes, the closest element found so far
+is returned and `exact` is `false` in the returned [`BudgetedResult`]. If the budget
+runs out before any leaves have been searched, the returned result is `None`.
+
+# Examples
+
+```rust
+    use kiddo::ImmutableKdTree;
+    use kiddo::SquaredEuclidean;
+    use kiddo::search_budget::SearchBudget;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let nearest = tree.nearest_one_budgeted::<SquaredEuclidean>(&[1.0, 2.0, 5.1], SearchBudget::with_max_leaves(4));
+
+    assert!(nearest.exact);
+    assert!((nearest.result.unwrap().distance - 0.01f64).abs() < f64::EPSILON);
+    assert_eq!(nearest.result.unwrap().item, 0);
+```"
+        ));
+    };
+}
+
+impl<A, T, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B>,
+    T: Content,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_one_budgeted!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<A, T, const K: usize, const B: usize> ArchivedImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B> + rkyv::Archive<Archived = A>,
+    T: Content + rkyv::Archive<Archived = T>,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_one_budgeted!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use crate::search_budget::SearchBudget;
+    use rand::Rng;
+
+    type AX = f32;
+
+    #[test]
+    fn can_query_nearest_one_item_budgeted() {
+        let content_to_add: [[AX; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.51f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.22f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.33f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.44f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.15f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<AX, u32, 4, 4> = ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f32, 0.55f32, 0.78f32, 0.55f32];
+
+        let result =
+            tree.nearest_one_budgeted::<SquaredEuclidean>(&query_point, SearchBudget::unlimited());
+        assert!(result.exact);
+        assert_eq!(
+            result.result.unwrap().distance,
+            linear_search(&content_to_add, &query_point)
+        );
+
+        let result = tree.nearest_one_budgeted::<SquaredEuclidean>(
+            &query_point,
+            SearchBudget::with_max_leaves(0),
+        );
+        assert!(!result.exact);
+        assert_eq!(result.result, None);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+                rng.gen_range(0f32..1f32),
+            ];
+            let budget = SearchBudget::new(rng.gen_range(0..6), rng.gen_range(0..8));
+            let expected = linear_search(&content_to_add, &query_point);
+
+            let result = tree.nearest_one_budgeted::<SquaredEuclidean>(&query_point, budget);
+
+            if result.exact {
+                assert_eq!(result.result.unwrap().distance, expected);
+            } else {
+                assert!(result
+                    .result
+                    .is_none_or(|nearest| nearest.distance >= expected));
+            }
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_one_item_budgeted_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<[AX; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[AX; 4]>()).collect();
+
+        let tree: ImmutableKdTree<AX, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        let query_points: Vec<[AX; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[AX; 4]>())
+            .collect();
+
+        for query_point in query_points {
+            let expected = linear_search(&content_to_add, &query_point);
+
+            let result = tree
+                .nearest_one_budgeted::<SquaredEuclidean>(&query_point, SearchBudget::unlimited());
+            assert!(result.exact);
+            assert_eq!(result.result.unwrap().distance, expected);
+
+            let result = tree.nearest_one_budgeted::<SquaredEuclidean>(
+                &query_point,
+                SearchBudget::with_max_leaves(4),
+            );
+            if result.exact {
+                assert_eq!(result.result.unwrap().distance, expected);
+            } else {
+                assert!(result
+                    .result
+                    .is_none_or(|nearest| nearest.distance >= expected));
+            }
+            assert!(result.result.is_some());
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize>(content: &[[A; K]], query_point: &[A; K]) -> A {
+        let mut best_dist: A = A::infinity();
+
+        for p in content {
+            let dist = SquaredEuclidean::dist(query_point, p);
+            if dist < best_dist {
+                best_dist = dist;
+            }
+        }
+
+        best_dist
+    }
+}
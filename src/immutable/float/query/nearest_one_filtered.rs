@@ -0,0 +1,197 @@
+use az::Cast;
+use num_traits::float::FloatCore;
+use num_traits::Zero;
+
+use crate::distance_metric::{DistanceMetric, StatelessMetric};
+use crate::float::kdtree::Axis;
+use crate::float_leaf_simd::leaf_node::BestFromDists;
+use crate::generate_immutable_nearest_one_filtered;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+
+macro_rules! generate_immutable_float_nearest_one_filtered {
+    ($doctest_build_tree:tt) => {
+        generate_immutable_nearest_one_filtered!((
+            "Queries the tree to find the nearest item to the `query` point whose
+item satisfies `filter`.
+
+Items that are rejected by `filter` are ignored entirely, so they don't
+prevent the search from pruning subtrees. Returns `None` if `filter`
+rejects every item in the tree.
+
+# Examples
+
+```rust
+    use kiddo::ImmutableKdTree;
+    use kiddo::SquaredEuclidean;
+
+    ",
+            $doctest_build_tree,
+            "
+
+    let nearest = tree.nearest_one_filtered::<SquaredEuclidean, _>(&[1.0, 2.0, 5.1], |item| item != 0).unwrap();
+
+    assert_eq!(nearest.item, 1);
+
+    let none = tree.nearest_one_filtered::<SquaredEuclidean, _>(&[1.0, 2.0, 5.1], |_| false);
+    assert!(none.is_none());
+```"
+        ));
+    };
+}
+
+impl<A, T, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B>,
+    T: Content,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_one_filtered!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<A, T, const K: usize, const B: usize> ArchivedImmutableKdTree<A, T, K, B>
+where
+    A: Axis + BestFromDists<T, B> + rkyv::Archive<Archived = A>,
+    T: Content + rkyv::Archive<Archived = T>,
+    usize: Cast<T>,
+{
+    generate_immutable_float_nearest_one_filtered!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance_metric::DistanceMetric;
+    use crate::float::distance::SquaredEuclidean;
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use crate::nearest_neighbour::NearestNeighbour;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn can_query_nearest_one_filtered_item() {
+        let content_to_add: [[f64; 4]; 16] = [
+            [0.9f64, 0.0f64, 0.9f64, 0.0f64],
+            [0.4f64, 0.5f64, 0.4f64, 0.51f64],
+            [0.12f64, 0.3f64, 0.12f64, 0.3f64],
+            [0.7f64, 0.2f64, 0.7f64, 0.22f64],
+            [0.13f64, 0.4f64, 0.13f64, 0.4f64],
+            [0.6f64, 0.3f64, 0.6f64, 0.33f64],
+            [0.2f64, 0.7f64, 0.2f64, 0.7f64],
+            [0.14f64, 0.5f64, 0.14f64, 0.5f64],
+            [0.3f64, 0.6f64, 0.3f64, 0.6f64],
+            [0.10f64, 0.1f64, 0.10f64, 0.1f64],
+            [0.16f64, 0.7f64, 0.16f64, 0.7f64],
+            [0.1f64, 0.8f64, 0.1f64, 0.8f64],
+            [0.15f64, 0.6f64, 0.15f64, 0.6f64],
+            [0.5f64, 0.4f64, 0.5f64, 0.44f64],
+            [0.8f64, 0.1f64, 0.8f64, 0.15f64],
+            [0.11f64, 0.2f64, 0.11f64, 0.2f64],
+        ];
+
+        let tree: ImmutableKdTree<f64, u32, 4, 4> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let query_point = [0.78f64, 0.55f64, 0.78f64, 0.55f64];
+        let expected = linear_search(&content_to_add, &query_point, |item| item != 5);
+
+        let result = tree
+            .nearest_one_filtered::<SquaredEuclidean, _>(&query_point, |item| item != 5)
+            .unwrap();
+        assert_eq!(result.distance, expected.distance);
+        assert_eq!(result.item as usize, expected.item);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let query_point = [
+                rng.gen_range(0f64..1f64),
+                rng.gen_range(0f64..1f64),
+                rng.gen_range(0f64..1f64),
+                rng.gen_range(0f64..1f64),
+            ];
+            let divisor = rng.gen_range(1u32..5u32);
+            let expected = linear_search(&content_to_add, &query_point, |item| {
+                item as u32 % divisor == divisor - 1
+            });
+
+            let result = tree
+                .nearest_one_filtered::<SquaredEuclidean, _>(&query_point, |item| {
+                    item % divisor == divisor - 1
+                })
+                .unwrap();
+
+            assert_eq!(result.distance, expected.distance);
+            assert_eq!(result.item % divisor, divisor - 1);
+        }
+    }
+
+    #[test]
+    fn can_query_nearest_one_filtered_item_large_scale() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(3);
+
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<[f32; 4]> = (0..TREE_SIZE).map(|_| rng.gen::<[f32; 4]>()).collect();
+
+        let tree: ImmutableKdTree<f32, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        let query_points: Vec<[f32; 4]> = (0..NUM_QUERIES)
+            .map(|_| rand::random::<[f32; 4]>())
+            .collect();
+
+        for query_point in query_points.iter() {
+            let expected = linear_search(&content_to_add, query_point, |item| item % 7 == 3);
+
+            let result = tree
+                .nearest_one_filtered::<SquaredEuclidean, _>(query_point, |item| item % 7 == 3)
+                .unwrap();
+
+            assert_eq!(result.distance, expected.distance);
+            assert_eq!(result.item as usize, expected.item);
+        }
+    }
+
+    fn linear_search<A: Axis, const K: usize, F: Fn(usize) -> bool>(
+        content: &[[A; K]],
+        query_point: &[A; K],
+        filter: F,
+    ) -> NearestNeighbour<A, usize> {
+        let mut best_dist: A = A::infinity();
+        let mut best_item: usize = usize::MAX;
+
+        for (idx, p) in content.iter().enumerate() {
+            let dist = SquaredEuclidean::dist(query_point, p);
+            if dist < best_dist && filter(idx) {
+                best_item = idx;
+                best_dist = dist;
+            }
+        }
+
+        NearestNeighbour {
+            distance: best_dist,
+            item: best_item,
+        }
+    }
+}
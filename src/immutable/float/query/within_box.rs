@@ -0,0 +1,170 @@
+use crate::float::kdtree::Axis;
+use crate::immutable::float::kdtree::ImmutableKdTree;
+use crate::types::Content;
+
+use crate::generate_within_box;
+
+macro_rules! generate_immutable_float_within_box {
+    ($doctest_build_tree:tt) => {
+        generate_within_box!((
+            "Finds all elements whose points lie within the axis-aligned box
+spanning from `min` to `max` (inclusive on all sides).
+
+Results are returned sorted by item.
+
+# Examples
+
+```rust
+use kiddo::ImmutableKdTree;
+",
+            $doctest_build_tree,
+            "
+
+let within = tree.within_box(&[0.0, 0.0, 0.0], &[1.5, 2.5, 5.5]);
+
+assert_eq!(within, vec![0]);
+```"
+        ));
+    };
+}
+
+impl<A: Axis, T: Content, const K: usize, const B: usize> ImmutableKdTree<A, T, K, B> {
+    generate_immutable_float_within_box!(
+        "let content: Vec<[f64; 3]> = vec!(
+            [1.0, 2.0, 5.0],
+            [2.0, 3.0, 6.0]
+        );
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&content);"
+    );
+}
+
+#[cfg(feature = "rkyv")]
+use crate::immutable::float::kdtree::ArchivedImmutableKdTree;
+#[cfg(feature = "rkyv")]
+impl<
+        A: Axis + rkyv::Archive<Archived = A>,
+        T: Content + rkyv::Archive<Archived = T>,
+        const K: usize,
+        const B: usize,
+    > ArchivedImmutableKdTree<A, T, K, B>
+{
+    generate_immutable_float_within_box!(
+        "use std::fs::File;
+    use memmap::MmapOptions;
+
+    let mmap = unsafe { MmapOptions::new().map(&File::open(\"./examples/immutable-doctest-tree.rkyv\").unwrap()).unwrap() };
+    let tree = unsafe { rkyv::archived_root::<ImmutableKdTree<f64, 3>>(&mmap) };"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::kdtree::Axis;
+    use crate::immutable::float::kdtree::ImmutableKdTree;
+    use rand::Rng;
+
+    #[test]
+    fn can_query_items_within_box() {
+        let content_to_add: [[f32; 4]; 16] = [
+            [0.9f32, 0.0f32, 0.9f32, 0.0f32],
+            [0.4f32, 0.5f32, 0.4f32, 0.51f32],
+            [0.12f32, 0.3f32, 0.12f32, 0.3f32],
+            [0.7f32, 0.2f32, 0.7f32, 0.22f32],
+            [0.13f32, 0.4f32, 0.13f32, 0.4f32],
+            [0.6f32, 0.3f32, 0.6f32, 0.33f32],
+            [0.2f32, 0.7f32, 0.2f32, 0.7f32],
+            [0.14f32, 0.5f32, 0.14f32, 0.5f32],
+            [0.3f32, 0.6f32, 0.3f32, 0.6f32],
+            [0.10f32, 0.1f32, 0.10f32, 0.1f32],
+            [0.16f32, 0.7f32, 0.16f32, 0.7f32],
+            [0.1f32, 0.8f32, 0.1f32, 0.8f32],
+            [0.15f32, 0.6f32, 0.15f32, 0.6f32],
+            [0.5f32, 0.4f32, 0.5f32, 0.44f32],
+            [0.8f32, 0.1f32, 0.8f32, 0.15f32],
+            [0.11f32, 0.2f32, 0.11f32, 0.2f32],
+        ];
+
+        let tree: ImmutableKdTree<f32, u32, 4, 4> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        assert_eq!(tree.size(), 16);
+
+        let min = [0.4f32, 0.2f32, 0.4f32, 0.2f32];
+        let max = [0.7f32, 0.52f32, 0.7f32, 0.52f32];
+
+        let result = tree.within_box(&min, &max);
+        assert_eq!(result, vec![1, 3, 5, 13]);
+
+        let mut rng = rand::thread_rng();
+        for _i in 0..1000 {
+            let (min, max) = random_box(&mut rng);
+            let expected = linear_search(&content_to_add, &min, &max);
+
+            let result = tree.within_box(&min, &max);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_box_large_scale() {
+        const TREE_SIZE: usize = 100_000;
+        const NUM_QUERIES: usize = 100;
+
+        let content_to_add: Vec<[f32; 4]> =
+            (0..TREE_SIZE).map(|_| rand::random::<[f32; 4]>()).collect();
+
+        let tree: ImmutableKdTree<f32, u32, 4, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+        assert_eq!(tree.size(), TREE_SIZE);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..NUM_QUERIES {
+            let (min, max) = random_box(&mut rng);
+            let expected = linear_search(&content_to_add, &min, &max);
+
+            let result = tree.within_box(&min, &max);
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn can_query_items_within_unbounded_box() {
+        let content_to_add: Vec<[f64; 2]> =
+            (0..100).map(|i| [i as f64, (i * 7 % 13) as f64]).collect();
+
+        let tree: ImmutableKdTree<f64, u32, 2, 32> =
+            ImmutableKdTree::new_from_slice(&content_to_add);
+
+        let result = tree.within_box(&[f64::NEG_INFINITY; 2], &[f64::INFINITY; 2]);
+
+        assert_eq!(result, (0..100).collect::<Vec<u32>>());
+    }
+
+    fn random_box<R: Rng>(rng: &mut R) -> ([f32; 4], [f32; 4]) {
+        let mut min = [0f32; 4];
+        let mut max = [0f32; 4];
+        for dim in 0..4 {
+            let a = rng.gen_range(0f32..1f32);
+            let b = rng.gen_range(0f32..1f32);
+            min[dim] = a.min(b);
+            max[dim] = a.max(b);
+        }
+        (min, max)
+    }
+
+    fn linear_search<A: Axis, const K: usize>(
+        content: &[[A; K]],
+        min: &[A; K],
+        max: &[A; K],
+    ) -> Vec<u32> {
+        content
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| (0..K).all(|dim| p[dim] >= min[dim] && p[dim] <= max[dim]))
+            .map(|(idx, _)| idx as u32)
+            .collect()
+    }
+}
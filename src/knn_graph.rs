@@ -0,0 +1,135 @@
+//! Compressed sparse row adjacency structure returned by `knn_graph`
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+
+/// The `k` nearest neighbours of every item in a tree, stored in compressed sparse row (CSR) form.
+///
+/// There is one row per stored item. Rows are sorted by item, and the neighbours of the
+/// item in row `r` are `neighbours[offsets[r]..offsets[r + 1]]`, sorted nearest-first.
+/// If the same item has been stored more than once, it will have more than one row.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::KdTree;
+/// use kiddo::SquaredEuclidean;
+///
+/// let mut tree: KdTree<f64, 2> = KdTree::new();
+/// tree.add(&[0.0, 0.0], 100);
+/// tree.add(&[1.0, 0.0], 101);
+/// tree.add(&[5.0, 0.0], 102);
+///
+/// let graph = tree.knn_graph::<SquaredEuclidean>(1, true);
+///
+/// assert_eq!(graph.len(), 3);
+/// assert_eq!(graph.neighbours_of(100).unwrap()[0].item, 101);
+/// assert_eq!(graph.neighbours_of(102).unwrap()[0].item, 101);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KnnGraph<A, T> {
+    /// the item that each row belongs to, in ascending order
+    pub items: Vec<T>,
+    /// the index into `neighbours` at which each row starts,
+    /// followed by the total number of neighbours
+    pub offsets: Vec<usize>,
+    /// the neighbours of every row, one row after the other
+    pub neighbours: Vec<NearestNeighbour<A, T>>,
+}
+
+impl<A, T: Content> KnnGraph<A, T> {
+    pub(crate) fn from_rows(mut rows: Vec<(T, Vec<NearestNeighbour<A, T>>)>) -> Self {
+        rows.sort_by_key(|(item, _)| *item);
+
+        let mut items = Vec::with_capacity(rows.len());
+        let mut offsets = Vec::with_capacity(rows.len() + 1);
+        offsets.push(0);
+        let mut total = 0;
+        for (item, row) in &rows {
+            items.push(*item);
+            total += row.len();
+            offsets.push(total);
+        }
+
+        let mut neighbours = Vec::with_capacity(total);
+        for (_, row) in rows {
+            neighbours.extend(row);
+        }
+
+        KnnGraph {
+            items,
+            offsets,
+            neighbours,
+        }
+    }
+
+    /// returns the number of rows in the graph
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// returns `true` if the graph has no rows
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// returns the neighbours in row `row`, or `None` if out of range
+    pub fn row(&self, row: usize) -> Option<&[NearestNeighbour<A, T>]> {
+        if row >= self.len() {
+            return None;
+        }
+
+        Some(&self.neighbours[self.offsets[row]..self.offsets[row + 1]])
+    }
+
+    /// returns the neighbours of `item`, or `None` if it is not in the graph.
+    ///
+    /// If `item` has more than one row, any one of them may be returned.
+    pub fn neighbours_of(&self, item: T) -> Option<&[NearestNeighbour<A, T>]> {
+        let row = self.items.binary_search(&item).ok()?;
+
+        self.row(row)
+    }
+
+    /// returns an iterator over each row's item and its neighbours
+    pub fn iter(&self) -> impl Iterator<Item = (T, &[NearestNeighbour<A, T>])> {
+        self.items
+            .iter()
+            .zip(self.offsets.windows(2))
+            .map(|(item, bounds)| (*item, &self.neighbours[bounds[0]..bounds[1]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::knn_graph::KnnGraph;
+    use crate::nearest_neighbour::NearestNeighbour;
+
+    fn nn(distance: f32, item: u32) -> NearestNeighbour<f32, u32> {
+        NearestNeighbour { distance, item }
+    }
+
+    #[test]
+    fn can_build_from_unsorted_rows() {
+        let graph = KnnGraph::from_rows(vec![
+            (3, vec![nn(0.5, 1)]),
+            (1, vec![nn(0.1, 2), nn(0.5, 3)]),
+            (2, vec![]),
+        ]);
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.items, vec![1, 2, 3]);
+        assert_eq!(graph.offsets, vec![0, 2, 2, 3]);
+        assert_eq!(graph.neighbours_of(1).unwrap(), [nn(0.1, 2), nn(0.5, 3)]);
+        assert!(graph.neighbours_of(2).unwrap().is_empty());
+        assert_eq!(graph.neighbours_of(4), None);
+        assert_eq!(graph.row(2).unwrap(), [nn(0.5, 1)]);
+        assert_eq!(graph.row(3), None);
+        assert_eq!(
+            graph
+                .iter()
+                .map(|(item, row)| (item, row.len()))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (2, 0), (3, 1)]
+        );
+    }
+}
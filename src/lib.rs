@@ -33,7 +33,7 @@
 //! Add `kiddo` to `Cargo.toml`
 //! ```toml
 //! [dependencies]
-//! kiddo = "4.2.0"
+//! kiddo = "5.0.0"
 //! ```
 //!
 //! ## Usage
@@ -78,10 +78,14 @@
 //! * `global_allocate` **(NIGHTLY)** -  When enabled Kiddo will use the unstable allocator_api feature within [`ImmutableKdTree`](`immutable::float::kdtree::ImmutableKdTree`) to get a slight performance improvement when allocating space for leaves.
 //! * `simd` **(NIGHTLY)** - enables some hand written SIMD and pre-fetch intrinsics code within [`ImmutableKdTree`](`immutable::float::kdtree::ImmutableKdTree`) that may improve performance (currently only on nearest_one with `f64`)
 //! * `f16` - enables usage of `f16` from the `half` crate for float trees.
+//! * `rayon` - enables the `nearest_one_batch`, `nearest_n_batch` and `within_batch` methods, which run many queries in parallel using [`Rayon`](https://docs.rs/rayon/latest/rayon/)
 
 #[macro_use]
 extern crate doc_comment;
 
+#[cfg(feature = "rayon")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "rayon")))]
+pub mod batch_results;
 pub mod best_neighbour;
 #[doc(hidden)]
 pub(crate) mod common;
@@ -89,11 +93,19 @@ pub(crate) mod common;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serialize")))]
 mod custom_serde;
 pub mod distance_metric;
+pub mod dual_tree;
 pub mod fixed;
 pub mod float;
+pub mod geo;
 pub mod immutable;
+pub mod knn_graph;
 mod mirror_select_nth_unstable_by;
 pub mod nearest_neighbour;
+pub mod neighbour;
+pub mod point_neighbour;
+pub mod query_region;
+pub mod result_collection;
+pub mod search_budget;
 #[doc(hidden)]
 #[cfg(feature = "test_utils")]
 pub mod test_utils;
@@ -101,6 +113,8 @@ pub mod types;
 
 mod iter;
 
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub mod nearest_iter;
 #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 pub mod within_unsorted_iter;
 
@@ -130,3 +144,4 @@ pub use best_neighbour::BestNeighbour;
 pub use float::distance::Manhattan;
 pub use float::distance::SquaredEuclidean;
 pub use nearest_neighbour::NearestNeighbour;
+pub use point_neighbour::PointNeighbour;
@@ -0,0 +1,78 @@
+//! Iterator object returned by nearest_iter
+use crate::nearest_neighbour::NearestNeighbour;
+use generator::Generator;
+use std::cmp::Ordering;
+
+/// Iterator object returned by nearest_iter
+pub struct NearestIter<'a, A, T>(Generator<'a, (), NearestNeighbour<A, T>>);
+
+impl<'a, A, T> NearestIter<'a, A, T> {
+    pub(crate) fn new(gen: Generator<'a, (), NearestNeighbour<A, T>>) -> Self {
+        NearestIter(gen)
+    }
+}
+
+impl<'a, A, T> Iterator for NearestIter<'a, A, T> {
+    type Item = NearestNeighbour<A, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/// An entry in the priority queue used by nearest_iter: either a subtree that
+/// has yet to be visited, or an item whose exact distance is already known.
+/// `distance` is a lower bound for subtrees and the exact distance for items.
+/// `O` is the distance type of the metric, which may differ from the axis type `A`.
+pub(crate) struct NearestIterCandidate<A, T, IDX, const K: usize, O> {
+    pub(crate) distance: O,
+    pub(crate) kind: NearestIterCandidateKind<A, T, IDX, K>,
+}
+
+pub(crate) enum NearestIterCandidateKind<A, T, IDX, const K: usize> {
+    Node {
+        node_idx: IDX,
+        split_dim: usize,
+        off: [A; K],
+        min: [A; K],
+        max: [A; K],
+    },
+    Item(T),
+}
+
+// Ordered so that a `BinaryHeap` pops the closest candidate first. Items are
+// popped ahead of subtrees at the same distance as nothing in the subtree can
+// be closer than them.
+impl<A, T, IDX, const K: usize, O: PartialOrd> Ord for NearestIterCandidate<A, T, IDX, K, O> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| match (&self.kind, &other.kind) {
+                (NearestIterCandidateKind::Item(_), NearestIterCandidateKind::Node { .. }) => {
+                    Ordering::Greater
+                }
+                (NearestIterCandidateKind::Node { .. }, NearestIterCandidateKind::Item(_)) => {
+                    Ordering::Less
+                }
+                _ => Ordering::Equal,
+            })
+    }
+}
+
+impl<A, T, IDX, const K: usize, O: PartialOrd> PartialOrd
+    for NearestIterCandidate<A, T, IDX, K, O>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A, T, IDX, const K: usize, O: PartialOrd> Eq for NearestIterCandidate<A, T, IDX, K, O> {}
+
+impl<A, T, IDX, const K: usize, O: PartialOrd> PartialEq for NearestIterCandidate<A, T, IDX, K, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
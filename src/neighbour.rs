@@ -0,0 +1,115 @@
+//! Traits shared by the result types that queries return
+use crate::best_neighbour::{BestNeighbour, ScoredNeighbour};
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::point_neighbour::PointNeighbour;
+use crate::types::Content;
+
+/// An entry in the results of a query, ordered the way the query ranks its results.
+///
+/// Implemented by [`NearestNeighbour`], [`BestNeighbour`] and [`PointNeighbour`], so
+/// that a [`ResultCollection`](crate::result_collection::ResultCollection) can hold any of them.
+pub trait Neighbour<O, T>: Copy + Ord {
+    /// the distance of the found item from the query point
+    fn distance(&self) -> O;
+    /// the stored index of the found item
+    fn item(&self) -> T;
+}
+
+/// A [`Neighbour`] that the search recursions can build from a matching leaf entry,
+/// so that the queries returning [`NearestNeighbour`] and [`PointNeighbour`] share
+/// a single implementation.
+pub(crate) trait FromLeafEntry<A, T, const K: usize, O>: Neighbour<O, T> {
+    /// Whether the point is kept. Leaf searches that only report the item of the
+    /// nearest entry, such as the SIMD search of the immutable tree, can only be
+    /// used when it isn't.
+    const KEEPS_POINT: bool;
+
+    fn from_leaf_entry(distance: O, item: T, point: &[A; K]) -> Self;
+}
+
+impl<O: Copy + PartialOrd, T: Content> Neighbour<O, T> for NearestNeighbour<O, T> {
+    fn distance(&self) -> O {
+        self.distance
+    }
+    fn item(&self) -> T {
+        self.item
+    }
+}
+
+impl<A, T: Content, const K: usize, O: Copy + PartialOrd> FromLeafEntry<A, T, K, O>
+    for NearestNeighbour<O, T>
+{
+    const KEEPS_POINT: bool = false;
+
+    fn from_leaf_entry(distance: O, item: T, _point: &[A; K]) -> Self {
+        NearestNeighbour { distance, item }
+    }
+}
+
+impl<O: Copy + PartialOrd, T: Content> Neighbour<O, T> for BestNeighbour<O, T> {
+    fn distance(&self) -> O {
+        self.distance
+    }
+    fn item(&self) -> T {
+        self.item
+    }
+}
+
+impl<A, T: Content, const K: usize, O: Copy + PartialOrd> FromLeafEntry<A, T, K, O>
+    for BestNeighbour<O, T>
+{
+    const KEEPS_POINT: bool = false;
+
+    fn from_leaf_entry(distance: O, item: T, _point: &[A; K]) -> Self {
+        BestNeighbour { distance, item }
+    }
+}
+
+impl<A: Copy + PartialEq, T: Content, const K: usize, O: Copy + PartialOrd> Neighbour<O, T>
+    for PointNeighbour<A, T, K, O>
+{
+    fn distance(&self) -> O {
+        self.distance
+    }
+    fn item(&self) -> T {
+        self.item
+    }
+}
+
+impl<A: Copy + PartialEq, T: Content, const K: usize, O: Copy + PartialOrd>
+    FromLeafEntry<A, T, K, O> for PointNeighbour<A, T, K, O>
+{
+    const KEEPS_POINT: bool = true;
+
+    fn from_leaf_entry(distance: O, item: T, point: &[A; K]) -> Self {
+        PointNeighbour {
+            distance,
+            item,
+            point: *point,
+        }
+    }
+}
+
+/// Ranked by item, like [`BestNeighbour`], for the "best" queries that return another
+/// result type
+impl<O, T: Content, N: Neighbour<O, T>> Neighbour<O, T> for ScoredNeighbour<T, N> {
+    fn distance(&self) -> O {
+        self.neighbour.distance()
+    }
+    fn item(&self) -> T {
+        self.neighbour.item()
+    }
+}
+
+impl<A, T: Content, const K: usize, O, N: FromLeafEntry<A, T, K, O>> FromLeafEntry<A, T, K, O>
+    for ScoredNeighbour<T, N>
+{
+    const KEEPS_POINT: bool = N::KEEPS_POINT;
+
+    fn from_leaf_entry(distance: O, item: T, point: &[A; K]) -> Self {
+        ScoredNeighbour {
+            score: item,
+            neighbour: N::from_leaf_entry(distance, item, point),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! A result item returned by a query, along with the point it is stored at
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::types::Content;
+use std::cmp::Ordering;
+
+/// Represents an entry in the results of a query that also returns point coordinates,
+/// with `distance` being the distance of this particular item from the query point,
+/// `item` being the stored item index that was found as part of the query, and
+/// `point` being the coordinates that `item` is stored at in the tree.
+///
+/// `O` is the type of `distance`, which is the
+/// [`Output`](crate::distance_metric::DistanceMetric::Output) of the metric used in
+/// the query, and is usually the same as the co-ordinate type `A`.
+#[derive(Debug, Copy, Clone)]
+pub struct PointNeighbour<A, T, const K: usize, O = A> {
+    /// the distance of the found item from the query point according to the supplied distance metric
+    pub distance: O,
+    /// the stored index of an item that was found in the query
+    pub item: T,
+    /// the point that the found item is stored at
+    pub point: [A; K],
+}
+
+impl<A: PartialEq, T: Content, const K: usize, O: PartialOrd> Ord for PointNeighbour<A, T, K, O> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+}
+
+#[allow(renamed_and_removed_lints)]
+#[allow(unknown_lints)]
+#[allow(clippy::incorrect_partial_ord_impl_on_ord_type)]
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl<A: PartialEq, T: Content, const K: usize, O: PartialOrd> PartialOrd
+    for PointNeighbour<A, T, K, O>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.distance.partial_cmp(&other.distance)
+    }
+}
+
+impl<A: PartialEq, T: Content, const K: usize, O: PartialEq> Eq for PointNeighbour<A, T, K, O> {}
+
+impl<A: PartialEq, T: Content, const K: usize, O: PartialEq> PartialEq
+    for PointNeighbour<A, T, K, O>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance && self.item == other.item && self.point == other.point
+    }
+}
+
+impl<A, T: Content, const K: usize, O> From<PointNeighbour<A, T, K, O>> for (O, T, [A; K]) {
+    fn from(elem: PointNeighbour<A, T, K, O>) -> Self {
+        (elem.distance, elem.item, elem.point)
+    }
+}
+
+impl<A, T: Content, const K: usize, O> From<PointNeighbour<A, T, K, O>> for NearestNeighbour<O, T> {
+    fn from(elem: PointNeighbour<A, T, K, O>) -> Self {
+        NearestNeighbour {
+            distance: elem.distance,
+            item: elem.item,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nearest_neighbour::NearestNeighbour;
+    use crate::point_neighbour::PointNeighbour;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_into_tuple() {
+        let pn: (f32, usize, [f32; 2]) = PointNeighbour {
+            distance: 1.0f32,
+            item: 1usize,
+            point: [0.5f32, 2.0f32],
+        }
+        .into();
+
+        assert_eq!(pn.0, 1.0f32);
+        assert_eq!(pn.1, 1usize);
+        assert_eq!(pn.2, [0.5f32, 2.0f32]);
+    }
+
+    #[test]
+    fn test_into_nearest_neighbour() {
+        let nn: NearestNeighbour<f32, usize> = PointNeighbour {
+            distance: 1.0f32,
+            item: 1usize,
+            point: [0.5f32, 2.0f32],
+        }
+        .into();
+
+        assert_eq!(
+            nn,
+            NearestNeighbour {
+                distance: 1.0f32,
+                item: 1usize
+            }
+        );
+    }
+
+    #[test]
+    fn test_partial_cmp() {
+        let a = PointNeighbour {
+            distance: 1.0f32,
+            item: 10usize,
+            point: [1.0f32],
+        };
+        let b = PointNeighbour {
+            distance: 2.0f32,
+            item: 5usize,
+            point: [2.0f32],
+        };
+
+        assert_eq!(a.partial_cmp(&b).unwrap(), Ordering::Less)
+    }
+}
@@ -0,0 +1,69 @@
+//! The trait that needs to be implemented by any region of space
+//! that is to be used with `within_region` queries
+
+/// Trait that needs to be implemented by any region of space (such as a
+/// half-space, convex polytope, view frustum or capsule) that is to be
+/// used as the target of a `within_region` query.
+///
+/// During a query, each node of the tree is described by the axis-aligned
+/// box spanning from `min` to `max` that bounds every point stored beneath it.
+/// Nodes whose box does not intersect the region are pruned, nodes whose box is
+/// entirely contained by the region have all of their items returned without
+/// any further tests, and any remaining points are tested individually.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::KdTree;
+/// use kiddo::query_region::QueryRegion;
+///
+/// /// All points with an `x` co-ordinate greater than or equal to `threshold`
+/// struct RightOf {
+///     threshold: f64,
+/// }
+///
+/// impl QueryRegion<f64, 2> for RightOf {
+///     fn intersects_box(&self, _min: &[f64; 2], max: &[f64; 2]) -> bool {
+///         max[0] >= self.threshold
+///     }
+///
+///     fn contains_box(&self, min: &[f64; 2], _max: &[f64; 2]) -> bool {
+///         min[0] >= self.threshold
+///     }
+///
+///     fn contains_point(&self, point: &[f64; 2]) -> bool {
+///         point[0] >= self.threshold
+///     }
+/// }
+///
+/// let mut tree: KdTree<f64, 2> = KdTree::new();
+/// tree.add(&[1.0, 2.0], 100);
+/// tree.add(&[5.0, 3.0], 101);
+///
+/// let within = tree.within_region(&RightOf { threshold: 3.0 });
+///
+/// assert_eq!(within, vec![101]);
+/// ```
+pub trait QueryRegion<A, const K: usize> {
+    /// returns `true` if any part of the axis-aligned box spanning
+    /// from `min` to `max` could lie within the region.
+    ///
+    /// It is fine for this to return `true` for some boxes that do not
+    /// actually intersect the region (this just results in less pruning),
+    /// but it must never return `false` for a box that does.
+    fn intersects_box(&self, min: &[A; K], max: &[A; K]) -> bool;
+
+    /// returns `true` if the whole of the axis-aligned box spanning
+    /// from `min` to `max` lies within the region.
+    ///
+    /// It is fine for this to return `false` for some boxes that are
+    /// actually contained (this just results in more per-point tests),
+    /// but it must never return `true` for a box that is not. The
+    /// default implementation always returns `false`.
+    fn contains_box(&self, _min: &[A; K], _max: &[A; K]) -> bool {
+        false
+    }
+
+    /// returns `true` if `point` lies within the region
+    fn contains_point(&self, point: &[A; K]) -> bool;
+}
@@ -0,0 +1,211 @@
+//! Collections that queries can add their results to
+//!
+//! Implementing [`ResultCollection`] for a type of your own lets a query such as
+//! `nearest_n_within_into_collection` keep its results however suits the caller,
+//! for example in a fixed size array that never allocates.
+use crate::nearest_neighbour::NearestNeighbour;
+use crate::neighbour::Neighbour;
+use crate::types::Content;
+use sorted_vec::SortedVec;
+use std::collections::BinaryHeap;
+
+/// A collection of query results, each with a distance of type `A` and an item of type `T`.
+///
+/// The query calls [`add`](Self::add) with every item that it finds within range, and
+/// can use [`max_dist`](Self::max_dist) to skip the parts of the tree that could not
+/// contain an item that the collection would keep.
+///
+/// Implemented for `Vec`, which keeps every result, and for `BinaryHeap` and
+/// `SortedVec`, which keep the closest results up to their capacity.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::KdTree;
+/// use kiddo::SquaredEuclidean;
+/// use kiddo::nearest_neighbour::NearestNeighbour;
+/// use kiddo::result_collection::ResultCollection;
+///
+/// // keeps only the single closest result, without allocating
+/// struct Closest(Option<NearestNeighbour<f64, u64>>);
+///
+/// impl ResultCollection<f64, u64> for Closest {
+///     fn new_with_capacity(_capacity: usize) -> Self {
+///         Closest(None)
+///     }
+///     fn add(&mut self, entry: NearestNeighbour<f64, u64>) {
+///         if self.0.map_or(true, |closest| entry < closest) {
+///             self.0 = Some(entry);
+///         }
+///     }
+///     fn max_dist(&self) -> Option<f64> {
+///         self.0.map(|closest| closest.distance)
+///     }
+///     fn into_vec(self) -> Vec<NearestNeighbour<f64, u64>> {
+///         self.0.into_iter().collect()
+///     }
+///     fn into_sorted_vec(self) -> Vec<NearestNeighbour<f64, u64>> {
+///         self.into_vec()
+///     }
+/// }
+///
+/// let mut tree: KdTree<f64, 3> = KdTree::new();
+/// tree.add(&[1.0, 2.0, 5.0], 100);
+/// tree.add(&[2.0, 3.0, 6.0], 101);
+///
+/// let mut closest = Closest(None);
+/// tree.nearest_n_within_into_collection::<SquaredEuclidean, _>(&[1.0, 2.0, 5.1], 10f64, &mut closest);
+///
+/// assert_eq!(closest.0.unwrap().item, 100);
+/// ```
+pub trait ResultCollection<A: Copy + PartialOrd, T: Content, N = NearestNeighbour<A, T>> {
+    /// Creates an empty collection. Collections that keep a limited number of
+    /// results keep up to `capacity` of them
+    fn new_with_capacity(capacity: usize) -> Self;
+    /// Offers a result to the collection, which can either keep or discard it
+    fn add(&mut self, entry: N);
+    /// the distance of the worst result if the collection is full, ie the distance
+    /// that a new entry needs to beat to be added, or `None` if any entry can be added
+    fn max_dist(&self) -> Option<A>;
+    /// Returns the results in arbitrary order
+    fn into_vec(self) -> Vec<N>;
+    /// Returns the results sorted nearest-first
+    fn into_sorted_vec(self) -> Vec<N>;
+}
+
+impl<A: Copy + PartialOrd, T: Content, N: Neighbour<A, T>> ResultCollection<A, T, N>
+    for BinaryHeap<N>
+{
+    fn new_with_capacity(capacity: usize) -> Self {
+        BinaryHeap::with_capacity(capacity)
+    }
+    fn add(&mut self, entry: N) {
+        let k = self.capacity();
+        if self.len() < k {
+            self.push(entry);
+        } else {
+            let mut max_heap_value = self.peek_mut().unwrap();
+            if entry < *max_heap_value {
+                *max_heap_value = entry;
+            }
+        }
+    }
+    fn max_dist(&self) -> Option<A> {
+        if self.len() < self.capacity() {
+            None
+        } else {
+            self.peek().map(|n| n.distance())
+        }
+    }
+    fn into_vec(self) -> Vec<N> {
+        BinaryHeap::into_vec(self)
+    }
+    fn into_sorted_vec(self) -> Vec<N> {
+        BinaryHeap::into_sorted_vec(self)
+    }
+}
+
+/// A max-heap holding at most `max_qty` results. Unlike a plain `BinaryHeap`,
+/// the limit does not depend on the heap's capacity, which allows a caller-supplied
+/// allocation of any size to be reused as its backing storage.
+pub(crate) struct BoundedHeap<A: Copy + PartialOrd, T: Content> {
+    heap: BinaryHeap<NearestNeighbour<A, T>>,
+    max_qty: usize,
+}
+
+impl<A: Copy + PartialOrd, T: Content> BoundedHeap<A, T> {
+    pub(crate) fn from_vec(vec: Vec<NearestNeighbour<A, T>>, max_qty: usize) -> Self {
+        BoundedHeap {
+            heap: BinaryHeap::from(vec),
+            max_qty,
+        }
+    }
+}
+
+impl<A: Copy + PartialOrd, T: Content> ResultCollection<A, T> for BoundedHeap<A, T> {
+    fn new_with_capacity(capacity: usize) -> Self {
+        BoundedHeap {
+            heap: BinaryHeap::with_capacity(capacity),
+            max_qty: capacity,
+        }
+    }
+    fn add(&mut self, entry: NearestNeighbour<A, T>) {
+        if self.heap.len() < self.max_qty {
+            self.heap.push(entry);
+        } else if let Some(mut max_heap_value) = self.heap.peek_mut() {
+            if entry < *max_heap_value {
+                *max_heap_value = entry;
+            }
+        }
+    }
+    fn max_dist(&self) -> Option<A> {
+        if self.heap.len() < self.max_qty {
+            None
+        } else {
+            self.heap.peek().map(|n| n.distance)
+        }
+    }
+    fn into_vec(self) -> Vec<NearestNeighbour<A, T>> {
+        self.heap.into_vec()
+    }
+    fn into_sorted_vec(self) -> Vec<NearestNeighbour<A, T>> {
+        self.heap.into_sorted_vec()
+    }
+}
+
+impl<A: Copy + PartialOrd, T: Content, N: Neighbour<A, T>> ResultCollection<A, T, N> for Vec<N> {
+    fn new_with_capacity(capacity: usize) -> Self {
+        Vec::with_capacity(capacity)
+    }
+
+    fn add(&mut self, entry: N) {
+        self.push(entry)
+    }
+
+    fn max_dist(&self) -> Option<A> {
+        None
+    }
+
+    fn into_vec(self) -> Vec<N> {
+        self
+    }
+
+    fn into_sorted_vec(mut self) -> Vec<N> {
+        self.sort();
+        self
+    }
+}
+
+impl<A: Copy + PartialOrd, T: Content, N: Neighbour<A, T>> ResultCollection<A, T, N>
+    for SortedVec<N>
+{
+    fn new_with_capacity(capacity: usize) -> Self {
+        SortedVec::with_capacity(capacity)
+    }
+
+    fn add(&mut self, entry: N) {
+        let len = self.len();
+        if len < self.capacity() {
+            self.insert(entry);
+        } else if self.last().is_some_and(|last| entry < *last) {
+            self.pop();
+            self.push(entry);
+        }
+    }
+
+    fn max_dist(&self) -> Option<A> {
+        if self.len() < self.capacity() {
+            None
+        } else {
+            self.last().map(|n| n.distance())
+        }
+    }
+
+    fn into_vec(self) -> Vec<N> {
+        self.into_vec()
+    }
+
+    fn into_sorted_vec(self) -> Vec<N> {
+        self.into_vec()
+    }
+}
@@ -0,0 +1,129 @@
+//! Limits on the amount of work done by `nearest_one_budgeted` and `nearest_n_budgeted` queries
+
+/// The maximum amount of work that a budgeted nearest neighbour query is allowed to do.
+///
+/// Similar to the "checks" parameter of FLANN, this allows a hard ceiling to be placed
+/// on query latency in exchange for a result that may not be exact. The query descends
+/// towards the leaf nearest the query point first, so the closest candidates tend to be
+/// found early, with any remaining budget being spent on checking the other subtrees
+/// that could contain closer items.
+///
+/// Every stem that gets visited uses up one of `max_stems`, and every leaf that gets
+/// searched uses up one of `max_leaves`. Once either is exhausted, the query stops
+/// and returns the best results found so far.
+///
+/// # Examples
+///
+/// ```rust
+/// use kiddo::KdTree;
+/// use kiddo::SquaredEuclidean;
+/// use kiddo::search_budget::SearchBudget;
+///
+/// let mut tree: KdTree<f64, 3> = KdTree::new();
+/// tree.add(&[1.0, 2.0, 5.0], 100);
+/// tree.add(&[2.0, 3.0, 6.0], 101);
+///
+/// let nearest = tree.nearest_one_budgeted::<SquaredEuclidean>(
+///     &[1.0, 2.0, 5.1],
+///     SearchBudget::with_max_leaves(8),
+/// );
+///
+/// assert!(nearest.exact);
+/// assert_eq!(nearest.result.unwrap().item, 100);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SearchBudget {
+    /// the maximum number of leaves whose contents will be checked
+    pub max_leaves: usize,
+    /// the maximum number of stems that will be visited
+    pub max_stems: usize,
+}
+
+impl SearchBudget {
+    /// Creates a `SearchBudget` that limits both the number of leaves and stems visited
+    pub fn new(max_leaves: usize, max_stems: usize) -> Self {
+        SearchBudget {
+            max_leaves,
+            max_stems,
+        }
+    }
+
+    /// Creates a `SearchBudget` that only limits the number of leaves visited
+    pub fn with_max_leaves(max_leaves: usize) -> Self {
+        SearchBudget {
+            max_leaves,
+            max_stems: usize::MAX,
+        }
+    }
+
+    /// Creates a `SearchBudget` that never runs out, giving the same results as an unbudgeted query
+    pub fn unlimited() -> Self {
+        SearchBudget {
+            max_leaves: usize::MAX,
+            max_stems: usize::MAX,
+        }
+    }
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// The result of a budgeted query, along with whether the search was able to complete
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetedResult<R> {
+    /// the best result(s) that were found within the budget
+    pub result: R,
+    /// `true` if the search completed before the budget ran out, in which case `result`
+    /// is the same as would have been returned by the unbudgeted query. `false` if some
+    /// parts of the tree that could have contained better results were not searched.
+    pub exact: bool,
+}
+
+/// Tracks the budget remaining during a budgeted query
+pub(crate) struct BudgetTracker {
+    leaves_remaining: usize,
+    stems_remaining: usize,
+    pub(crate) truncated: bool,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(budget: SearchBudget) -> Self {
+        BudgetTracker {
+            leaves_remaining: budget.max_leaves,
+            stems_remaining: budget.max_stems,
+            truncated: false,
+        }
+    }
+
+    /// returns `true` and uses up one stem from the budget if any remain
+    #[inline]
+    pub(crate) fn visit_stem(&mut self) -> bool {
+        if self.stems_remaining == 0 {
+            self.truncated = true;
+            return false;
+        }
+        self.stems_remaining -= 1;
+        true
+    }
+
+    /// returns `true` and uses up one leaf from the budget if any remain
+    #[inline]
+    pub(crate) fn visit_leaf(&mut self) -> bool {
+        if self.leaves_remaining == 0 {
+            self.truncated = true;
+            return false;
+        }
+        self.leaves_remaining -= 1;
+        true
+    }
+
+    pub(crate) fn into_result<R>(self, result: R) -> BudgetedResult<R> {
+        BudgetedResult {
+            result,
+            exact: !self.truncated,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//! Iterator object returned by within_unsorted_iter
+//! Iterator objects returned by within_unsorted_iter and within_box_unsorted_iter
 use crate::nearest_neighbour::NearestNeighbour;
 use generator::Generator;
 
@@ -18,3 +18,20 @@ impl<'a, A, T> Iterator for WithinUnsortedIter<'a, A, T> {
         self.0.next()
     }
 }
+
+/// Iterator object returned by within_box_unsorted_iter
+pub struct WithinBoxUnsortedIter<'a, T>(Generator<'a, (), T>);
+
+impl<'a, T> WithinBoxUnsortedIter<'a, T> {
+    pub(crate) fn new(gen: Generator<'a, (), T>) -> Self {
+        WithinBoxUnsortedIter(gen)
+    }
+}
+
+impl<'a, T> Iterator for WithinBoxUnsortedIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}